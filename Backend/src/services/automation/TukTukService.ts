@@ -11,7 +11,7 @@ import {
 import fs from "fs";
 import path from "path";
 import crypto from "crypto";
-import {compileTransaction} from "@helium/tuktuk-sdk";
+import {compileTransaction, customSignerKey} from "@helium/tuktuk-sdk";
 import {cronJobTransactionKey, getCronJobForName, init as initCron} from "@helium/cron-sdk";
 import {
   clientAddress,
@@ -131,25 +131,66 @@ export class TukTukService {
   }
 
   /**
-   * Updates cron transaction with new requestAccountAddress
+   * Recompiles the cron transaction for its next run: rollover of the current round, then the
+   * draw of the front of the pending queue, the current round when it is empty. Called after
+   * every rollover, randomness request and callback, the rounds change with each
    */
-  public async updateCronJobAfterRequestsCompleted() {
+  public async updateCronJob() {
     await this.ensureInitialized();
 
     const programId = new PublicKey(this.config!.programId);
     const numberOfRandomnessAccounts = this.config!.numberOfRandomnessAccounts;
     const cronJobName = this.config!.cronJobName;
-    const skipPreflight = this.config!.skipPreflight;
 
     logger.info(`Updating cron job "${cronJobName}" with ${numberOfRandomnessAccounts} randomness accounts`);
 
+    const cronProgram = await initCron(this.provider!);
+    const cronJob = await getCronJobForName(cronProgram, cronJobName);
+
+    if (!cronJob) {
+      throw new Error(`Cron job named "${cronJobName}" not found`);
+    }
+
+    logger.info(`Found cron job: ${cronJob.toBase58()}`);
+
+    // --- Keeper signed by TukTuk, receives the keeper tip ---
+    const cronJobAccount = await cronProgram.account.cronJobV0.fetch(cronJob);
+    const keeperSeeds = [Buffer.from("keeper")];
+    const [keeperPDA] = customSignerKey(cronJobAccount.taskQueue, keeperSeeds);
+
+    logger.info(`Keeper: ${keeperPDA.toBase58()}, must be in the KeeperRegistry while the allowlist is on`);
+
     // --- PDA calculation ---
     const [raffleStatePDA] = PublicKey.findProgramAddressSync(
       [Buffer.from("raffle_state")],
       programId
     );
     const [solRafflePDA] = PublicKey.findProgramAddressSync(
-      [Buffer.from("sol_raffle")],
+      [Buffer.from("sol_raffle"), Buffer.from([0])],
+      programId
+    );
+    const [pendingQueuePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("pending_queue"), solRafflePDA.toBuffer()],
+      programId
+    );
+    const [vaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("sol_vault")],
+      programId
+    );
+    const [vaultLedgerPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_ledger")],
+      programId
+    );
+    const [insuranceVaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance_vault")],
+      programId
+    );
+    const [megaJackpotPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mega_jackpot")],
+      programId
+    );
+    const [clusterProfilePDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("cluster_profile")],
       programId
     );
     const [vrfFeeVaultPDA] = PublicKey.findProgramAddressSync(
@@ -161,6 +202,65 @@ export class TukTukService {
       programId
     );
 
+    const [rentVaultPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("rent_vault")],
+      programId
+    );
+
+    // --- Rollover, rounds are only created here ---
+    const solRaffle: any = await this.program!.account.tokenRaffle.fetch(solRafflePDA);
+    const currentRoundId: number | null = solRaffle.currentRoundId ?? null;
+    const nextRoundId = currentRoundId === null ? 0 : currentRoundId + 1;
+
+    const [nextRoundPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("round"), solRafflePDA.toBuffer(), this.u32ToBuffer(nextRoundId)],
+      programId
+    );
+    const [nextRoundTicketsPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("round_tickets"), nextRoundPDA.toBuffer()],
+      programId
+    );
+
+    const rolloverIx = await this.program!.methods
+      .rollover(currentRoundId ?? 0, nextRoundId)
+      .accounts({
+        raffleState: raffleStatePDA,
+        solRaffle: solRafflePDA,
+        pendingQueue: pendingQueuePDA,
+        nextRound: nextRoundPDA,
+        nextRoundTickets: nextRoundTicketsPDA,
+        rentVault: rentVaultPDA,
+        keeper: keeperPDA,
+        keeperRegistry: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    logger.info(`Next rollover: round ${currentRoundId} -> ${nextRoundId}`);
+
+    if (currentRoundId === null) {
+      logger.info("No round yet, the cron job opens the first one");
+      await this.replaceCronTransaction(cronProgram, cronJob, [rolloverIx], keeperSeeds);
+      return;
+    }
+
+    // --- Round to draw ---
+    const roundId = await this.roundToDraw(pendingQueuePDA, currentRoundId);
+
+    const [roundPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("round"), solRafflePDA.toBuffer(), this.u32ToBuffer(roundId)],
+      programId
+    );
+
+    logger.info(`Next draw: round ${roundId} (${roundPDA.toBase58()})`);
+
+    // Optional accounts, passed once they exist
+    const connection = this.provider!.connection;
+    const [clusterProfileInfo, megaJackpotInfo] = await connection.getMultipleAccountsInfo([
+      clusterProfilePDA,
+      megaJackpotPDA,
+    ]);
+
     // --- ORAO client & network state ---
     const [clientPDA] = clientAddress(programId, clientStatePDA);
     const vrf = new OraoCb(this.provider!);
@@ -194,12 +294,21 @@ export class TukTukService {
         .accounts({
           raffleState: raffleStatePDA,
           solRaffle: solRafflePDA,
+          pendingQueue: pendingQueuePDA,
+          round: roundPDA,
+          vault: vaultPDA,
+          vaultLedger: vaultLedgerPDA,
+          keeper: keeperPDA,
+          keeperRegistry: null,
           vrfFeeVault: vrfFeeVaultPDA,
           vrf: vrf.programId,
           clientState: clientStatePDA,
           client: clientPDA,
           networkState: NetworkState.createAddress(networkState.bump)[0],
           treasury: treasuryPubkey,
+          clusterProfile: clusterProfileInfo ? clusterProfilePDA : null,
+          insuranceVault: insuranceVaultPDA,
+          megaJackpot: megaJackpotInfo ? megaJackpotPDA : null,
           systemProgram: SystemProgram.programId,
         })
         .remainingAccounts(randomnessAccounts.map(a => ({
@@ -215,18 +324,20 @@ export class TukTukService {
       throw new Error(`Error creating instruction: ${err?.message ?? err}`);
     }
 
-    // --- Compiling transaction ---
-    const { transaction, remainingAccounts } = compileTransaction([performTaskIx], []);
-    logger.info("Transaction compiled successfully");
-
-    const cronProgram = await initCron(this.provider!);
-    const cronJob = await getCronJobForName(cronProgram, cronJobName);
+    await this.replaceCronTransaction(cronProgram, cronJob, [rolloverIx, performTaskIx], keeperSeeds);
+  }
 
-    if (!cronJob) {
-      throw new Error(`Cron job named "${cronJobName}" not found`);
-    }
+  private async replaceCronTransaction(
+    cronProgram: any,
+    cronJob: PublicKey,
+    instructions: TransactionInstruction[],
+    keeperSeeds: Buffer[]
+  ) {
+    const skipPreflight = this.config!.skipPreflight;
 
-    logger.info(`Found cron job: ${cronJob.toBase58()}`);
+    // --- Compiling transaction ---
+    const { transaction, remainingAccounts } = compileTransaction(instructions, [keeperSeeds]);
+    logger.info("Transaction compiled successfully");
 
     // --- Deleting an existing transaction ---
     try {
@@ -262,6 +373,25 @@ export class TukTukService {
     logger.info(`Cron transaction added successfully. Signature: ${addTxSignature}`);
   }
 
+  /**
+   * The round request_randomness will take: the front of the pending queue, else the current
+   * round the rollover queues
+   */
+  private async roundToDraw(pendingQueuePDA: PublicKey, currentRoundId: number): Promise<number> {
+    const pendingQueue: any = await this.program!.account.pendingQueue.fetch(pendingQueuePDA);
+    if (pendingQueue.len > 0) {
+      return pendingQueue.rounds[pendingQueue.head];
+    }
+
+    return currentRoundId;
+  }
+
+  private u32ToBuffer(value: number): Buffer {
+    const buffer = Buffer.alloc(4);
+    buffer.writeUInt32LE(value);
+    return buffer;
+  }
+
   private buf32ToNumberArray(b: Buffer | Uint8Array): number[] {
     if (b.length !== 32) {
       throw new Error("seed must be 32 bytes");
@@ -94,6 +94,7 @@ export class BlockchainService {
   private notificationService: NotificationService;
   private statisticsService: StatisticsService;
   private tukTukService: TukTukService;
+  private lastCronJobUpdateSignature?: string;
 
   constructor() {
     // Initialize repositories
@@ -1133,18 +1134,17 @@ export class BlockchainService {
             events,
             isHistorical
           );
+          if (!isHistorical) {
+            await this.processDrawQueueChanged(signature, block, blockchainName, events);
+          }
           break;
 
+        case 'rollover':
         case 'request_randomness':
           if (!isHistorical) {
-            await this.processAllRequestsCompletedEvent(
-              signature,
-              block,
-              blockchainName,
-              events
-            );
+            await this.processDrawQueueChanged(signature, block, blockchainName, events);
           } else {
-            logger.debug(`Skipping request_randomness...`);
+            logger.debug(`Skipping ${decodedIx.name}...`);
           }
           break;
 
@@ -1635,7 +1635,7 @@ export class BlockchainService {
       );
 
       const [solRafflePDA] = PublicKey.findProgramAddressSync(
-        [Buffer.from('sol_raffle')],
+        [Buffer.from('sol_raffle'), Buffer.from([0])],
         programId
       );
 
@@ -1654,6 +1654,16 @@ export class BlockchainService {
         purchaseIndex
       );
 
+      const [auditLogPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from('audit_log')],
+        programId
+      );
+
+      const [recordsPDA] = PublicKey.findProgramAddressSync(
+        [Buffer.from('records')],
+        programId
+      );
+
       logger.debug(`[${blockchainName}] [SET_WINNER] Raffle State PDA: ${raffleStatePDA.toBase58()}`);
       logger.debug(`[${blockchainName}] [SET_WINNER] Sol Raffle PDA: ${solRafflePDA.toBase58()}`);
       logger.debug(`[${blockchainName}] [SET_WINNER] Round PDA: ${roundPDA.toBase58()}`);
@@ -1668,6 +1678,8 @@ export class BlockchainService {
           round: roundPDA,
           roundTicketsPurchase: purchasePDA,
           authority: authorityKeypair.publicKey,
+          auditLog: auditLogPDA,
+          records: recordsPDA,
         })
         .signers([authorityKeypair])
         .rpc();
@@ -1692,34 +1704,38 @@ export class BlockchainService {
     }
   }
 
-  private async processAllRequestsCompletedEvent(
+  // The cron transaction names the round it draws, it is recompiled whenever that changes
+  private async processDrawQueueChanged(
     signature: string,
     block: number,
     blockchainName: string,
     events: any[]
   ) {
-    try {
-      const allRequestsCompletedEvent = events.find(e => e.name === 'AllRequestsCompleted');
+    if (this.lastCronJobUpdateSignature === signature) {
+      logger.debug(`TukTuk cron job already updated for ${signature}`);
+      return;
+    }
+    this.lastCronJobUpdateSignature = signature;
 
-      if (!allRequestsCompletedEvent) {
-        logger.warn(`AllRequestsCompleted event not found in transaction ${signature}`);
-        return;
+    try {
+      if (events.some(e => e.name === 'AllRequestsCompleted')) {
+        logger.info(`All randomness accounts used in ${signature}`);
       }
 
       const programId = this.solanaProgramId[blockchainName];
       const contractAddress = programId.toBase58();
 
       logger.debug('Triggering TukTuk cron job update...');
-      await this.tukTukService.updateCronJobAfterRequestsCompleted();
+      await this.tukTukService.updateCronJob();
       logger.debug(`TukTuk cron job updated successfully!`);
 
       await this.updateLastSyncedBlock(blockchainName, contractAddress, block);
 
     } catch (error) {
       if (error instanceof QueryFailedError && error.driverError.code === '23505') {
-        logger.info(`Duplicate Solana AllRequestsCompleted event ignored: ${signature}`);
+        logger.info(`Duplicate Solana draw queue update ignored: ${signature}`);
       } else {
-        logger.error(`Error processing Solana AllRequestsCompleted event ${signature}:`, error);
+        logger.error(`Error updating the TukTuk cron job after ${signature}:`, error);
         throw error;
       }
     }
@@ -7,8 +7,11 @@ import { u32ToLittleEndianBuffer } from "utils/buffer";
 
 const PROGRAM_ID = new PublicKey(RAFFLE_PROGRAM_ADDRESS);
 
-const getSolRafflePda = () =>
-  PublicKey.findProgramAddressSync([Buffer.from("sol_raffle")], new PublicKey(PROGRAM_ID))[0];
+const getSolRafflePda = (cadenceId = 0) =>
+  PublicKey.findProgramAddressSync(
+    [Buffer.from("sol_raffle"), Buffer.from([cadenceId])],
+    new PublicKey(PROGRAM_ID),
+  )[0];
 
 const getRoundPda = (roundId: number) => {
   const solRaffle = getSolRafflePda();
@@ -0,0 +1,150 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::{
+    Round,
+    RoundStatus,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateTokenRaffle,
+};
+use crate::attestation::verify_attestation;
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::blocklist::{require_not_blocked, Blocklist};
+
+// Only authority. Merkle root of the wallets allowed into rounds opened from now on, None - public
+pub fn set_raffle_access_list(ctx: Context<UpdateTokenRaffle>, access_root: Option<[u8; 32]>) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.access_root = access_root;
+
+    emit!(AccessListUpdated {
+        token_raffle: sol_raffle.key(),
+        round_id: None,
+        access_root,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::AccessListChanged,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Gates or opens a single round, only before its first purchase
+pub fn set_round_access_list(
+    ctx: Context<SetRoundAccessList>,
+    round_id: u32,
+    access_root: Option<[u8; 32]>,
+) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require!(round.purchases_count == 0, RaffleError::RoundHasPurchases);
+
+    round.access_root = access_root;
+
+    emit!(AccessListUpdated {
+        token_raffle: ctx.accounts.sol_raffle.key(),
+        round_id: Some(round_id),
+        access_root,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::AccessListChanged,
+        round_id as u64,
+    )?;
+
+    Ok(())
+}
+
+// Leaves are keccak(wallet), pairs are hashed in sorted order so proofs carry no directions
+pub fn verify_access(access_root: Option<[u8; 32]>, wallet: &Pubkey, proof: &[[u8; 32]]) -> Result<()> {
+    let Some(access_root) = access_root else {
+        return Ok(());
+    };
+
+    let mut node = keccak::hashv(&[wallet.as_ref()]).to_bytes();
+    for sibling in proof {
+        node = if node <= *sibling {
+            keccak::hashv(&[&node, sibling]).to_bytes()
+        } else {
+            keccak::hashv(&[sibling, &node]).to_bytes()
+        };
+    }
+
+    require!(node == access_root, RaffleError::NotOnAccessList);
+
+    Ok(())
+}
+
+// Every wallet getting tickets in a round, bought, granted, redeemed, rolled over or transferred,
+// passes the gates buy_tickets_sol applies: the blocklist, the round's access list and the
+// raffle's attestation requirement
+pub fn require_entry_allowed(
+    raffle_state: &RaffleState,
+    sol_raffle: &TokenRaffle,
+    round: &Round,
+    blocklist: Option<&Account<Blocklist>>,
+    attestation: Option<&UncheckedAccount>,
+    wallet: &Pubkey,
+    access_proof: &[[u8; 32]],
+) -> Result<()> {
+    require_not_blocked(raffle_state, blocklist, wallet)?;
+    verify_access(round.access_root, wallet, access_proof)?;
+    verify_attestation(
+        sol_raffle,
+        attestation.map(|attestation| attestation.as_ref()),
+        wallet,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct SetRoundAccessList<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Events
+#[event]
+pub struct AccessListUpdated {
+    pub token_raffle: Pubkey,
+    pub round_id: Option<u32>, // None - default for new rounds
+    pub access_root: Option<[u8; 32]>,
+}
@@ -1,15 +1,43 @@
 use anchor_lang::prelude::*;
 use std::collections::HashMap;
 use crate::{
+    CommissionMode,
+    PriceDecayMode,
+    PriceSampleMode,
+    PricingMode,
     Round,
     RoundStatus,
+    RaffleError,
     RaffleState,
+    RoundTickets,
+    RoundTicketsPurchase,
     TokenRaffle,
 };
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::commission::FeeTier;
+use crate::cluster_profile::{check_cluster_sol_feed, ClusterProfile};
+use crate::oracle_failover::{FeedReader, FeedFailover};
+use crate::price_cache::{require_price_cache, PriceCache};
+use crate::schedule::is_valid_round_duration;
+use crate::jackpot::MegaJackpot;
+use crate::solvency::ledger_outstanding;
 use crate::price_feeds::{
+    apply_price_decay,
+    calculate_fixed_ticket_price,
+    calculate_round_ticket_price,
     calculate_ticket_price_for_sol,
-    TICKET_BTC_SATOSHIS,
+    price_breakdown,
+    quote_decimals,
+    require_feed_registry,
+    FeedRegistry,
+    ticket_peg,
+    PegComponent,
+    PriceSampling,
 };
+
+// Return data is capped at 1024 bytes
+pub const MAX_PURCHASES_VIEW_ENTRIES: u8 = 19;
+pub const MAX_ROUND_ADDRESSES_VIEW_ENTRIES: u8 = 15;
     
 pub fn get_raffle_state(ctx: Context<GetRaffleState>) -> Result<RaffleStateView> {
     let state = &ctx.accounts.raffle_state;
@@ -20,6 +48,28 @@ pub fn get_raffle_state(ctx: Context<GetRaffleState>) -> Result<RaffleStateView>
         beneficiary: state.beneficiary,
         created_at: state.created_at,
         vrf_request_counter: state.vrf_request_counter,
+        vesting_threshold: state.vesting_threshold,
+        vesting_cliff_seconds: state.vesting_cliff_seconds,
+        vesting_duration_seconds: state.vesting_duration_seconds,
+        operator: state.operator,
+        insurance_bps: state.insurance_bps,
+        jackpot_bps: state.jackpot_bps,
+        maintenance_start: state.maintenance_start,
+        maintenance_end: state.maintenance_end,
+        max_price_deviation_bps: state.max_price_deviation_bps,
+        watchtower: state.watchtower,
+        pending_authority: state.pending_authority,
+        keeper_tip: state.keeper_tip,
+        keeper_allowlist_enabled: state.keeper_allowlist_enabled,
+        randomness_cooldown_seconds: state.randomness_cooldown_seconds,
+        max_randomness_requests: state.max_randomness_requests,
+        ticket_price_satoshis: state.ticket_price_satoshis,
+        ticket_peg: state.ticket_peg.clone(),
+        blocklist_enabled: state.blocklist_enabled,
+        max_price_confidence_bps: state.max_price_confidence_bps,
+        cluster_profile_enabled: state.cluster_profile_enabled,
+        price_cache_enabled: state.price_cache_enabled,
+        feed_registry_enabled: state.feed_registry_enabled,
     })
 }
 
@@ -33,17 +83,197 @@ pub fn get_token_raffle(ctx: Context<GetTokenRaffle>) -> Result<TokenRaffleView>
         current_round_status: raffle.current_round_status.clone(),
         current_round_end_time: raffle.current_round_end_time,
         total_rounds: raffle.total_rounds,
-        pending_rounds: raffle.pending_rounds.clone(),
+        streak_bonus_every: raffle.streak_bonus_every,
+        streak_bonus_tickets: raffle.streak_bonus_tickets,
+        first_ticket_bonus_enabled: raffle.first_ticket_bonus_enabled,
+        first_ticket_bonus_tickets: raffle.first_ticket_bonus_tickets,
+        first_ticket_bonus_every: raffle.first_ticket_bonus_every,
+        volume_bonus_per: raffle.volume_bonus_per,
+        volume_bonus_tickets: raffle.volume_bonus_tickets,
+        price_decay_mode: raffle.price_decay_mode.clone(),
+        price_decay_start_bps: raffle.price_decay_start_bps,
+        price_decay_end_bps: raffle.price_decay_end_bps,
+        price_decay_steps: raffle.price_decay_steps,
+        price_sample_mode: raffle.price_sample_mode.clone(),
+        price_sample_count: raffle.price_sample_count,
+        cancel_penalty_bps: raffle.cancel_penalty_bps,
+        commission_mode: raffle.commission_mode.clone(),
+        staking_enabled: raffle.staking_enabled,
+        staking_yield_to_insurance: raffle.staking_yield_to_insurance,
+        price_feed: raffle.price_feed,
+        token_decimals: raffle.token_decimals,
+        fee_percentage: raffle.fee_percentage,
+        fee_tiers: raffle.fee_tiers.clone(),
+        max_purchases_per_wallet: raffle.max_purchases_per_wallet,
+        min_tickets_per_purchase: raffle.min_tickets_per_purchase,
+        min_purchase_lamports: raffle.min_purchase_lamports,
+        prize_target_lamports: raffle.prize_target_lamports,
+        prize_cap_lamports: raffle.prize_cap_lamports,
+        carryover: raffle.carryover,
+        min_prize_lamports: raffle.min_prize_lamports,
+        house_ticket_threshold: raffle.house_ticket_threshold,
+        house_tickets: raffle.house_tickets,
+        redraw_after_seconds: raffle.redraw_after_seconds,
+        access_root: raffle.access_root,
+        attestation_required: raffle.attestation_required,
+        attestation_credential: raffle.attestation_credential,
+        attestation_schema: raffle.attestation_schema,
+        pricing_mode: raffle.pricing_mode.clone(),
+        fixed_ticket_price_usd: raffle.fixed_ticket_price_usd,
+        commit_round_price: raffle.commit_round_price,
+        loyalty_points_per_sol: raffle.loyalty_points_per_sol,
+        loyalty_points_per_ticket: raffle.loyalty_points_per_ticket,
+        cadence_id: raffle.cadence_id,
+        round_duration: raffle.round_duration,
+        mega_round_weekday: raffle.mega_round_weekday,
+        mega_round_fee_percentage: raffle.mega_round_fee_percentage,
+        mega_round_duration: raffle.mega_round_duration,
+        mega_round_jackpot_bps: raffle.mega_round_jackpot_bps,
+        sales_paused: raffle.sales_paused,
     })
 }
 
-pub fn get_raffle_round_result(ctx: Context<GetRoundAccounts>, round_id: u32) -> Result<RoundResultView> {
+pub fn set_round_duration(ctx: Context<UpdateTokenRaffle>, round_duration: i64) -> Result<()> {
+    require!(
+        is_valid_round_duration(round_duration),
+        RaffleError::InvalidRoundDuration
+    );
 
-    let round = &ctx.accounts.round;
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    let old_duration = sol_raffle.round_duration;
+    sol_raffle.round_duration = round_duration;
 
-    let mut players_map: std::collections::HashMap<Pubkey, RoundPlayerDataWithAddress> = HashMap::new();
+    msg!("✅ Cadence {} round duration set to {}s", sol_raffle.cadence_id, round_duration);
 
-    let round_players: Vec<RoundPlayerDataWithAddress> = players_map.into_values().collect();
+    emit!(RoundDurationChanged {
+        cadence_id: sol_raffle.cadence_id,
+        old_duration,
+        new_duration: round_duration,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::RoundDurationChanged,
+        round_duration as u64,
+    )?;
+
+    Ok(())
+}
+
+pub fn set_sales_paused(ctx: Context<UpdateTokenRaffle>, paused: bool) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.sales_paused = paused;
+
+    msg!("✅ Cadence {} sales paused: {}", sol_raffle.cadence_id, paused);
+
+    emit!(SalesPauseChanged {
+        token_raffle: sol_raffle.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::SalesPausedToggled,
+        paused as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Gives `bonus_tickets` free tickets to the round's first buyer, or to every
+// `every`th buyer when `every` is non-zero
+pub fn set_first_ticket_bonus(
+    ctx: Context<UpdateTokenRaffle>,
+    enabled: bool,
+    bonus_tickets: u32,
+    every: u32,
+) -> Result<()> {
+    require!(
+        !enabled || bonus_tickets > 0,
+        RaffleError::InvalidFirstTicketBonusConfig
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.first_ticket_bonus_enabled = enabled;
+    sol_raffle.first_ticket_bonus_tickets = bonus_tickets;
+    sol_raffle.first_ticket_bonus_every = every;
+
+    msg!(
+        "✅ Cadence {} first ticket bonus: enabled {}, {} tickets, every {}",
+        sol_raffle.cadence_id,
+        enabled,
+        bonus_tickets,
+        every
+    );
+
+    emit!(FirstTicketBonusUpdated {
+        token_raffle: sol_raffle.key(),
+        enabled,
+        bonus_tickets,
+        every,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::FirstTicketBonusChanged,
+        if enabled { bonus_tickets as u64 } else { 0 },
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Every `per_tickets` bought in one transaction earn `bonus_tickets`, 0 disables
+pub fn set_volume_bonus(
+    ctx: Context<UpdateTokenRaffle>,
+    per_tickets: u32,
+    bonus_tickets: u32,
+) -> Result<()> {
+    require!(
+        (per_tickets == 0) == (bonus_tickets == 0),
+        RaffleError::InvalidVolumeBonusConfig
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.volume_bonus_per = per_tickets;
+    sol_raffle.volume_bonus_tickets = bonus_tickets;
+
+    msg!(
+        "✅ Cadence {} volume bonus: {} per {} tickets",
+        sol_raffle.cadence_id,
+        bonus_tickets,
+        per_tickets
+    );
+
+    emit!(VolumeBonusUpdated {
+        token_raffle: sol_raffle.key(),
+        per_tickets,
+        bonus_tickets,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::VolumeBonusChanged,
+        bonus_tickets as u64,
+    )?;
+
+    Ok(())
+}
+
+// remaining_accounts: RoundTicketsPurchase accounts of the round in increasing purchase_index order,
+// players come back in order of their first purchase
+pub fn get_raffle_round_result<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetRoundAccounts<'info>>,
+    _round_id: u32,
+) -> Result<RoundResultView> {
+
+    let round = &ctx.accounts.round;
+
+    let round_players = aggregate_round_players(round.key(), ctx.remaining_accounts)?;
 
     Ok(RoundResultView {
         winner_address: round.winner_address,
@@ -53,7 +283,7 @@ pub fn get_raffle_round_result(ctx: Context<GetRoundAccounts>, round_id: u32) ->
     })
 }
 
-pub fn get_raffle_round_data(ctx: Context<GetRoundAccounts>, round_id: u32) -> Result<RoundDataView> {
+pub fn get_raffle_round_data(ctx: Context<GetRoundAccounts>, _round_id: u32) -> Result<RoundDataView> {
 
     let round_account_info = ctx.accounts.round.to_account_info();
 
@@ -63,7 +293,7 @@ pub fn get_raffle_round_data(ctx: Context<GetRoundAccounts>, round_id: u32) -> R
 
     let round = &ctx.accounts.round;
 
-    let mut players_map: std::collections::HashMap<Pubkey, RoundPlayerDataWithAddress> = HashMap::new();
+    let players_map: std::collections::HashMap<Pubkey, RoundPlayerDataWithAddress> = HashMap::new();
 
     let round_players: Vec<RoundPlayerDataWithAddress> = players_map.into_values().collect();
 
@@ -80,6 +310,198 @@ pub fn get_raffle_round_data(ctx: Context<GetRoundAccounts>, round_id: u32) -> R
         winner_ticket_index: round.winner_ticket_index,
         round_players,
         prize_claimed: round.prize_claimed,
+        sponsored_amount: round.sponsored_amount,
+        free_entry: round.free_entry,
+        jackpot_amount: round.jackpot_amount,
+        mega_round: round.mega_round,
+        open_ticket_price: round.open_ticket_price,
+        open_sol_price_usd: round.open_sol_price_usd,
+        open_reference_price_usd: round.open_reference_price_usd,
+        frozen: round.frozen,
+        request_count: round.request_count,
+    })
+}
+
+// remaining_accounts: RoundTicketsPurchase accounts from start_index on, as many as the page holds.
+// Ticket ranges are [ticket_range_start, ticket_range_end), the winner ticket index falls in one of them
+pub fn get_round_purchases<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetRoundPurchases<'info>>,
+    _round_id: u32,
+    start_index: u32,
+    limit: u8,
+) -> Result<Vec<RoundPurchaseView>> {
+    let round = &ctx.accounts.round;
+    let round_tickets = ctx.accounts.round_tickets.load()?;
+
+    let end_index = round
+        .purchases_count
+        .min(round_tickets.len)
+        .min(start_index.saturating_add(limit.min(MAX_PURCHASES_VIEW_ENTRIES) as u32));
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        end_index.saturating_sub(start_index) as usize,
+        RaffleError::NotEnoughRemainingAccounts
+    );
+
+    let mut purchases = Vec::with_capacity(ctx.remaining_accounts.len());
+    for (purchase_index, purchase_info) in (start_index..end_index).zip(ctx.remaining_accounts.iter()) {
+        let purchase = Account::<RoundTicketsPurchase>::try_from(purchase_info)?;
+        require_keys_eq!(purchase.round, round.key(), RaffleError::InvalidPurchaseIndex);
+        require_eq!(purchase.purchase_index, purchase_index, RaffleError::InvalidPurchaseIndex);
+
+        let ticket_range_start = match purchase_index {
+            0 => 0,
+            index => round_tickets.get_ticket_at(index as usize - 1),
+        };
+
+        purchases.push(RoundPurchaseView {
+            purchase_index,
+            player: purchase.player,
+            tickets_count: purchase.tickets_count,
+            bonus_tickets: purchase.bonus_tickets,
+            ticket_price: purchase.ticket_price,
+            oracle_price: purchase.oracle_price,
+            price_slot: purchase.price_slot,
+            cancelled: purchase.cancelled,
+            ticket_range_start,
+            ticket_range_end: round_tickets.get_ticket_at(purchase_index as usize),
+        });
+    }
+
+    Ok(purchases)
+}
+
+// PDAs of rounds start_round_id.. of the raffle, derived whether or not the rounds exist
+pub fn get_round_addresses(
+    ctx: Context<GetTokenRaffle>,
+    start_round_id: u32,
+    limit: u8,
+) -> Result<Vec<RoundAddressesView>> {
+    let sol_raffle_key = ctx.accounts.sol_raffle.key();
+    let end_round_id = start_round_id.saturating_add(limit.min(MAX_ROUND_ADDRESSES_VIEW_ENTRIES) as u32);
+
+    Ok((start_round_id..end_round_id)
+        .map(|round_id| {
+            let (round, _) = Pubkey::find_program_address(
+                &[b"round", sol_raffle_key.as_ref(), &round_id.to_le_bytes()],
+                &crate::id(),
+            );
+            let (round_tickets, _) =
+                Pubkey::find_program_address(&[b"round_tickets", round.as_ref()], &crate::id());
+
+            RoundAddressesView {
+                round_id,
+                round,
+                round_tickets,
+            }
+        })
+        .collect())
+}
+
+// Sums tickets per player over the given purchases
+fn aggregate_round_players<'info>(
+    round_key: Pubkey,
+    purchase_infos: &'info [AccountInfo<'info>],
+) -> Result<Vec<RoundPlayerDataWithAddress>> {
+    let mut players_index: HashMap<Pubkey, usize> = HashMap::new();
+    let mut round_players: Vec<RoundPlayerDataWithAddress> = Vec::new();
+    let mut next_index: u32 = 0;
+
+    for purchase_info in purchase_infos {
+        let purchase = Account::<RoundTicketsPurchase>::try_from(purchase_info)?;
+        require_keys_eq!(purchase.round, round_key, RaffleError::InvalidPurchaseIndex);
+        require!(
+            purchase.purchase_index >= next_index,
+            RaffleError::InvalidPurchaseIndex
+        );
+        next_index = purchase.purchase_index + 1;
+
+        if purchase.cancelled {
+            continue;
+        }
+
+        let index = *players_index.entry(purchase.player).or_insert_with(|| {
+            round_players.push(RoundPlayerDataWithAddress {
+                player: purchase.player,
+                tickets_count: 0,
+                has_bonus_ticket: false,
+            });
+            round_players.len() - 1
+        });
+
+        let player_data = &mut round_players[index];
+        player_data.tickets_count = player_data
+            .tickets_count
+            .checked_add(purchase.tickets_count)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        player_data.has_bonus_ticket |= purchase.bonus_tickets > 0;
+    }
+
+    Ok(round_players)
+}
+
+// Ticket sales must equal the round pool once sponsored value, the jackpot won,
+// jackpot contributions, keeper tips, refunds, carryovers, the prize floor top-up and a
+// house win roll-over are accounted for.
+// remaining_accounts: every RoundTicketsPurchase of the round in purchase_index order
+pub fn verify_round_invariants<'info>(
+    ctx: Context<'_, '_, 'info, 'info, GetRoundAccounts<'info>>,
+    round_id: u32,
+) -> Result<RoundInvariantsView> {
+    let round = &ctx.accounts.round;
+    let round_key = round.key();
+
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        round.purchases_count as usize,
+        RaffleError::PurchaseIndexMismatch
+    );
+
+    let mut ticket_sales: u128 = 0;
+    for (index, purchase_info) in ctx.remaining_accounts.iter().enumerate() {
+        let purchase = Account::<RoundTicketsPurchase>::try_from(purchase_info)?;
+        require_keys_eq!(purchase.round, round_key, RaffleError::InvalidPurchaseIndex);
+        require_eq!(
+            purchase.purchase_index as usize,
+            index,
+            RaffleError::InvalidPurchaseIndex
+        );
+
+        let paid_tickets = purchase.tickets_count.saturating_sub(purchase.bonus_tickets);
+        ticket_sales = ticket_sales
+            .checked_add(purchase.ticket_price as u128 * paid_tickets as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    let pool = round.prize_amount as u128
+        + round.commission_balance as u128
+        + round.commission_accrued as u128
+        + round.jackpot_contributed as u128
+        + round.keeper_tips_paid as u128
+        + round.carryover_out as u128
+        + round.house_rolled as u128;
+    let expected = (ticket_sales
+        + round.sponsored_amount as u128
+        + round.jackpot_amount as u128
+        + round.staking_yield as u128
+        + round.carryover_in as u128
+        + round.prize_top_up as u128)
+        .saturating_sub(round.refunded_amount as u128 + round.staking_loss as u128);
+    let holds = pool == expected;
+
+    if holds {
+        msg!("✅ Round {} invariants hold", round_id);
+    } else {
+        msg!("❌ Round {} pool {} != ticket sales {} + extras", round_id, pool, ticket_sales);
+    }
+
+    Ok(RoundInvariantsView {
+        round_id,
+        purchases_checked: round.purchases_count,
+        ticket_sales: ticket_sales as u64,
+        pool_amount: pool as u64,
+        expected_amount: expected as u64,
+        holds,
     })
 }
 
@@ -107,17 +529,198 @@ pub fn get_raffle_round_count(ctx: Context<GetTockenRaffle>) -> Result<u32> {
     Ok(ctx.accounts.sol_raffle.total_rounds)
 }
     
-pub fn get_ticket_price_in_btc(ctx: Context<EmptyAccounts>) -> Result<u64> {
-    Ok(TICKET_BTC_SATOSHIS)
+// Every component of the ticket peg, a single BTC component if no peg is set
+pub fn get_ticket_reference_price(ctx: Context<GetRaffleState>) -> Result<Vec<PegComponent>> {
+    Ok(ticket_peg(&ctx.accounts.raffle_state))
+}
+
+// With a round passed, priced the way its purchases are: the committed price once set, else
+// the live price in the round's pricing mode
+pub fn get_ticket_price(ctx: Context<GetTicketPrice>, round_id: u32) -> Result<u64> {
+    let cluster_profile = check_cluster_sol_feed(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_price_feed,
+    )?;
+    let feed_reader = FeedReader::new(
+        require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?,
+        ctx.accounts.feed_failover.as_deref(),
+        require_price_cache(&ctx.accounts.raffle_state, ctx.accounts.price_cache.as_deref())?,
+        cluster_profile,
+        ctx.remaining_accounts,
+    );
+    if let Some(round) = ctx.accounts.round.as_ref() {
+        let sol_raffle = ctx.accounts.sol_raffle.as_ref().ok_or(RaffleError::NotTokenRaffle)?;
+        require!(
+            round.token_raffle == sol_raffle.key() && round.round_id == round_id,
+            RaffleError::RoundNotAvailable
+        );
+    }
+
+    view_ticket_price(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.sol_raffle.as_deref(),
+        ctx.accounts.round.as_deref(),
+        &feed_reader,
+        &ctx.accounts.reference_price_feed,
+        ctx.remaining_accounts,
+        &ctx.accounts.sol_price_feed,
+    )
+}
+
+// Oracle price in lamports before price decay, the round's when passed, else the live price of
+// the raffle's pricing mode, the ticket peg without a raffle
+fn view_ticket_price(
+    raffle_state: &RaffleState,
+    sol_raffle: Option<&TokenRaffle>,
+    round: Option<&Round>,
+    feed_reader: &FeedReader,
+    reference_price_feed: &AccountInfo,
+    basket_feeds: &[AccountInfo],
+    sol_price_feed: &AccountInfo,
+) -> Result<u64> {
+    let decimals = quote_decimals(sol_raffle, sol_price_feed)?;
+
+    match (sol_raffle, round) {
+        (Some(sol_raffle), Some(round)) => calculate_round_ticket_price(
+            raffle_state,
+            sol_raffle,
+            round,
+            feed_reader,
+            reference_price_feed,
+            basket_feeds,
+            sol_price_feed,
+        ),
+        (Some(sol_raffle), None) if sol_raffle.pricing_mode == PricingMode::FixedUsd => calculate_fixed_ticket_price(
+            raffle_state,
+            &PriceSampling::LATEST,
+            feed_reader,
+            sol_raffle.fixed_ticket_price_usd,
+            sol_price_feed,
+            decimals,
+        ),
+        _ => calculate_ticket_price_for_sol(
+            raffle_state,
+            &PriceSampling::LATEST,
+            feed_reader,
+            reference_price_feed,
+            basket_feeds,
+            sol_price_feed,
+            decimals,
+        ),
+    }
+}
+
+// Live price parts for frontends, a committed round price is left out. With a round passed,
+// read in its pricing mode and price sampling
+pub fn get_price_breakdown(ctx: Context<GetTicketPrice>, round_id: u32) -> Result<PriceBreakdownView> {
+    let cluster_profile = check_cluster_sol_feed(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_price_feed,
+    )?;
+    let feed_reader = FeedReader::new(
+        require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?,
+        ctx.accounts.feed_failover.as_deref(),
+        require_price_cache(&ctx.accounts.raffle_state, ctx.accounts.price_cache.as_deref())?,
+        cluster_profile,
+        ctx.remaining_accounts,
+    );
+
+    if let Some(round) = ctx.accounts.round.as_ref() {
+        let sol_raffle = ctx.accounts.sol_raffle.as_ref().ok_or(RaffleError::NotTokenRaffle)?;
+        require!(
+            round.token_raffle == sol_raffle.key() && round.round_id == round_id,
+            RaffleError::RoundNotAvailable
+        );
+    }
+
+    price_breakdown(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.sol_raffle.as_deref(),
+        ctx.accounts.round.as_deref(),
+        &feed_reader,
+        &ctx.accounts.reference_price_feed,
+        ctx.remaining_accounts,
+        &ctx.accounts.sol_price_feed,
+    )
 }
 
-pub fn get_ticket_price(ctx: Context<GetTicketPrice>) -> Result<u64> {
-    let ticket_price = calculate_ticket_price_for_sol(
-        &ctx.accounts.btc_price_feed,
+// Homepage data in one call. The full RaffleState and TokenRaffle views don't fit return data
+// together, the fields the homepage renders are bundled. With the current round passed the
+// ticket price is what a purchase pays now, price decay included
+pub fn get_state_bundle(ctx: Context<GetStateBundle>) -> Result<StateBundleView> {
+    let raffle_state = &ctx.accounts.raffle_state;
+    let sol_raffle = &ctx.accounts.sol_raffle;
+    let round = ctx.accounts.round.as_deref();
+    if let Some(round) = round {
+        require!(
+            round.token_raffle == sol_raffle.key() && Some(round.round_id) == sol_raffle.current_round_id,
+            RaffleError::RoundNotAvailable
+        );
+    }
+
+    let cluster_profile = check_cluster_sol_feed(
+        raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_price_feed,
+    )?;
+    let feed_reader = FeedReader::new(
+        require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?,
+        ctx.accounts.feed_failover.as_deref(),
+        require_price_cache(&ctx.accounts.raffle_state, ctx.accounts.price_cache.as_deref())?,
+        cluster_profile,
+        ctx.remaining_accounts,
+    );
+    let oracle_price = view_ticket_price(
+        raffle_state,
+        Some(sol_raffle),
+        round,
+        &feed_reader,
+        &ctx.accounts.reference_price_feed,
+        ctx.remaining_accounts,
         &ctx.accounts.sol_price_feed,
     )?;
 
-    Ok(ticket_price)
+    let now = Clock::get()?.unix_timestamp;
+    let ticket_price = match round {
+        Some(round) => apply_price_decay(sol_raffle, round, oracle_price, now)?,
+        None => oracle_price,
+    };
+
+    Ok(StateBundleView {
+        entrance_fee_percentage: raffle_state.entrance_fee_percentage,
+        jackpot_bps: raffle_state.jackpot_bps,
+        ticket_price_satoshis: raffle_state.ticket_price_satoshis,
+        in_maintenance: raffle_state.is_in_maintenance(now),
+        token_mint: sol_raffle.token_mint,
+        cadence_id: sol_raffle.cadence_id,
+        round_duration: sol_raffle.round_duration,
+        total_rounds: sol_raffle.total_rounds,
+        pricing_mode: sol_raffle.pricing_mode.clone(),
+        fixed_ticket_price_usd: sol_raffle.fixed_ticket_price_usd,
+        sales_paused: sol_raffle.sales_paused,
+        current_round: round.map(|round| RoundHeaderView {
+            round_id: round.round_id,
+            status: round.status.clone(),
+            start_time: round.start_time,
+            end_time: round.end_time,
+            prize_amount: round.prize_amount,
+            jackpot_amount: round.jackpot_amount,
+            total_tickets: round.total_tickets,
+            purchases_count: round.purchases_count,
+            mega_round: round.mega_round,
+            free_entry: round.free_entry,
+            frozen: round.frozen,
+            winner_address: round.winner_address,
+        }),
+        ticket_price,
+        vault_lamports: ctx.accounts.vault.lamports(),
+        vault_outstanding: ledger_outstanding(&ctx.accounts.vault_ledger)?,
+        insurance_lamports: ctx.accounts.insurance_vault.lamports(),
+        jackpot_balance: ctx.accounts.mega_jackpot.as_ref().map_or(0, |mega_jackpot| mega_jackpot.balance),
+        timestamp: now,
+    })
 }
 
 #[derive(Accounts)]
@@ -132,7 +735,7 @@ pub struct GetRaffleState<'info> {
 #[derive(Accounts)]
 pub struct GetTokenRaffle<'info> {
     #[account(
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
@@ -140,17 +743,139 @@ pub struct GetTokenRaffle<'info> {
 
 #[derive(Accounts)]
 pub struct GetTicketPrice<'info> {
-    /// CHECK: Switchboard BTC price feed
-    pub btc_price_feed: AccountInfo<'info>,
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    // None - the pegged price. Its current pricing mode, open rounds may still use the previous one
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Option<Account<'info, TokenRaffle>>,
+
+    // Round round_id of sol_raffle, None - the live price
+    pub round: Option<Account<'info, Round>>,
+
+    /// CHECK: Switchboard feed of the first ticket peg component, basket feeds go in remaining_accounts
+    pub reference_price_feed: AccountInfo<'info>,
 
     /// CHECK: Switchboard SOL price feed
     pub sol_price_feed: AccountInfo<'info>,
+
+    // Fallbacks for stale feeds, the fallback feeds go in remaining_accounts after the basket feeds
+    #[account(
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Option<Account<'info, FeedFailover>>,
+
+    // Crank-refreshed feed prices, read instead of the feeds while fresh
+    #[account(
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Option<Account<'info, PriceCache>>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+
+    // Required once the cluster profile is set up, pins the SOL feed and the oracle program
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+}
+
+#[derive(Accounts)]
+pub struct GetStateBundle<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    // The raffle's current round, None - no round header and the live price
+    pub round: Option<Account<'info, Round>>,
+
+    #[account(
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL, only its balance is read
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    #[account(
+        seeds = [b"mega_jackpot"],
+        bump = mega_jackpot.bump
+    )]
+    pub mega_jackpot: Option<Account<'info, MegaJackpot>>,
+
+    /// CHECK: Switchboard feed of the first ticket peg component, basket feeds go in remaining_accounts
+    pub reference_price_feed: AccountInfo<'info>,
+
+    /// CHECK: Switchboard SOL price feed
+    pub sol_price_feed: AccountInfo<'info>,
+
+    // Fallbacks for stale feeds, the fallback feeds go in remaining_accounts after the basket feeds
+    #[account(
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Option<Account<'info, FeedFailover>>,
+
+    // Crank-refreshed feed prices, read instead of the feeds while fresh
+    #[account(
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Option<Account<'info, PriceCache>>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+
+    // Required once the cluster profile is set up, pins the SOL feed and the oracle program
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
 }
 
 #[derive(Accounts)]
 pub struct GetTockenRaffle<'info> {
     #[account(
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
@@ -160,7 +885,7 @@ pub struct GetTockenRaffle<'info> {
 #[instruction(round_id: u32)]
 pub struct GetRoundAccounts<'info> {
     #[account(
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
@@ -176,10 +901,138 @@ pub struct GetRoundAccounts<'info> {
     pub round: Account<'info, Round>,
 }
 
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct GetRoundPurchases<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    // Closed once the round is settled
+    #[account(
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+}
+
 #[derive(Accounts)]
 pub struct EmptyAccounts {}
 
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateRaffleState<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateTokenRaffle<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
 // // View data structures
+// Prices in micro-USD, 0 reference price and slot in FixedUsd mode
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PriceBreakdownView {
+    pub reference_usd: u64, // first ticket peg component, BTC by default
+    pub sol_usd: u64,
+    pub ticket_usd: u64,
+    pub ticket_lamports: u64, // before price decay
+    pub reference_slot: u64, // slot of the feed result
+    pub sol_slot: u64,
+    pub slot: u64, // read at
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RoundHeaderView {
+    pub round_id: u32,
+    pub status: RoundStatus,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub prize_amount: u64,
+    pub jackpot_amount: u64,
+    pub total_tickets: u32,
+    pub purchases_count: u32,
+    pub mega_round: bool,
+    pub free_entry: bool,
+    pub frozen: bool,
+    pub winner_address: Option<Pubkey>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StateBundleView {
+    pub entrance_fee_percentage: u8,
+    pub jackpot_bps: u16,
+    pub ticket_price_satoshis: u64,
+    pub in_maintenance: bool,
+    pub token_mint: Pubkey,
+    pub cadence_id: u8,
+    pub round_duration: i64,
+    pub total_rounds: u32,
+    pub pricing_mode: PricingMode,
+    pub fixed_ticket_price_usd: u64,
+    pub sales_paused: bool,
+    pub current_round: Option<RoundHeaderView>,
+    pub ticket_price: u64, // lamports
+    pub vault_lamports: u64, // sol_vault balance, rent included
+    pub vault_outstanding: u64, // prizes and commission the vault owes
+    pub insurance_lamports: u64,
+    pub jackpot_balance: u64,
+    pub timestamp: i64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RaffleStateView {
     pub authority: Pubkey,
@@ -187,6 +1040,28 @@ pub struct RaffleStateView {
     pub beneficiary: Pubkey,
     pub created_at: i64,
     pub vrf_request_counter: u8,
+    pub vesting_threshold: u64,
+    pub vesting_cliff_seconds: i64,
+    pub vesting_duration_seconds: i64,
+    pub operator: Pubkey,
+    pub insurance_bps: u16,
+    pub jackpot_bps: u16,
+    pub maintenance_start: i64,
+    pub maintenance_end: i64,
+    pub max_price_deviation_bps: u16,
+    pub watchtower: Pubkey,
+    pub pending_authority: Pubkey,
+    pub keeper_tip: u64,
+    pub keeper_allowlist_enabled: bool,
+    pub randomness_cooldown_seconds: i64,
+    pub max_randomness_requests: u8,
+    pub ticket_price_satoshis: u64,
+    pub ticket_peg: Vec<PegComponent>,
+    pub blocklist_enabled: bool,
+    pub max_price_confidence_bps: u16,
+    pub cluster_profile_enabled: bool,
+    pub price_cache_enabled: bool,
+    pub feed_registry_enabled: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
@@ -197,7 +1072,53 @@ pub struct TokenRaffleView {
     pub current_round_status: RoundStatus,
     pub current_round_end_time: Option<i64>,
     pub total_rounds: u32,
-    pub pending_rounds: Vec<u32>,
+    pub streak_bonus_every: u32,
+    pub streak_bonus_tickets: u32,
+    pub first_ticket_bonus_enabled: bool,
+    pub first_ticket_bonus_tickets: u32,
+    pub first_ticket_bonus_every: u32,
+    pub volume_bonus_per: u32,
+    pub volume_bonus_tickets: u32,
+    pub price_decay_mode: PriceDecayMode,
+    pub price_decay_start_bps: u16,
+    pub price_decay_end_bps: u16,
+    pub price_decay_steps: u8,
+    pub price_sample_mode: PriceSampleMode,
+    pub price_sample_count: u8,
+    pub cancel_penalty_bps: Option<u16>,
+    pub commission_mode: CommissionMode,
+    pub staking_enabled: bool,
+    pub staking_yield_to_insurance: bool,
+    pub price_feed: Pubkey,
+    pub token_decimals: u8,
+    pub fee_percentage: Option<u8>,
+    pub fee_tiers: Vec<FeeTier>,
+    pub max_purchases_per_wallet: u32,
+    pub min_tickets_per_purchase: u32,
+    pub min_purchase_lamports: u64,
+    pub prize_target_lamports: Option<u64>,
+    pub prize_cap_lamports: Option<u64>,
+    pub carryover: u64,
+    pub min_prize_lamports: u64,
+    pub house_ticket_threshold: u32,
+    pub house_tickets: u32,
+    pub redraw_after_seconds: i64,
+    pub access_root: Option<[u8; 32]>,
+    pub attestation_required: bool,
+    pub attestation_credential: Pubkey,
+    pub attestation_schema: Pubkey,
+    pub pricing_mode: PricingMode,
+    pub fixed_ticket_price_usd: u64,
+    pub commit_round_price: bool,
+    pub loyalty_points_per_sol: u64,
+    pub loyalty_points_per_ticket: u64,
+    pub cadence_id: u8,
+    pub round_duration: i64,
+    pub mega_round_weekday: Option<u8>,
+    pub mega_round_fee_percentage: u8,
+    pub mega_round_duration: i64,
+    pub mega_round_jackpot_bps: u16,
+    pub sales_paused: bool,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -207,6 +1128,27 @@ pub struct RoundPlayerDataWithAddress {
     pub has_bonus_ticket: bool,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RoundPurchaseView {
+    pub purchase_index: u32,
+    pub player: Pubkey,
+    pub tickets_count: u32, // includes bonus_tickets
+    pub bonus_tickets: u32,
+    pub ticket_price: u64, // lamports per paid ticket as charged
+    pub oracle_price: u64,
+    pub price_slot: u64,
+    pub cancelled: bool, // the ticket range is empty
+    pub ticket_range_start: u32,
+    pub ticket_range_end: u32, // exclusive
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RoundAddressesView {
+    pub round_id: u32,
+    pub round: Pubkey,
+    pub round_tickets: Pubkey,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct RoundResultView {
     pub winner_address: Option<Pubkey>,
@@ -229,6 +1171,25 @@ pub struct RoundDataView {
     pub winner_ticket_index: Option<u32>,
     pub round_players: Vec<RoundPlayerDataWithAddress>,
     pub prize_claimed: bool,
+    pub sponsored_amount: u64,
+    pub free_entry: bool,
+    pub jackpot_amount: u64,
+    pub mega_round: bool,
+    pub open_ticket_price: u64,
+    pub open_sol_price_usd: u64,
+    pub open_reference_price_usd: u64,
+    pub frozen: bool,
+    pub request_count: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RoundInvariantsView {
+    pub round_id: u32,
+    pub purchases_checked: u32,
+    pub ticket_sales: u64,
+    pub pool_amount: u64, // prize + commission + jackpot contributions + keeper tips
+    pub expected_amount: u64, // ticket sales + sponsored + jackpot won
+    pub holds: bool,
 }
 
 // Error codes
@@ -237,3 +1198,33 @@ pub enum ErrorCode {
     #[msg("Round does not exist")]
     RoundDoesNotExist,
 }
+
+// Events
+#[event]
+pub struct FirstTicketBonusUpdated {
+    pub token_raffle: Pubkey,
+    pub enabled: bool,
+    pub bonus_tickets: u32,
+    pub every: u32,
+}
+
+#[event]
+pub struct VolumeBonusUpdated {
+    pub token_raffle: Pubkey,
+    pub per_tickets: u32,
+    pub bonus_tickets: u32,
+}
+
+#[event]
+pub struct SalesPauseChanged {
+    pub token_raffle: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundDurationChanged {
+    pub cadence_id: u8,
+    pub old_duration: i64,
+    pub new_duration: i64,
+}
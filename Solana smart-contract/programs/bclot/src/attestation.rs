@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+use crate::{
+    RaffleError,
+    TokenRaffle,
+    UpdateTokenRaffle,
+};
+use crate::audit::{record_audit, AuditAction};
+
+pub const SAS_PROGRAM_ID: Pubkey = pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+
+// Solana Attestation Service account layout: discriminator, nonce, credential, schema,
+// u32-prefixed data, signer, expiry, token_account
+const ATTESTATION_DISCRIMINATOR: u8 = 2;
+const NONCE_OFFSET: usize = 1;
+const CREDENTIAL_OFFSET: usize = NONCE_OFFSET + 32;
+const SCHEMA_OFFSET: usize = CREDENTIAL_OFFSET + 32;
+const DATA_LEN_OFFSET: usize = SCHEMA_OFFSET + 32;
+
+// Only authority. Players need an attestation issued under `credential` for `schema`
+// (e.g. age or KYC) to buy tickets of this raffle
+pub fn set_attestation_requirement(
+    ctx: Context<UpdateTokenRaffle>,
+    required: bool,
+    credential: Pubkey,
+    schema: Pubkey,
+) -> Result<()> {
+    require!(
+        !required || (credential != Pubkey::default() && schema != Pubkey::default()),
+        RaffleError::InvalidAttestationConfig
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.attestation_required = required;
+    sol_raffle.attestation_credential = credential;
+    sol_raffle.attestation_schema = schema;
+
+    emit!(AttestationRequirementUpdated {
+        token_raffle: sol_raffle.key(),
+        required,
+        credential,
+        schema,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::AttestationRequirementChanged,
+        required as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only SAS can create accounts it owns, so matching credential, schema and nonce (the player)
+// is enough without re-deriving the attestation PDA
+pub fn verify_attestation(
+    sol_raffle: &TokenRaffle,
+    attestation: Option<&AccountInfo>,
+    player: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !sol_raffle.attestation_required {
+        return Ok(());
+    }
+
+    let attestation = attestation.ok_or(RaffleError::AttestationRequired)?;
+    require_keys_eq!(*attestation.owner, SAS_PROGRAM_ID, RaffleError::InvalidAttestation);
+
+    let data = attestation.try_borrow_data()?;
+    require!(data.len() > DATA_LEN_OFFSET + 4, RaffleError::InvalidAttestation);
+    require!(data[0] == ATTESTATION_DISCRIMINATOR, RaffleError::InvalidAttestation);
+
+    let read_pubkey = |offset: usize| -> Result<Pubkey> {
+        let bytes: [u8; 32] = data
+            .get(offset..offset + 32)
+            .and_then(|slice| slice.try_into().ok())
+            .ok_or(RaffleError::InvalidAttestation)?;
+        Ok(Pubkey::new_from_array(bytes))
+    };
+
+    require_keys_eq!(read_pubkey(NONCE_OFFSET)?, *player, RaffleError::InvalidAttestation);
+    require_keys_eq!(
+        read_pubkey(CREDENTIAL_OFFSET)?,
+        sol_raffle.attestation_credential,
+        RaffleError::InvalidAttestation
+    );
+    require_keys_eq!(
+        read_pubkey(SCHEMA_OFFSET)?,
+        sol_raffle.attestation_schema,
+        RaffleError::InvalidAttestation
+    );
+
+    let data_len = u32::from_le_bytes(data[DATA_LEN_OFFSET..DATA_LEN_OFFSET + 4].try_into().unwrap()) as usize;
+    let expiry_offset = DATA_LEN_OFFSET + 4 + data_len + 32;
+    let expiry = data
+        .get(expiry_offset..expiry_offset + 8)
+        .map(|bytes| i64::from_le_bytes(bytes.try_into().unwrap()))
+        .ok_or(RaffleError::InvalidAttestation)?;
+
+    // 0 - never expires
+    require!(expiry == 0 || expiry > now, RaffleError::AttestationExpired);
+
+    Ok(())
+}
+
+// Events
+#[event]
+pub struct AttestationRequirementUpdated {
+    pub token_raffle: Pubkey,
+    pub required: bool,
+    pub credential: Pubkey,
+    pub schema: Pubkey,
+}
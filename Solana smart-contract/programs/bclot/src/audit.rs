@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+};
+
+pub const AUDIT_LOG_CAPACITY: usize = 128;
+
+// Return data is capped at 1024 bytes
+pub const MAX_AUDIT_VIEW_ENTRIES: u8 = 16;
+
+// Stored as u8 in AuditEntry.action, new codes are only appended
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AuditAction {
+    EntranceFeeChanged = 0,
+    BeneficiaryChanged = 1,
+    AuthorityProposed = 2,
+    AuthorityAccepted = 3,
+    VrfVaultWithdrawn = 4,
+    RentVaultWithdrawn = 5,
+    PromoVaultWithdrawn = 6,
+    TukTukVaultWithdrawn = 7,
+    InsurancePaidOut = 8,
+    RoundFrozen = 9,
+    RoundUnfrozen = 10,
+    InsuranceBpsChanged = 11,
+    KeeperTipChanged = 12,
+    KeeperAllowlistToggled = 13,
+    KeeperAdded = 14,
+    KeeperRemoved = 15,
+    PriceCircuitBreakerChanged = 16,
+    OperatorChanged = 17,
+    MaintenanceScheduled = 18,
+    VestingConfigChanged = 19,
+    RandomnessRateLimitChanged = 20,
+    RandomnessRequestsReset = 21,
+    WatchtowerChanged = 22,
+    JackpotConfigChanged = 23,
+    RoundDurationChanged = 24,
+    SalesPausedToggled = 25,
+    StreakRewardsChanged = 26,
+    LoyaltyConfigChanged = 27,
+    MegaRoundConfigChanged = 28,
+    FirstTicketBonusChanged = 29,
+    VolumeBonusChanged = 30,
+    PriceDecayChanged = 31,
+    CancelPenaltyChanged = 32,
+    RoundCancelled = 33,
+    CommissionModeChanged = 34,
+    CommissionWithdrawalCapChanged = 35,
+    StakingModeChanged = 36,
+    YieldSweepIntervalChanged = 37,
+    TreasurerChanged = 38,
+    PriceFeedRegistered = 39,
+    PriceFeedUnregistered = 40,
+    TicketPriceChanged = 41,
+    TicketPegChanged = 42,
+    PricingModeChanged = 43,
+    FeeTiersChanged = 44,
+    FeeDiscountChanged = 45,
+    MaxPurchasesPerWalletChanged = 46,
+    AccessListChanged = 47,
+    BlocklistToggled = 48,
+    AddressBlocked = 49,
+    AddressUnblocked = 50,
+    AttestationRequirementChanged = 51,
+    MinPurchaseChanged = 52,
+    PrizeTargetChanged = 53,
+    PrizeCapChanged = 54,
+    MinPrizeChanged = 55,
+    HouseTicketChanged = 56,
+    RedrawConfigChanged = 57,
+    CommitRoundPriceChanged = 58,
+    PriceConfidenceChanged = 59,
+    PriceSamplingChanged = 60,
+    FeedFailoverChanged = 61,
+    EmergencyPriceChanged = 62,
+    PriceCacheChanged = 63,
+    FeedReadLimitsChanged = 64,
+    ClusterProfileChanged = 65,
+    WinnerAddressSet = 66,
+}
+
+// Only authority
+pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+    let mut audit_log = ctx.accounts.audit_log.load_init()?;
+    audit_log.bump = ctx.bumps.audit_log;
+
+    msg!("✅ Audit log initialized");
+
+    Ok(())
+}
+
+// Entries with seq >= from_seq, oldest first. Entries older than the last
+// AUDIT_LOG_CAPACITY are overwritten
+pub fn get_audit_log(ctx: Context<GetAuditLog>, from_seq: u64, limit: u8) -> Result<Vec<AuditEntryView>> {
+    let audit_log = ctx.accounts.audit_log.load()?;
+
+    let start = from_seq.max(audit_log.oldest_seq());
+    let end = audit_log
+        .total
+        .min(start.saturating_add(limit.min(MAX_AUDIT_VIEW_ENTRIES) as u64));
+
+    Ok((start..end)
+        .map(|seq| {
+            let entry = &audit_log.entries[(seq % AUDIT_LOG_CAPACITY as u64) as usize];
+            AuditEntryView {
+                seq,
+                actor: entry.actor,
+                timestamp: entry.timestamp,
+                action: entry.action,
+                value: entry.value,
+            }
+        })
+        .collect())
+}
+
+impl AuditLog {
+    pub fn oldest_seq(&self) -> u64 {
+        self.total.saturating_sub(AUDIT_LOG_CAPACITY as u64)
+    }
+
+    // Overwrites the oldest entry once full
+    pub fn append(&mut self, actor: Pubkey, action: AuditAction, value: u64, timestamp: i64) -> u64 {
+        let seq = self.total;
+        self.entries[(seq % AUDIT_LOG_CAPACITY as u64) as usize] = AuditEntry {
+            actor,
+            timestamp,
+            value,
+            action: action as u8,
+            padding: [0; 7],
+        };
+        self.total += 1;
+
+        seq
+    }
+}
+
+// `value` is the action's main argument: new fee or bps, amount, round id, 0 for key changes
+pub fn record_audit(
+    audit_log: &AccountLoader<AuditLog>,
+    actor: Pubkey,
+    action: AuditAction,
+    value: u64,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    let seq = audit_log.load_mut()?.append(actor, action, value, timestamp);
+
+    emit!(AuditRecorded {
+        seq,
+        actor,
+        action: action as u8,
+        value,
+        timestamp,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeAuditLog<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<AuditLog>(),
+        seeds = [b"audit_log"],
+        bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetAuditLog<'info> {
+    #[account(
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+#[zero_copy]
+#[derive(Debug)]
+pub struct AuditEntry {
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub value: u64,
+    pub action: u8, // AuditAction
+    pub padding: [u8; 7],
+}
+
+// Append-only record of privileged actions
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct AuditLog {
+    pub entries: [AuditEntry; AUDIT_LOG_CAPACITY], // ring buffer, entry seq lives at seq % capacity
+    pub total: u64, // entries ever recorded, the next seq
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+// View data structures
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AuditEntryView {
+    pub seq: u64,
+    pub actor: Pubkey,
+    pub timestamp: i64,
+    pub action: u8,
+    pub value: u64,
+}
+
+// Events
+#[event]
+pub struct AuditRecorded {
+    pub seq: u64,
+    pub actor: Pubkey,
+    pub action: u8,
+    pub value: u64,
+    pub timestamp: i64,
+}
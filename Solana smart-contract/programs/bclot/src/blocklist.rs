@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+    UpdateRaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+pub const MAX_BLOCKED_ADDRESSES: usize = 64;
+
+// Only authority
+pub fn initialize_blocklist(ctx: Context<InitializeBlocklist>) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+    blocklist.addresses = Vec::new();
+    blocklist.bump = ctx.bumps.blocklist;
+
+    msg!("✅ Blocklist initialized");
+
+    Ok(())
+}
+
+// Only authority. While enabled, purchase and claim instructions must pass the blocklist
+pub fn set_blocklist_enabled(ctx: Context<UpdateRaffleState>, enabled: bool) -> Result<()> {
+    ctx.accounts.raffle_state.blocklist_enabled = enabled;
+
+    emit!(BlocklistToggled { enabled });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::BlocklistToggled,
+        enabled as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn block_address(ctx: Context<UpdateBlocklist>, address: Pubkey) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+    require!(
+        !blocklist.addresses.contains(&address),
+        RaffleError::AddressAlreadyBlocked
+    );
+    require!(
+        blocklist.addresses.len() < MAX_BLOCKED_ADDRESSES,
+        RaffleError::BlocklistFull
+    );
+
+    blocklist.addresses.push(address);
+
+    emit!(AddressBlocked { address });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::AddressBlocked,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn unblock_address(ctx: Context<UpdateBlocklist>, address: Pubkey) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+    let len_before = blocklist.addresses.len();
+    blocklist.addresses.retain(|a| *a != address);
+    require!(
+        blocklist.addresses.len() < len_before,
+        RaffleError::AddressNotBlocked
+    );
+
+    emit!(AddressUnblocked { address });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::AddressUnblocked,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// With the blocklist enabled it must be passed, so leaving it out doesn't skip the check
+pub fn is_blocked(
+    raffle_state: &RaffleState,
+    blocklist: Option<&Account<Blocklist>>,
+    address: &Pubkey,
+) -> Result<bool> {
+    if !raffle_state.blocklist_enabled {
+        return Ok(false);
+    }
+
+    let blocklist = blocklist.ok_or(RaffleError::BlocklistRequired)?;
+    Ok(blocklist.addresses.contains(address))
+}
+
+pub fn require_not_blocked(
+    raffle_state: &RaffleState,
+    blocklist: Option<&Account<Blocklist>>,
+    address: &Pubkey,
+) -> Result<()> {
+    if is_blocked(raffle_state, blocklist, address)? {
+        msg!("🚨 Address {} is blocked", address);
+        return err!(RaffleError::AddressBlocked);
+    }
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeBlocklist<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Blocklist::INIT_SPACE,
+        seeds = [b"blocklist"],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateBlocklist<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+// Sanctioned or abusive addresses, excluded from purchases and claims
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Blocklist {
+    #[max_len(MAX_BLOCKED_ADDRESSES)]
+    pub addresses: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct BlocklistToggled {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct AddressBlocked {
+    pub address: Pubkey,
+}
+
+#[event]
+pub struct AddressUnblocked {
+    pub address: Pubkey,
+}
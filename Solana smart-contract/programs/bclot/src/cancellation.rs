@@ -0,0 +1,559 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionMode,
+    Round,
+    RoundStatus,
+    RoundTickets,
+    RoundTicketsPurchase,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateTokenRaffle,
+    split_commission,
+    MAX_TICKETS,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::jackpot::jackpot_contribution;
+use crate::lifecycle::transition_round;
+use crate::migration::Versioned;
+use crate::player_stats::{revoke_purchase_rewards, PlayerStats};
+use crate::prize_limits::{pass_on_carryover, remove_capped_prize};
+use crate::purchase_limits::{release_wallet_purchase, WalletPurchases};
+use crate::queue::PendingQueue;
+use crate::schedule::round_jackpot_bps;
+use crate::solvency::{release_in_ledger_account, require_solvent};
+use crate::sponsor::{check_sponsor_match, unmatch_purchase, SponsorMatch};
+
+// Only authority. Share of a cancelled purchase kept in the prize, None disables cancellation
+pub fn set_cancel_penalty(ctx: Context<UpdateTokenRaffle>, penalty_bps: Option<u16>) -> Result<()> {
+    require!(
+        penalty_bps.is_none_or(|bps| bps <= 10_000),
+        RaffleError::InvalidBps
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.cancel_penalty_bps = penalty_bps;
+
+    msg!("✅ Cadence {} cancel penalty set to {:?} bps", sol_raffle.cadence_id, penalty_bps);
+
+    emit!(CancelPenaltyUpdated {
+        token_raffle: sol_raffle.key(),
+        penalty_bps,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::CancelPenaltyChanged,
+        penalty_bps.map_or(0, |bps| bps as u64),
+    )?;
+
+    Ok(())
+}
+
+// The buyer leaves an Open round before end_time. The paid part minus the jackpot share
+// and the penalty is refunded, the penalty stays in the prize. The purchase is kept as a
+// tombstone: its ticket range is collapsed to zero width so purchase indexes stay stable
+// and the draw can never land on it
+pub fn cancel_purchase(ctx: Context<CancelPurchase>, round_id: u32, purchase_index: u32) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let raffle_state = &ctx.accounts.raffle_state;
+    let sol_raffle = &ctx.accounts.sol_raffle;
+    let round = &mut ctx.accounts.round;
+    let purchase = &mut ctx.accounts.round_tickets_purchase;
+
+    round.check_version()?;
+
+    let penalty_bps = sol_raffle
+        .cancel_penalty_bps
+        .ok_or(RaffleError::CancellationDisabled)?;
+    require!(
+        round.status == RoundStatus::Open && now < round.end_time,
+        RaffleError::RoundNotOpen
+    );
+    require!(!round.free_entry, RaffleError::FreeEntryRound);
+    require!(!purchase.cancelled, RaffleError::PurchaseCancelled);
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+
+    let (refund, penalty) = release_purchase_funds(raffle_state, sol_raffle, round, purchase, penalty_bps)?;
+    revoke_purchase_rewards(&mut ctx.accounts.player_stats, purchase, round_id)?;
+    release_wallet_purchase(ctx.accounts.wallet_purchases.as_mut());
+    check_sponsor_match(round, ctx.accounts.sponsor_match.as_ref().map(|sponsor_match| sponsor_match.key()))?;
+    let unmatched = unmatch_purchase(ctx.accounts.sponsor_match.as_deref_mut(), round, purchase)?;
+
+    ctx.accounts
+        .round_tickets
+        .load_mut()?
+        .void_purchase(purchase_index as usize, purchase.tickets_count)?;
+    round.total_tickets -= purchase.tickets_count;
+    purchase.cancelled = true;
+
+    require_solvent(&ctx.accounts.vault_ledger, &ctx.accounts.vault, round_id)?;
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= refund + unmatched;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += refund;
+    if let Some(sponsor_match) = ctx.accounts.sponsor_match.as_ref() {
+        **sponsor_match.to_account_info().try_borrow_mut_lamports()? += unmatched;
+    }
+    release_in_ledger_account(&ctx.accounts.vault_ledger, refund + unmatched)?;
+
+    msg!("✅ Purchase {} of round {} cancelled, {} lamports refunded", purchase_index, round_id, refund);
+
+    emit!(PurchaseCancelled {
+        token: sol_raffle.token_mint,
+        round_id,
+        purchase_index,
+        player: purchase.player,
+        tickets_count: purchase.tickets_count,
+        refund,
+        penalty,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// Takes a purchase's paid part back out of the round. The jackpot share already left
+// sol_vault and the penalty stays in the prize. Returns (refund, penalty)
+fn release_purchase_funds(
+    raffle_state: &RaffleState,
+    sol_raffle: &TokenRaffle,
+    round: &mut Round,
+    purchase: &RoundTicketsPurchase,
+    penalty_bps: u16,
+) -> Result<(u64, u64)> {
+    let paid_tickets = purchase.tickets_count - purchase.bonus_tickets;
+    let cost = purchase.ticket_price
+        .checked_mul(paid_tickets as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // The fee curve may have moved since, the purchase keeps the fee it paid
+    let (commission_amount, prize_amount) = split_commission(cost, purchase.fee_bps)?;
+    // Same for the jackpot share, older purchases fall back to the current one
+    let jackpot_bps = purchase
+        .jackpot_bps
+        .unwrap_or_else(|| round_jackpot_bps(raffle_state, sol_raffle, round));
+    let jackpot_amount = jackpot_contribution(jackpot_bps, cost)?;
+    let prize_refund = prize_amount
+        .checked_sub(jackpot_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    // Commission already sent to commission_vault is not refunded
+    let refundable = if round.commission_mode == CommissionMode::Immediate {
+        prize_refund
+    } else {
+        round.commission_balance = round
+            .commission_balance
+            .checked_sub(commission_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        cost - jackpot_amount
+    };
+    let penalty = (refundable as u128 * penalty_bps as u128 / 10_000) as u64;
+    let refund = refundable - penalty;
+
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(penalty)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    remove_capped_prize(round, prize_refund)?;
+    round.refunded_amount = round
+        .refunded_amount
+        .checked_add(refund)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok((refund, penalty))
+}
+
+// Only authority. Calls off an Open round, its buyers get their paid part back through
+// claim_refunds_batch. As with cancel_purchase, the jackpot share and commission already
+// sent to commission_vault are kept, RoundCancelled reports both
+pub fn cancel_round(ctx: Context<CancelRound>, round_id: u32) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let round = &mut ctx.accounts.round;
+    round.check_version()?;
+    require!(
+        matches!(round.status, RoundStatus::Open | RoundStatus::Locked),
+        RaffleError::RoundNotOpen
+    );
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+
+    transition_round(&mut ctx.accounts.sol_raffle, round, RoundStatus::Cancelled)?;
+    // Refunds cover paid parts only, the carried in prize, any floor top-up and the
+    // sponsored value move on
+    let carried_over = round
+        .carryover_in
+        .checked_add(round.prize_top_up)
+        .and_then(|amount| amount.checked_add(round.sponsored_amount))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    pass_on_carryover(&mut ctx.accounts.sol_raffle, carried_over)?;
+
+    let mut refund_bitmap = ctx.accounts.refund_bitmap.load_init()?;
+    refund_bitmap.round = round.key();
+    refund_bitmap.bump = ctx.bumps.refund_bitmap;
+    drop(refund_bitmap);
+
+    // An ended round may already wait for its draw
+    ctx.accounts.pending_queue.load_mut()?.remove(round_id);
+
+    msg!("🚨 Round {} cancelled", round_id);
+
+    emit!(RoundCancelled {
+        token: ctx.accounts.sol_raffle.token_mint,
+        round_id,
+        carried_over,
+        jackpot_kept: round.jackpot_contributed,
+        commission_kept: round.commission_accrued,
+        timestamp: now,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::RoundCancelled,
+        round_id as u64,
+    )?;
+
+    Ok(())
+}
+
+// Refunds the signer's purchases in Cancelled rounds of one raffle in a single call.
+// The round's RefundBitmap makes each refund one-off, refunded purchase accounts are
+// closed and their rent goes back to the player.
+// remaining_accounts: (Round, RefundBitmap, RoundTicketsPurchase) triples, all writable
+pub fn claim_refunds_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimRefundsBatch<'info>>,
+) -> Result<()> {
+    require!(
+        !ctx.remaining_accounts.is_empty() && ctx.remaining_accounts.len().is_multiple_of(3),
+        RaffleError::NotEnoughRemainingAccounts
+    );
+
+    let raffle_state = &ctx.accounts.raffle_state;
+    let sol_raffle = &ctx.accounts.sol_raffle;
+    let player = ctx.accounts.player.key();
+    let now = Clock::get()?.unix_timestamp;
+    let mut total_refund: u64 = 0;
+
+    for triple in ctx.remaining_accounts.chunks(3) {
+        let mut round = Account::<Round>::try_from(&triple[0])?;
+        let refund_bitmap = AccountLoader::<RefundBitmap>::try_from(&triple[1])?;
+        let purchase = Account::<RoundTicketsPurchase>::try_from(&triple[2])?;
+
+        require_keys_eq!(round.token_raffle, sol_raffle.key(), RaffleError::RoundNotAvailable);
+        require_keys_eq!(purchase.round, round.key(), RaffleError::InvalidPurchaseIndex);
+        require_keys_eq!(purchase.player, player, RaffleError::Unauthorized);
+        require!(round.status == RoundStatus::Cancelled, RaffleError::RoundNotCancelled);
+        // Refunded at cancel_purchase time
+        require!(!purchase.cancelled, RaffleError::PurchaseCancelled);
+
+        {
+            let mut bitmap = refund_bitmap.load_mut()?;
+            require_keys_eq!(bitmap.round, round.key(), RaffleError::InvalidPurchaseIndex);
+            require!(!bitmap.is_refunded(purchase.purchase_index), RaffleError::AlreadyRefunded);
+            bitmap.set_refunded(purchase.purchase_index);
+        }
+
+        let (refund, _) = release_purchase_funds(raffle_state, sol_raffle, &mut round, &purchase, 0)?;
+        total_refund = total_refund
+            .checked_add(refund)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        emit!(RefundClaimed {
+            token: sol_raffle.token_mint,
+            round_id: round.round_id,
+            purchase_index: purchase.purchase_index,
+            player,
+            refund,
+            timestamp: now,
+        });
+
+        round.exit(&crate::id())?;
+        refund_bitmap.exit(&crate::id())?;
+        purchase.close(ctx.accounts.player.to_account_info())?;
+    }
+
+    require_solvent(&ctx.accounts.vault_ledger, &ctx.accounts.vault, 0)?;
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= total_refund;
+    **ctx.accounts.player.try_borrow_mut_lamports()? += total_refund;
+    release_in_ledger_account(&ctx.accounts.vault_ledger, total_refund)?;
+
+    msg!("✅ Refunded {} lamports for {} purchases", total_refund, ctx.remaining_accounts.len() / 3);
+
+    Ok(())
+}
+
+impl RefundBitmap {
+    pub fn is_refunded(&self, purchase_index: u32) -> bool {
+        let index = purchase_index as usize;
+        self.bits[index / 8] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_refunded(&mut self, purchase_index: u32) {
+        let index = purchase_index as usize;
+        self.bits[index / 8] |= 1 << (index % 8);
+    }
+}
+
+impl RoundTickets {
+    // Collapses a purchase's ticket range by shifting every later cumulative value down
+    pub fn void_purchase(&mut self, index: usize, tickets: u32) -> Result<()> {
+        require!(index < self.len as usize, RaffleError::InvalidPurchaseIndex);
+
+        for i in index..self.len as usize {
+            let cumulative = self
+                .get_ticket_at(i)
+                .checked_sub(tickets)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            if i < 1024 {
+                self.cumulative_tickets_1[i] = cumulative;
+            } else {
+                self.cumulative_tickets_2[i - 1024] = cumulative;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32, purchase_index: u32)]
+pub struct CancelPurchase<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets_purchase",
+            round.key().as_ref(),
+            &purchase_index.to_le_bytes()
+        ],
+        bump = round_tickets_purchase.bump,
+        constraint = round_tickets_purchase.player == player.key() @ RaffleError::Unauthorized
+    )]
+    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"player_stats",
+            sol_raffle.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    // The player's purchase count, if one was kept for the round
+    #[account(
+        mut,
+        seeds = [
+            b"wallet_purchases",
+            round.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = wallet_purchases.bump
+    )]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+
+    // Required while the round has an open match
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_match",
+            round.key().as_ref(),
+            sponsor_match.sponsor.as_ref()
+        ],
+        bump = sponsor_match.bump
+    )]
+    pub sponsor_match: Option<Account<'info, SponsorMatch>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct CancelRound<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump = pending_queue.load()?.bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<RefundBitmap>(),
+        seeds = [b"refund_bitmap", round.key().as_ref()],
+        bump
+    )]
+    pub refund_bitmap: AccountLoader<'info, RefundBitmap>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimRefundsBatch<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+}
+
+// Account data structs
+// One bit per purchase_index of a cancelled round, set once the purchase is refunded
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct RefundBitmap {
+    pub round: Pubkey, //connection with Round struct
+    pub bits: [u8; MAX_TICKETS / 8],
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+// Events
+#[event]
+pub struct CancelPenaltyUpdated {
+    pub token_raffle: Pubkey,
+    pub penalty_bps: Option<u16>,
+}
+
+#[event]
+pub struct PurchaseCancelled {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub purchase_index: u32,
+    pub player: Pubkey,
+    pub tickets_count: u32,
+    pub refund: u64,
+    pub penalty: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundCancelled {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub carried_over: u64, // to the next round's prize
+    pub jackpot_kept: u64, // stays in the mega jackpot
+    pub commission_kept: u64, // stays in commission_vault, CommissionMode::Immediate
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RefundClaimed {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub purchase_index: u32,
+    pub player: Pubkey,
+    pub refund: u64,
+    pub timestamp: i64,
+}
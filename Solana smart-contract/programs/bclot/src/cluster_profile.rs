@@ -0,0 +1,214 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+// Only authority, right after initialize_raffle and set_ticket_peg. From then on purchases and
+// randomness requests must pass the profile, and feeds, VRF program and treasury must match it.
+// The default BTC peg prices off any feed passed, so the peg must name its feeds first. The
+// cluster can't change
+pub fn initialize_cluster_profile(
+    ctx: Context<InitializeClusterProfile>,
+    cluster: Cluster,
+    args: ClusterProfileArgs,
+) -> Result<()> {
+    args.validate()?;
+    require!(
+        !ctx.accounts.raffle_state.ticket_peg.is_empty(),
+        RaffleError::TicketPegFeedRequired
+    );
+
+    let cluster_profile = &mut ctx.accounts.cluster_profile;
+    cluster_profile.cluster = cluster;
+    cluster_profile.apply(&args);
+    cluster_profile.bump = ctx.bumps.cluster_profile;
+
+    ctx.accounts.raffle_state.cluster_profile_enabled = true;
+
+    msg!("✅ Cluster profile initialized for {:?}", cluster);
+
+    Ok(())
+}
+
+// Only authority. Moves the pins to new feeds or VRF accounts on the same cluster
+pub fn set_cluster_profile(ctx: Context<UpdateClusterProfile>, args: ClusterProfileArgs) -> Result<()> {
+    args.validate()?;
+
+    let cluster_profile = &mut ctx.accounts.cluster_profile;
+    cluster_profile.apply(&args);
+
+    emit!(ClusterProfileUpdated {
+        cluster: cluster_profile.cluster,
+        oracle_program: args.oracle_program,
+        sol_feed: args.sol_feed,
+        vrf_program: args.vrf_program,
+        vrf_treasury: args.vrf_treasury,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::ClusterProfileChanged,
+        cluster_profile.cluster as u64,
+    )?;
+
+    Ok(())
+}
+
+// With the profile set up it must be passed, so leaving it out doesn't skip the checks
+pub fn require_cluster_profile<'a>(
+    raffle_state: &RaffleState,
+    cluster_profile: Option<&'a ClusterProfile>,
+) -> Result<Option<&'a ClusterProfile>> {
+    match cluster_profile {
+        None if raffle_state.cluster_profile_enabled => err!(RaffleError::ClusterProfileRequired),
+        cluster_profile => Ok(cluster_profile),
+    }
+}
+
+// The profile to read feeds under, the SOL feed has to be the pinned one
+pub fn check_cluster_sol_feed<'a>(
+    raffle_state: &RaffleState,
+    cluster_profile: Option<&'a ClusterProfile>,
+    sol_price_feed: &AccountInfo,
+) -> Result<Option<&'a ClusterProfile>> {
+    let cluster_profile = require_cluster_profile(raffle_state, cluster_profile)?;
+    if let Some(profile) = cluster_profile {
+        if sol_price_feed.key() != profile.sol_feed {
+            return Err(error!(RaffleError::ClusterMismatch).with_account_name(sol_price_feed.key().to_string()));
+        }
+    }
+
+    Ok(cluster_profile)
+}
+
+impl ClusterProfileArgs {
+    fn validate(&self) -> Result<()> {
+        require!(
+            self.oracle_program != Pubkey::default()
+                && self.sol_feed != Pubkey::default()
+                && self.vrf_program != Pubkey::default()
+                && self.vrf_treasury != Pubkey::default(),
+            RaffleError::InvalidClusterProfile
+        );
+        Ok(())
+    }
+}
+
+impl ClusterProfile {
+    fn apply(&mut self, args: &ClusterProfileArgs) {
+        self.oracle_program = args.oracle_program;
+        self.sol_feed = args.sol_feed;
+        self.vrf_program = args.vrf_program;
+        self.vrf_treasury = args.vrf_treasury;
+    }
+
+    // Feeds of another cluster's oracle program are rejected, not failed over
+    pub fn check_feed(&self, feed: &AccountInfo) -> Result<()> {
+        if *feed.owner != self.oracle_program {
+            return Err(error!(RaffleError::ClusterMismatch).with_account_name(feed.key().to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn check_vrf(&self, vrf: &AccountInfo, treasury: &AccountInfo) -> Result<()> {
+        for (key, pinned) in [(vrf.key(), self.vrf_program), (treasury.key(), self.vrf_treasury)] {
+            if key != pinned {
+                return Err(error!(RaffleError::ClusterMismatch).with_account_name(key.to_string()));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeClusterProfile<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ClusterProfile::INIT_SPACE,
+        seeds = [b"cluster_profile"],
+        bump
+    )]
+    pub cluster_profile: Account<'info, ClusterProfile>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateClusterProfile<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Account<'info, ClusterProfile>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum Cluster {
+    Devnet,
+    Mainnet,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ClusterProfileArgs {
+    pub oracle_program: Pubkey, // Switchboard on-demand program of the cluster, owns every feed read
+    pub sol_feed: Pubkey,
+    pub vrf_program: Pubkey,
+    pub vrf_treasury: Pubkey, // ORAO treasury of the cluster
+}
+
+// Addresses valid on the cluster the program is deployed to
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ClusterProfile {
+    pub cluster: Cluster,
+    pub oracle_program: Pubkey,
+    pub sol_feed: Pubkey,
+    pub vrf_program: Pubkey,
+    pub vrf_treasury: Pubkey,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct ClusterProfileUpdated {
+    pub cluster: Cluster,
+    pub oracle_program: Pubkey,
+    pub sol_feed: Pubkey,
+    pub vrf_program: Pubkey,
+    pub vrf_treasury: Pubkey,
+}
@@ -0,0 +1,442 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionMode,
+    Round,
+    RoundStatus,
+    RaffleError,
+    RaffleState,
+    UpdateTokenRaffle,
+    SECONDS_IN_DAY,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::insurance::{insurance_share, InsuranceSkimmed};
+use crate::solvency::release_in_ledger_account;
+
+const MAX_BPS: u16 = 10_000;
+pub const MAX_FEE_TIERS: usize = 4;
+
+// Only authority. Entrance fee falling as the pot grows, empty `tiers` restores the flat fee.
+// Tiers go by increasing min_pot_lamports with non-increasing fees, mega rounds keep their own fee
+pub fn set_fee_tiers(ctx: Context<UpdateTokenRaffle>, tiers: Vec<FeeTier>) -> Result<()> {
+    require!(tiers.len() <= MAX_FEE_TIERS, RaffleError::InvalidFeeTiers);
+    require!(
+        tiers.iter().all(|tier| tier.fee_bps <= MAX_BPS),
+        RaffleError::InvalidFeeTiers
+    );
+    require!(
+        tiers.windows(2).all(|pair| {
+            pair[0].min_pot_lamports < pair[1].min_pot_lamports && pair[0].fee_bps >= pair[1].fee_bps
+        }),
+        RaffleError::InvalidFeeTiers
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.fee_tiers = tiers.clone();
+
+    emit!(FeeTiersUpdated {
+        token_raffle: sol_raffle.key(),
+        tiers: tiers.clone(),
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::FeeTiersChanged,
+        tiers.len() as u64,
+    )?;
+
+    Ok(())
+}
+
+// Pays a SOL round's commission out of sol_vault: `insurance_bps` of it goes to
+// insurance_vault, the rest to the beneficiary. Returns the beneficiary's part
+pub fn settle_sol_commission<'info>(
+    raffle_state: &RaffleState,
+    vault: &AccountInfo<'info>,
+    vault_ledger: &AccountInfo<'info>,
+    beneficiary: &AccountInfo<'info>,
+    insurance_vault: &AccountInfo<'info>,
+    round_id: u32,
+    commission_amount: u64,
+) -> Result<u64> {
+    let insurance_amount = insurance_share(raffle_state, insurance_vault, commission_amount)?;
+    let beneficiary_amount = commission_amount - insurance_amount;
+
+    **vault.try_borrow_mut_lamports()? -= commission_amount;
+    **beneficiary.try_borrow_mut_lamports()? += beneficiary_amount;
+    **insurance_vault.try_borrow_mut_lamports()? += insurance_amount;
+    release_in_ledger_account(vault_ledger, commission_amount)?;
+
+    msg!("✓ Transferred {} lamports to beneficiary", beneficiary_amount);
+
+    if insurance_amount > 0 {
+        emit!(InsuranceSkimmed {
+            round_id,
+            commission_amount,
+            amount: insurance_amount,
+        });
+    }
+
+    Ok(beneficiary_amount)
+}
+
+// Only authority. Run once before switching any raffle away from CommissionMode::OnClaim
+pub fn initialize_commission_vault(ctx: Context<InitializeCommissionVault>) -> Result<()> {
+    let commission_vault = &mut ctx.accounts.commission_vault;
+    commission_vault.bump = ctx.bumps.commission_vault;
+
+    msg!("✅ Commission vault initialized");
+    msg!("📍 Commission vault PDA: {}", commission_vault.key());
+
+    Ok(())
+}
+
+// Only authority. Applies to rounds created afterwards, each round keeps the mode it started with
+pub fn set_commission_mode(ctx: Context<UpdateTokenRaffle>, mode: CommissionMode) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.commission_mode = mode.clone();
+
+    emit!(CommissionModeUpdated {
+        token_raffle: sol_raffle.key(),
+        mode: mode.clone(),
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::CommissionModeChanged,
+        mode as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Caps what the beneficiary can withdraw per UTC day, so a leaked
+// beneficiary key can't drain commission_vault at once. 0 - no cap
+pub fn set_commission_withdrawal_cap(ctx: Context<SetCommissionWithdrawalCap>, daily_cap: u64) -> Result<()> {
+    ctx.accounts.commission_vault.daily_cap = daily_cap;
+
+    emit!(CommissionWithdrawalCapUpdated { daily_cap });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::CommissionWithdrawalCapChanged,
+        daily_cap,
+    )?;
+
+    Ok(())
+}
+
+// Signed by the beneficiary. Pays out commission accrued in commission_vault, up to
+// daily_cap per UTC day. `insurance_bps` of the amount goes to insurance_vault as with
+// claim-time settlement
+pub fn withdraw_commission(ctx: Context<WithdrawCommission>, amount: u64) -> Result<()> {
+    let commission_vault = &mut ctx.accounts.commission_vault;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(amount > 0, RaffleError::InvalidAmount);
+    require!(
+        commission_vault.balance >= amount,
+        RaffleError::InsufficientVaultBalance
+    );
+    commission_vault.record_daily_withdrawal(amount, now)?;
+
+    let insurance_amount = insurance_share(&ctx.accounts.raffle_state, &ctx.accounts.insurance_vault, amount)?;
+    let beneficiary_amount = amount - insurance_amount;
+
+    let commission_vault_info = commission_vault.to_account_info();
+    **commission_vault_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.beneficiary.try_borrow_mut_lamports()? += beneficiary_amount;
+    **ctx.accounts.insurance_vault.try_borrow_mut_lamports()? += insurance_amount;
+
+    commission_vault.balance -= amount;
+    commission_vault.total_withdrawn = commission_vault
+        .total_withdrawn
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("✓ Withdrew {} lamports of commission to beneficiary", beneficiary_amount);
+
+    emit!(CommissionWithdrawn {
+        beneficiary: ctx.accounts.beneficiary.key(),
+        amount,
+        insurance_amount,
+        balance: commission_vault.balance,
+        withdrawn_today: commission_vault.withdrawn_today,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// CommissionMode::Immediate purchases send their commission straight to commission_vault
+pub fn accrue_purchase_commission<'info>(
+    commission_vault: &mut Account<'info, CommissionVault>,
+    player: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    round: &mut Round,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: player.clone(),
+                to: commission_vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    commission_vault.accrue(amount)?;
+    round.commission_accrued = round
+        .commission_accrued
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Same for purchases paid out of sol_vault, like a prize rolled into tickets
+pub fn accrue_vault_commission<'info>(
+    commission_vault: &mut Account<'info, CommissionVault>,
+    vault: &AccountInfo<'info>,
+    round: &mut Round,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    **vault.try_borrow_mut_lamports()? -= amount;
+    **commission_vault.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    commission_vault.accrue(amount)?;
+    round.commission_accrued = round
+        .commission_accrued
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Run from the VRF callback for CommissionMode::OnCompletion rounds: the drawn round's
+// commission moves out of sol_vault into commission_vault. Returns the amount moved
+pub fn accrue_round_commission<'info>(
+    commission_vault_info: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    round: &mut Round,
+) -> Result<u64> {
+    if round.commission_mode != CommissionMode::OnCompletion
+        || round.status != RoundStatus::Completed
+        || round.commission_settled
+    {
+        return Ok(0);
+    }
+    if commission_vault_info.data_is_empty() || commission_vault_info.owner != &crate::id() {
+        return Ok(0);
+    }
+
+    let amount = round.commission_balance;
+    let mut commission_vault =
+        CommissionVault::try_deserialize(&mut &commission_vault_info.try_borrow_data()?[..])?;
+
+    **vault.try_borrow_mut_lamports()? -= amount;
+    **commission_vault_info.try_borrow_mut_lamports()? += amount;
+
+    commission_vault.accrue(amount)?;
+    commission_vault.try_serialize(&mut &mut commission_vault_info.try_borrow_mut_data()?[..])?;
+
+    round.commission_balance = 0;
+    round.commission_accrued = round
+        .commission_accrued
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.commission_settled = true;
+
+    msg!("✓ Round {} commission of {} lamports moved to commission_vault", round.round_id, amount);
+
+    emit!(CommissionAccrued {
+        round_id: round.round_id,
+        amount,
+        balance: commission_vault.balance,
+    });
+
+    Ok(amount)
+}
+
+impl CommissionVault {
+    pub fn accrue(&mut self, amount: u64) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_accrued = self
+            .total_accrued
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // The window resets at UTC midnight
+    pub fn record_daily_withdrawal(&mut self, amount: u64, now: i64) -> Result<()> {
+        let day = now / SECONDS_IN_DAY;
+        if day != self.withdrawal_day {
+            self.withdrawal_day = day;
+            self.withdrawn_today = 0;
+        }
+
+        let withdrawn_today = self
+            .withdrawn_today
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        require!(
+            self.daily_cap == 0 || withdrawn_today <= self.daily_cap,
+            RaffleError::CommissionWithdrawalCapExceeded
+        );
+        self.withdrawn_today = withdrawn_today;
+
+        Ok(())
+    }
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeCommissionVault<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + CommissionVault::INIT_SPACE,
+        seeds = [b"commission_vault"],
+        bump
+    )]
+    pub commission_vault: Account<'info, CommissionVault>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCommission<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"commission_vault"],
+        bump = commission_vault.bump
+    )]
+    pub commission_vault: Account<'info, CommissionVault>,
+
+    #[account(
+        mut,
+        constraint = beneficiary.key() == raffle_state.beneficiary @ RaffleError::InvalidBeneficiary
+    )]
+    pub beneficiary: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetCommissionWithdrawalCap<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"commission_vault"],
+        bump = commission_vault.bump
+    )]
+    pub commission_vault: Account<'info, CommissionVault>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct FeeTier {
+    pub min_pot_lamports: u64, // round prize_amount before the purchase
+    pub fee_bps: u16,
+}
+
+// Commission of CommissionMode::OnCompletion and Immediate rounds owed to the beneficiary
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct CommissionVault {
+    pub balance: u64, // lamports held on top of rent
+    pub total_accrued: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+    pub daily_cap: u64, // lamports per UTC day, 0 - no cap
+    pub withdrawal_day: i64, // days since epoch of withdrawn_today
+    pub withdrawn_today: u64,
+}
+
+// Events
+#[event]
+pub struct CommissionModeUpdated {
+    pub token_raffle: Pubkey,
+    pub mode: CommissionMode,
+}
+
+#[event]
+pub struct CommissionAccrued {
+    pub round_id: u32,
+    pub amount: u64,
+    pub balance: u64,
+}
+
+#[event]
+pub struct CommissionWithdrawn {
+    pub beneficiary: Pubkey,
+    pub amount: u64, // before the insurance share
+    pub insurance_amount: u64,
+    pub balance: u64,
+    pub withdrawn_today: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommissionWithdrawalCapUpdated {
+    pub daily_cap: u64,
+}
+
+#[event]
+pub struct FeeTiersUpdated {
+    pub token_raffle: Pubkey,
+    pub tiers: Vec<FeeTier>,
+}
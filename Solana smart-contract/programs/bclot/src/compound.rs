@@ -0,0 +1,557 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RoundTicketsPurchase,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    TicketPurchased,
+    append_cumulative_tickets,
+    initialize_round_tickets_purchase,
+    split_purchase,
+};
+use crate::access_list::require_entry_allowed;
+use crate::blocklist::Blocklist;
+use crate::commission::{accrue_vault_commission, settle_sol_commission, CommissionVault};
+use crate::daily_stats::{record_daily_purchase, utc_day, DailyStats};
+use crate::cluster_profile::{check_cluster_sol_feed, ClusterProfile};
+use crate::fee_discount::{discounted_fee_bps, split_fee_discount_accounts};
+use crate::jackpot::{contribute_to_jackpot_from_vault, MegaJackpot};
+use crate::oracle_failover::{FeedReader, FeedFailover};
+use crate::player_stats::PlayerStats;
+use crate::price_cache::{require_price_cache, PriceCache};
+use crate::price_feeds::{require_feed_registry, FeedRegistry};
+use crate::price_feeds::{apply_price_decay, calculate_round_ticket_price, check_price_circuit_breaker, snapshot_open_prices, PriceSampling};
+use crate::purchase_limits::{check_min_purchase, record_wallet_purchase, WalletPurchases};
+use crate::raffle_stats::{record_payout, record_raffle_purchase, PayoutStats, RaffleStats};
+use crate::records::record_purchase;
+use crate::schedule::{close_at_prize_target, round_fee_bps};
+use crate::solvency::{release_in_ledger_account, require_solvent};
+
+// Converts part or all of a won prize into tickets of the current open round.
+// Lamports never leave sol_vault: the spent part moves from the won round's pot
+// into the target round's pot, the remainder is paid out to the winner.
+pub fn roll_prize_into_tickets<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RollPrizeIntoTickets<'info>>,
+    round_id: u32,
+    target_round_id: u32,
+    purchase_index: u32,
+    count: u32,
+    access_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(count > 0, RaffleError::InvalidTicketCount);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+        RaffleError::MaintenanceMode
+    );
+    require!(!ctx.accounts.sol_raffle.sales_paused, RaffleError::SalesPaused);
+
+    let raffle_state = &ctx.accounts.raffle_state;
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    let round = &mut ctx.accounts.round;
+    let target_round = &mut ctx.accounts.target_round;
+
+    require!(
+        round.status == RoundStatus::Completed,
+        RaffleError::RoundNotCompleted
+    );
+    require!(
+        round.winner_address == Some(ctx.accounts.winner.key()),
+        RaffleError::NotTheWinner
+    );
+    require!(!round.prize_claimed, RaffleError::PrizeAlreadyClaimed);
+    require!(!round.frozen, RaffleError::RoundFrozen);
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+
+    require!(
+        sol_raffle.current_round_id == Some(target_round_id)
+            && current_time < sol_raffle.current_round_end_time.unwrap_or(0),
+        RaffleError::RoundNotAvailable
+    );
+    require!(
+        target_round.status == RoundStatus::Open,
+        RaffleError::RoundNotOpen
+    );
+    require!(!target_round.free_entry, RaffleError::FreeEntryRound);
+    require_entry_allowed(
+        raffle_state,
+        sol_raffle,
+        target_round,
+        ctx.accounts.blocklist.as_ref(),
+        ctx.accounts.attestation.as_ref(),
+        &ctx.accounts.winner.key(),
+        &access_proof,
+    )?;
+    require_eq!(
+        purchase_index,
+        target_round.purchases_count,
+        RaffleError::PurchaseIndexMismatch
+    );
+
+    let (fee_discount, price_accounts) =
+        split_fee_discount_accounts(ctx.remaining_accounts, &ctx.accounts.winner.key())?;
+    let cluster_profile = check_cluster_sol_feed(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_price_feed,
+    )?;
+    let feed_reader = FeedReader::new(
+        require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?,
+        ctx.accounts.feed_failover.as_deref(),
+        require_price_cache(&ctx.accounts.raffle_state, ctx.accounts.price_cache.as_deref())?,
+        cluster_profile,
+        price_accounts,
+    );
+    let oracle_price = calculate_round_ticket_price(
+        raffle_state,
+        sol_raffle,
+        target_round,
+        &feed_reader,
+        &ctx.accounts.reference_price_feed,
+        price_accounts,
+        &ctx.accounts.sol_price_feed,
+    )?;
+    require!(oracle_price > 0, RaffleError::InvalidTicketPrice);
+    snapshot_open_prices(target_round, &ctx.accounts.reference_price_feed, &ctx.accounts.sol_price_feed, &feed_reader)?;
+    check_price_circuit_breaker(target_round, raffle_state.max_price_deviation_bps, oracle_price)?;
+    let ticket_price = apply_price_decay(sol_raffle, target_round, oracle_price, current_time)?;
+    require!(ticket_price > 0, RaffleError::InvalidTicketPrice);
+
+    let cost = ticket_price
+        .checked_mul(count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    check_min_purchase(sol_raffle, count, cost)?;
+    require!(cost <= round.prize_amount, RaffleError::InsufficientFunds);
+
+    // The whole prize decides vesting, rolling part of it in doesn't get the rest out early
+    require!(
+        !raffle_state.is_vested_prize(round.prize_amount),
+        RaffleError::PrizeSubjectToVesting
+    );
+    let payout = round.prize_amount - cost;
+
+    // Split the spent part of the prize the way a bought purchase is split
+    let fee_bps = discounted_fee_bps(
+        round_fee_bps(raffle_state, sol_raffle, target_round),
+        fee_discount.fee_discount_config.as_ref(),
+        fee_discount.staker_position.as_ref(),
+        target_round.start_time,
+    );
+    let split = split_purchase(
+        raffle_state,
+        sol_raffle,
+        target_round,
+        cost,
+        fee_bps,
+        ctx.accounts.mega_jackpot.is_some(),
+    )?;
+    // Leaves sol_vault along with the payout
+    let moved_out = split.jackpot_amount + split.immediate_commission;
+
+    let commission_to_settle = round.commission_balance;
+    let total_required = payout
+        .checked_add(commission_to_settle)
+        .and_then(|total| total.checked_add(moved_out))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(
+        ctx.accounts.vault.lamports() >= total_required,
+        RaffleError::InsufficientVaultBalance
+    );
+    require_solvent(&ctx.accounts.vault_ledger, &ctx.accounts.vault, round_id)?;
+
+    let new_total = target_round
+        .total_tickets
+        .checked_add(count)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    append_cumulative_tickets(&ctx.accounts.round_tickets, new_total)?;
+
+    initialize_round_tickets_purchase(
+        &mut ctx.accounts.round_tickets_purchase,
+        target_round.key(),
+        target_round.purchases_count,
+        ctx.accounts.winner.key(),
+        count,
+        ctx.bumps.round_tickets_purchase,
+    )?;
+    let price_slot = feed_reader.price_slot(&ctx.accounts.sol_price_feed, &PriceSampling::for_round(target_round))?;
+    ctx.accounts.round_tickets_purchase.ticket_price = ticket_price;
+    ctx.accounts.round_tickets_purchase.oracle_price = oracle_price;
+    ctx.accounts.round_tickets_purchase.price_slot = price_slot;
+    ctx.accounts.round_tickets_purchase.fee_bps = fee_bps;
+    ctx.accounts.round_tickets_purchase.jackpot_bps = Some(split.jackpot_bps);
+
+    record_wallet_purchase(
+        ctx.accounts.wallet_purchases.as_mut(),
+        target_round.key(),
+        ctx.accounts.winner.key(),
+        ctx.bumps.wallet_purchases,
+        sol_raffle.max_purchases_per_wallet,
+    )?;
+
+    record_raffle_purchase(
+        &mut ctx.accounts.raffle_stats,
+        sol_raffle.key(),
+        ctx.bumps.raffle_stats,
+        target_round.purchases_count == 0,
+        count,
+        cost,
+    )?;
+    record_purchase(
+        &ctx.accounts.records,
+        target_round,
+        ctx.accounts.winner.key(),
+        cost,
+        0,
+        current_time,
+    )?;
+    let player_stats = &mut ctx.accounts.player_stats;
+    player_stats.player = ctx.accounts.winner.key();
+    player_stats.bump = ctx.bumps.player_stats;
+    record_daily_purchase(
+        &mut ctx.accounts.daily_stats,
+        sol_raffle.key(),
+        ctx.bumps.daily_stats,
+        Some(player_stats),
+        count,
+        cost,
+        current_time,
+    )?;
+
+    let ticket_start = target_round.total_tickets;
+    let purchase_index = target_round.purchases_count;
+    target_round.total_tickets = new_total;
+    target_round.purchases_count += 1;
+    close_at_prize_target(sol_raffle, target_round, current_time);
+
+    // Settle the won round: remainder to the winner, commission to beneficiary
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= payout;
+    **ctx.accounts.winner.try_borrow_mut_lamports()? += payout;
+    release_in_ledger_account(&ctx.accounts.vault_ledger, payout)?;
+
+    if split.immediate_commission > 0 {
+        let commission_vault = ctx
+            .accounts
+            .commission_vault
+            .as_mut()
+            .ok_or(RaffleError::MissingCommissionVault)?;
+        accrue_vault_commission(
+            commission_vault,
+            &ctx.accounts.vault,
+            target_round,
+            split.immediate_commission,
+        )?;
+    }
+    if let Some(mega_jackpot) = ctx.accounts.mega_jackpot.as_mut() {
+        contribute_to_jackpot_from_vault(mega_jackpot, &ctx.accounts.vault, split.jackpot_amount)?;
+    }
+    release_in_ledger_account(&ctx.accounts.vault_ledger, moved_out)?;
+
+    settle_sol_commission(
+        raffle_state,
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_ledger,
+        &ctx.accounts.beneficiary,
+        &ctx.accounts.insurance_vault,
+        round.round_id,
+        commission_to_settle,
+    )?;
+
+    round.prize_claimed = true;
+    round.commission_settled = true;
+    // The rolled part is paid too, into tickets
+    record_payout(
+        PayoutStats {
+            raffle_stats: &mut ctx.accounts.raffle_stats,
+            raffle_stats_bump: ctx.bumps.raffle_stats,
+            daily_stats: &mut ctx.accounts.daily_stats,
+            daily_stats_bump: ctx.bumps.daily_stats,
+            winner_history: &ctx.accounts.winner_history,
+        },
+        round,
+        round.prize_amount,
+        true,
+    )?;
+
+    msg!(
+        "✅ Rolled {} lamports of round {} prize into {} tickets of round {}",
+        cost,
+        round_id,
+        count,
+        target_round_id
+    );
+
+    emit!(TicketPurchased {
+        token: sol_raffle.token_mint,
+        round_id: target_round.round_id,
+        buyer: ctx.accounts.winner.key(),
+        count,
+        ticket_price,
+        oracle_price,
+        price_slot,
+        total_amount: cost,
+        prize_amount: split.prize_amount,
+        commission_amount: split.commission_amount,
+        fee_bps,
+        timestamp: current_time,
+        purchase_index,
+        ticket_start,
+        ticket_end: new_total,
+        effective_ticket_price: ticket_price,
+        total_tickets: new_total,
+    });
+
+    emit!(PrizeRolledIntoTickets {
+        round_id,
+        target_round_id,
+        winner: ctx.accounts.winner.key(),
+        tickets_count: count,
+        rolled_amount: cost,
+        paid_out_amount: payout,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32, target_round_id: u32, purchase_index: u32)]
+pub struct RollPrizeIntoTickets<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &target_round_id.to_le_bytes()
+        ],
+        bump = target_round.bump
+    )]
+    pub target_round: Account<'info, Round>,
+
+    /// CHECK: zero-copy tickets of the target round, mutated in place
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            target_round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + RoundTicketsPurchase::INIT_SPACE,
+        seeds = [
+            b"round_tickets_purchase",
+            target_round.key().as_ref(),
+            &purchase_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+
+    // Required while sol_raffle.max_purchases_per_wallet is set
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + WalletPurchases::INIT_SPACE,
+        seeds = [
+            b"wallet_purchases",
+            target_round.key().as_ref(),
+            winner.key().as_ref()
+        ],
+        bump
+    )]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+
+    // Counts the winner once a day in DailyStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [
+            b"player_stats",
+            sol_raffle.key().as_ref(),
+            winner.key().as_ref()
+        ],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    // Lifetime totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: WinnerHistory PDA for the recent winners widget, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: UncheckedAccount<'info>,
+
+    /// CHECK: Records PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = beneficiary.key() == raffle_state.beneficiary @ RaffleError::InvalidBeneficiary
+    )]
+    /// CHECK: key validated via constraint
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    // Required while jackpot_bps > 0
+    #[account(
+        mut,
+        seeds = [b"mega_jackpot"],
+        bump = mega_jackpot.bump
+    )]
+    pub mega_jackpot: Option<Account<'info, MegaJackpot>>,
+
+    // Required if the target round has CommissionMode::Immediate
+    #[account(
+        mut,
+        seeds = [b"commission_vault"],
+        bump = commission_vault.bump
+    )]
+    pub commission_vault: Option<Account<'info, CommissionVault>>,
+
+    /// CHECK: price feed of the first ticket peg component, basket feeds go in remaining_accounts
+    pub reference_price_feed: AccountInfo<'info>,
+
+    /// CHECK: SOL price feed account
+    pub sol_price_feed: AccountInfo<'info>,
+
+    // Fallbacks for stale feeds, the fallback feeds go in remaining_accounts after the basket feeds
+    #[account(
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Option<Account<'info, FeedFailover>>,
+
+    // Crank-refreshed feed prices, read instead of the feeds while fresh
+    #[account(
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Option<Account<'info, PriceCache>>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+
+    // Required once the cluster profile is set up, pins the SOL feed and the oracle program
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    /// CHECK: SAS attestation of the winner, required if sol_raffle.attestation_required. Validated in the handler
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct PrizeRolledIntoTickets {
+    pub round_id: u32,
+    pub target_round_id: u32,
+    pub winner: Pubkey,
+    pub tickets_count: u32,
+    pub rolled_amount: u64,
+    pub paid_out_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,226 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    TokenRaffle,
+    SECONDS_IN_DAY,
+};
+use crate::player_stats::PlayerStats;
+
+// UTC days since epoch
+pub fn utc_day(timestamp: i64) -> i64 {
+    timestamp.div_euclid(SECONDS_IN_DAY)
+}
+
+// Permissionless, the payer funds the account. Today's or tomorrow's, so a crank can open the
+// next day ahead of midnight
+pub fn initialize_daily_stats(ctx: Context<InitializeDailyStats>, day: i64) -> Result<()> {
+    let today = utc_day(Clock::get()?.unix_timestamp);
+    require!(day == today || day == today + 1, RaffleError::InvalidDailyStatsDay);
+
+    let daily_stats = &mut ctx.accounts.daily_stats;
+    daily_stats.token_raffle = ctx.accounts.sol_raffle.key();
+    daily_stats.day = day;
+    daily_stats.bump = ctx.bumps.daily_stats;
+
+    Ok(())
+}
+
+pub fn get_daily_stats(ctx: Context<GetDailyStats>, _day: i64) -> Result<DailyStats> {
+    Ok((*ctx.accounts.daily_stats).clone())
+}
+
+impl DailyStats {
+    // Purchase and payout paths create the day's account on first use, the seeds pin it to today
+    fn open(&mut self, token_raffle: Pubkey, now: i64, bump: u8) {
+        self.token_raffle = token_raffle;
+        self.day = utc_day(now);
+        self.bump = bump;
+    }
+}
+
+// Called by every purchase path. A buyer counts once a day per raffle, tracked in PlayerStats,
+// grants pass no PlayerStats and add no buyer
+pub fn record_daily_purchase(
+    daily_stats: &mut Account<DailyStats>,
+    token_raffle: Pubkey,
+    bump: u8,
+    player_stats: Option<&mut PlayerStats>,
+    tickets: u32,
+    volume: u64,
+    now: i64,
+) -> Result<()> {
+    daily_stats.open(token_raffle, now, bump);
+
+    if let Some(player_stats) = player_stats {
+        if player_stats.last_purchase_day != daily_stats.day {
+            player_stats.last_purchase_day = daily_stats.day;
+            daily_stats.unique_buyers = daily_stats
+                .unique_buyers
+                .checked_add(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+    }
+    daily_stats.tickets = daily_stats
+        .tickets
+        .checked_add(tickets as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    daily_stats.volume = daily_stats
+        .volume
+        .checked_add(volume)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    daily_stats.purchases_count = daily_stats
+        .purchases_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Called by record_payout, see there
+pub fn record_daily_prize_paid(
+    daily_stats: &mut Account<DailyStats>,
+    token_raffle: Pubkey,
+    bump: u8,
+    amount: u64,
+    first_payment: bool,
+) -> Result<()> {
+    daily_stats.open(token_raffle, Clock::get()?.unix_timestamp, bump);
+
+    daily_stats.prizes_paid = daily_stats
+        .prizes_paid
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if first_payment {
+        daily_stats.prizes_paid_count = daily_stats
+            .prizes_paid_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct InitializeDailyStats<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [b"daily_stats", sol_raffle.key().as_ref(), &day.to_le_bytes()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: i64)]
+pub struct GetDailyStats<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [b"daily_stats", sol_raffle.key().as_ref(), &day.to_le_bytes()],
+        bump = daily_stats.bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+}
+
+// Account data structs
+// Totals of one TokenRaffle over one UTC day
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct DailyStats {
+    pub token_raffle: Pubkey, //connection with TokenRaffle struct
+    pub day: i64, // UTC days since epoch
+    pub volume: u64, // lamports paid for tickets
+    pub tickets: u64, // bonus tickets included
+    pub purchases_count: u32,
+    pub unique_buyers: u32,
+    pub prizes_paid: u64, // lamports paid out this day, whichever day the round ended
+    pub prizes_paid_count: u32,
+    pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 20_000 * SECONDS_IN_DAY + 3_600;
+
+    fn player_stats() -> PlayerStats {
+        PlayerStats::try_deserialize_unchecked(&mut &vec![0u8; 8 + PlayerStats::INIT_SPACE][..]).unwrap()
+    }
+
+    fn daily_stats_data() -> Vec<u8> {
+        let mut data = vec![0u8; 8 + DailyStats::INIT_SPACE];
+        data[..8].copy_from_slice(DailyStats::DISCRIMINATOR);
+        data
+    }
+
+    #[test]
+    fn utc_day_starts_at_midnight() {
+        assert_eq!(utc_day(0), 0);
+        assert_eq!(utc_day(SECONDS_IN_DAY - 1), 0);
+        assert_eq!(utc_day(SECONDS_IN_DAY), 1);
+        assert_eq!(utc_day(-1), -1);
+    }
+
+    #[test]
+    fn buyer_counts_once_a_day() {
+        let key = Pubkey::new_unique();
+        let owner = crate::id();
+        let token_raffle = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = daily_stats_data();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let mut daily_stats = Account::<DailyStats>::try_from(&info).unwrap();
+        let mut player_stats = player_stats();
+
+        record_daily_purchase(&mut daily_stats, token_raffle, 254, Some(&mut player_stats), 3, 300, NOW).unwrap();
+        record_daily_purchase(&mut daily_stats, token_raffle, 254, Some(&mut player_stats), 2, 200, NOW + 60).unwrap();
+        // Promo grants add no buyer
+        record_daily_purchase(&mut daily_stats, token_raffle, 254, None, 1, 0, NOW + 120).unwrap();
+
+        assert_eq!(daily_stats.token_raffle, token_raffle);
+        assert_eq!(daily_stats.day, 20_000);
+        assert_eq!(daily_stats.bump, 254);
+        assert_eq!(daily_stats.unique_buyers, 1);
+        assert_eq!(daily_stats.purchases_count, 3);
+        assert_eq!(daily_stats.tickets, 6);
+        assert_eq!(daily_stats.volume, 500);
+        assert_eq!(player_stats.last_purchase_day, 20_000);
+    }
+
+    #[test]
+    fn buyer_counts_again_next_day() {
+        let key = Pubkey::new_unique();
+        let owner = crate::id();
+        let mut lamports = 0;
+        let mut data = daily_stats_data();
+        let info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        let mut daily_stats = Account::<DailyStats>::try_from(&info).unwrap();
+        let mut player_stats = player_stats();
+        player_stats.last_purchase_day = 19_999;
+
+        record_daily_purchase(&mut daily_stats, Pubkey::new_unique(), 254, Some(&mut player_stats), 1, 100, NOW).unwrap();
+
+        assert_eq!(daily_stats.unique_buyers, 1);
+        assert_eq!(player_stats.last_purchase_day, 20_000);
+    }
+}
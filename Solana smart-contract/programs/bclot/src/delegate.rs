@@ -0,0 +1,245 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use crate::{
+    Round,
+    RoundTickets,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    SettlementAccounts,
+    attach_memo,
+    pay_out_prize,
+};
+use crate::blocklist::{require_not_blocked, Blocklist};
+use crate::daily_stats::{utc_day, DailyStats};
+use crate::raffle_stats::{record_payout, PayoutStats, RaffleStats};
+
+// Signed by the wallet itself. The delegate may claim its prizes, which still land in
+// the wallet. Pubkey::default() removes the delegate
+pub fn set_claim_delegate(ctx: Context<SetClaimDelegate>, delegate: Pubkey) -> Result<()> {
+    let claim_delegate = &mut ctx.accounts.claim_delegate;
+    claim_delegate.owner = ctx.accounts.owner.key();
+    claim_delegate.delegate = delegate;
+    claim_delegate.bump = ctx.bumps.claim_delegate;
+
+    msg!("✅ Claim delegate of {} set to {}", claim_delegate.owner, delegate);
+
+    emit!(ClaimDelegateUpdated {
+        owner: claim_delegate.owner,
+        delegate,
+    });
+
+    Ok(())
+}
+
+pub fn claim_prize_as_delegate(
+    ctx: Context<ClaimPrizeAsDelegate>,
+    _round_id: u32,
+    memo: Option<String>,
+) -> Result<()> {
+    require!(
+        ctx.accounts.round.winner_address == Some(ctx.accounts.winner.key()),
+        RaffleError::NotTheWinner
+    );
+    for address in [ctx.accounts.winner.key(), ctx.accounts.delegate.key()] {
+        require_not_blocked(&ctx.accounts.raffle_state, ctx.accounts.blocklist.as_ref(), &address)?;
+    }
+
+    let winner = ctx.accounts.winner.to_account_info();
+
+    pay_out_prize(
+        &ctx.accounts.raffle_state,
+        &mut ctx.accounts.round,
+        winner.key(),
+        &winner,
+        SettlementAccounts {
+            vault: &ctx.accounts.vault,
+            vault_ledger: &ctx.accounts.vault_ledger,
+            beneficiary: &ctx.accounts.beneficiary,
+            insurance_vault: &ctx.accounts.insurance_vault,
+        },
+    )?;
+    record_payout(
+        PayoutStats {
+            raffle_stats: &mut ctx.accounts.raffle_stats,
+            raffle_stats_bump: ctx.bumps.raffle_stats,
+            daily_stats: &mut ctx.accounts.daily_stats,
+            daily_stats_bump: ctx.bumps.daily_stats,
+            winner_history: &ctx.accounts.winner_history,
+        },
+        &ctx.accounts.round,
+        ctx.accounts.round.prize_amount,
+        true,
+    )?;
+
+    attach_memo(ctx.accounts.memo_program.as_ref(), memo)
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct SetClaimDelegate<'info> {
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + ClaimDelegate::INIT_SPACE,
+        seeds = [b"claim_delegate", owner.key().as_ref()],
+        bump
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct ClaimPrizeAsDelegate<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = rent_vault,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+
+    #[account(
+        seeds = [b"claim_delegate", winner.key().as_ref()],
+        bump = claim_delegate.bump,
+        constraint = claim_delegate.delegate == delegate.key() @ RaffleError::Unauthorized
+    )]
+    pub claim_delegate: Account<'info, ClaimDelegate>,
+
+    // Receives the prize
+    #[account(mut)]
+    pub winner: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub delegate: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_vault"],
+        bump
+    )]
+    pub rent_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = beneficiary.key() == raffle_state.beneficiary @ RaffleError::InvalidBeneficiary
+    )]
+    /// CHECK: key validated via constraint
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    // Lifetime prize totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: WinnerHistory PDA for the recent winners widget, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = delegate,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    // Required with a memo
+    pub memo_program: Option<Program<'info, Memo>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+// Key allowed to claim prizes on behalf of `owner`
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ClaimDelegate {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct ClaimDelegateUpdated {
+    pub owner: Pubkey,
+    pub delegate: Pubkey,
+}
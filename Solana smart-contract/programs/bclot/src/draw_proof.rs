@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::{
+    Round,
+    RoundStatus,
+    RoundTickets,
+    RaffleError,
+    TokenRaffle,
+};
+use crate::migration::Versioned;
+
+// Permissionless. Snapshots what a verifier needs to recompute the draw: winner_ticket_index is
+// the first 8 bytes of randomness (LE) modulo total_tickets + house_tickets, less the first winner's
+// range on a redraw, and the purchase is the first cumulative entry above it. Has to run before
+// settlement closes RoundTickets, publishing again after a redraw overwrites the proof
+pub fn publish_draw_proof(ctx: Context<PublishDrawProof>, round_id: u32) -> Result<()> {
+    let round = &ctx.accounts.round;
+    round.check_version()?;
+
+    require!(
+        round.status == RoundStatus::Completed && !round.redraw_pending,
+        RaffleError::RoundNotCompleted
+    );
+    let winner_ticket_index = round.winner_ticket_index.ok_or(RaffleError::RoundNotCompleted)?;
+    let winner_purchase_index = round
+        .winner_purchase_index
+        .ok_or(RaffleError::WinnerNotFinalized)?;
+
+    let round_tickets = ctx.accounts.round_tickets.load()?;
+    round_tickets.check_version()?;
+
+    let draw_proof = &mut ctx.accounts.draw_proof;
+    draw_proof.round = round.key();
+    draw_proof.round_id = round_id;
+    draw_proof.randomness = round.randomness;
+    draw_proof.vrf_request = round.vrf_request;
+    draw_proof.total_tickets = round.total_tickets;
+    draw_proof.house_tickets = round.house_tickets;
+    draw_proof.purchases_count = round_tickets.len;
+    draw_proof.winner_ticket_index = winner_ticket_index;
+    draw_proof.winner_purchase_index = winner_purchase_index;
+    draw_proof.first_winner_purchase_index = round.first_winner_purchase_index;
+    draw_proof.tickets_checksum = tickets_checksum(&round_tickets);
+    draw_proof.published_at = Clock::get()?.unix_timestamp;
+    draw_proof.bump = ctx.bumps.draw_proof;
+
+    msg!("✅ Draw proof of round {} published", round_id);
+
+    emit!(DrawProofPublished {
+        round_id,
+        draw_proof: draw_proof.key(),
+        tickets_checksum: draw_proof.tickets_checksum,
+    });
+
+    Ok(())
+}
+
+// keccak of the cumulative ticket counts as little-endian u32, in purchase order
+pub fn tickets_checksum(round_tickets: &RoundTickets) -> [u8; 32] {
+    let len = round_tickets.len as usize;
+    let first_page = len.min(RoundTickets::PAGE_SIZE);
+    let second_page = len - first_page;
+
+    keccak::hashv(&[
+        bytemuck::cast_slice(&round_tickets.cumulative_tickets_1[..first_page]),
+        bytemuck::cast_slice(&round_tickets.cumulative_tickets_2[..second_page]),
+    ])
+    .to_bytes()
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct PublishDrawProof<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + DrawProof::INIT_SPACE,
+        seeds = [b"draw_proof", round.key().as_ref()],
+        bump
+    )]
+    pub draw_proof: Account<'info, DrawProof>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+// Self-contained record of one round's draw, outlives the round's RoundTickets
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct DrawProof {
+    pub round: Pubkey, //connection with Round struct
+    pub round_id: u32,
+    pub randomness: [u8; 64],
+    pub vrf_request: Pubkey, // ORAO request account that delivered the randomness
+    pub total_tickets: u32,
+    pub house_tickets: u32,
+    pub purchases_count: u32,
+    pub winner_ticket_index: u32,
+    pub winner_purchase_index: u32,
+    pub first_winner_purchase_index: Option<u32>, // left out of the pool by a redraw
+    pub tickets_checksum: [u8; 32],
+    pub published_at: i64,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct DrawProofPublished {
+    pub round_id: u32,
+    pub draw_proof: Pubkey,
+    pub tickets_checksum: [u8; 32],
+}
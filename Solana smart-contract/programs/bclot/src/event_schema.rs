@@ -0,0 +1,759 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::*;
+
+// Off-chain only, built with `no-entrypoint`. Anchor logs an event as "Program data: " and the
+// base64 of its discriminator and borsh body. Events only change by appending fields, bumping
+// their version here, so decode_event reads the fields it knows and skips what a newer program
+// appended. Fields are listed in declaration order, a renamed or removed field fails the build
+
+pub struct EventField {
+    pub name: &'static str,
+    pub ty: &'static str,
+}
+
+pub struct EventSchema {
+    pub name: &'static str,
+    pub version: u8,
+    pub discriminator: &'static [u8],
+    pub fields: &'static [EventField],
+}
+
+macro_rules! event_registry {
+    ($($event:ident $version:literal { $($field:ident: $ty:ty,)* })*) => {
+        pub const EVENT_SCHEMAS: &[EventSchema] = &[
+            $(EventSchema {
+                name: stringify!($event),
+                version: $version,
+                discriminator: $event::DISCRIMINATOR,
+                fields: &[$(EventField { name: stringify!($field), ty: stringify!($ty) },)*],
+            },)*
+        ];
+
+        pub enum DecodedEvent {
+            $($event($event),)*
+        }
+
+        // None for an unknown discriminator or a body shorter than the known layout
+        pub fn decode_event(data: &[u8]) -> Option<DecodedEvent> {
+            let discriminator = data.get(..8)?;
+            let mut body = &data[8..];
+            $(
+                if discriminator == $event::DISCRIMINATOR {
+                    return $event::deserialize(&mut body).ok().map(DecodedEvent::$event);
+                }
+            )*
+            None
+        }
+
+        #[allow(dead_code, unused_variables)]
+        fn check_layouts() {
+            $(
+                let _ = |event: &$event| {
+                    let $event { $($field,)* } = event;
+                    $(let _: &$ty = $field;)*
+                };
+            )*
+        }
+    };
+}
+
+pub fn event_schema(discriminator: &[u8]) -> Option<&'static EventSchema> {
+    EVENT_SCHEMAS
+        .iter()
+        .find(|schema| schema.discriminator == discriminator)
+}
+
+event_registry! {
+    AccessListUpdated 1 {
+        token_raffle: Pubkey,
+        round_id: Option<u32>,
+        access_root: Option<[u8; 32]>,
+    }
+    AddressBlocked 1 {
+        address: Pubkey,
+    }
+    AddressUnblocked 1 {
+        address: Pubkey,
+    }
+    AllRequestsCompleted 1 {}
+    AttestationRequirementUpdated 1 {
+        token_raffle: Pubkey,
+        required: bool,
+        credential: Pubkey,
+        schema: Pubkey,
+    }
+    AuditRecorded 1 {
+        seq: u64,
+        actor: Pubkey,
+        action: u8,
+        value: u64,
+        timestamp: i64,
+    }
+    AuthorityTransferProposed 1 {
+        authority: Pubkey,
+        pending_authority: Pubkey,
+    }
+    AuthorityTransferred 1 {
+        old_authority: Pubkey,
+        new_authority: Pubkey,
+    }
+    BeneficiaryChanged 1 {
+        old_beneficiary: Pubkey,
+        new_beneficiary: Pubkey,
+    }
+    BlocklistToggled 1 {
+        enabled: bool,
+    }
+    CachedFeedsUpdated 1 {
+        sol_feed: Pubkey,
+        reference_feeds: Vec<Pubkey>,
+        max_age_slots: u64,
+    }
+    CancelPenaltyUpdated 1 {
+        token_raffle: Pubkey,
+        penalty_bps: Option<u16>,
+    }
+    ClaimDelegateUpdated 1 {
+        owner: Pubkey,
+        delegate: Pubkey,
+    }
+    ClusterProfileUpdated 1 {
+        cluster: Cluster,
+        oracle_program: Pubkey,
+        sol_feed: Pubkey,
+        vrf_program: Pubkey,
+        vrf_treasury: Pubkey,
+    }
+    CommissionAccrued 1 {
+        round_id: u32,
+        amount: u64,
+        balance: u64,
+    }
+    CommissionModeUpdated 1 {
+        token_raffle: Pubkey,
+        mode: CommissionMode,
+    }
+    CommissionWithdrawalCapUpdated 1 {
+        daily_cap: u64,
+    }
+    CommissionWithdrawn 1 {
+        beneficiary: Pubkey,
+        amount: u64,
+        insurance_amount: u64,
+        balance: u64,
+        withdrawn_today: u64,
+        timestamp: i64,
+    }
+    CommitRoundPriceUpdated 1 {
+        token_raffle: Pubkey,
+        enabled: bool,
+    }
+    DrawDeferred 1 {
+        round_id: u32,
+        winner_ticket_index: u32,
+        pages: u32,
+    }
+    DrawProofPublished 1 {
+        round_id: u32,
+        draw_proof: Pubkey,
+        tickets_checksum: [u8; 32],
+    }
+    EmergencyPriceCleared 1 {
+        slot: u64,
+    }
+    EmergencyPriceSet 1 {
+        reference_feed: Pubkey,
+        reference_usd: u64,
+        sol_feed: Pubkey,
+        sol_usd: u64,
+        expiry_slot: u64,
+    }
+    EmergencyPriceUsed 1 {
+        feed: Pubkey,
+        price_usd: u64,
+        expiry_slot: u64,
+        slot: u64,
+    }
+    EntranceFeeChanged 1 {
+        old_percentage: u8,
+        new_percentage: u8,
+    }
+    FeeDiscountUpdated 1 {
+        min_stake: u64,
+        discount_bps: u16,
+    }
+    FeeTiersUpdated 1 {
+        token_raffle: Pubkey,
+        tiers: Vec<FeeTier>,
+    }
+    FeedFailoverUpdated 1 {
+        primary: Pubkey,
+        fallbacks: Vec<Pubkey>,
+    }
+    FeedReadLimitsUpdated 1 {
+        mint: Pubkey,
+        feed: Pubkey,
+        max_staleness_slots: u64,
+        min_samples: u8,
+    }
+    FirstTicketBonusAwarded 1 {
+        token: Pubkey,
+        round_id: u32,
+        buyer: Pubkey,
+        purchase_index: u32,
+        bonus_tickets: u32,
+        timestamp: i64,
+        round_start_time: i64,
+        round_end_time: i64,
+    }
+    FirstTicketBonusUpdated 1 {
+        token_raffle: Pubkey,
+        enabled: bool,
+        bonus_tickets: u32,
+        every: u32,
+    }
+    FreeEntryClaimed 1 {
+        token: Pubkey,
+        round_id: u32,
+        player: Pubkey,
+        purchase_index: u32,
+        count: u32,
+        wallet_tickets: u32,
+        timestamp: i64,
+    }
+    FreeEntryRoundOpened 1 {
+        token: Pubkey,
+        round_id: u32,
+        prize_amount: u64,
+        max_tickets_per_wallet: u32,
+        start_time: i64,
+        end_time: i64,
+    }
+    GovernanceTokensStaked 1 {
+        staker: Pubkey,
+        amount: u64,
+        position_amount: u64,
+    }
+    GovernanceTokensUnstaked 1 {
+        staker: Pubkey,
+        amount: u64,
+        position_amount: u64,
+    }
+    HouseTicketConfigUpdated 1 {
+        cadence_id: u8,
+        threshold: u32,
+        house_tickets: u32,
+    }
+    HouseWon 1 {
+        round_id: u32,
+        amount: u64,
+        to_jackpot: bool,
+        timestamp: i64,
+    }
+    InsuranceBpsUpdated 1 {
+        insurance_bps: u16,
+    }
+    InsuranceFunded 1 {
+        funder: Pubkey,
+        amount: u64,
+        balance: u64,
+        timestamp: i64,
+    }
+    InsurancePaidOut 1 {
+        authority: Pubkey,
+        recipient: Pubkey,
+        amount: u64,
+        reason: u8,
+        balance: u64,
+        timestamp: i64,
+    }
+    InsuranceSkimmed 1 {
+        round_id: u32,
+        commission_amount: u64,
+        amount: u64,
+    }
+    JackpotConfigUpdated 1 {
+        jackpot_bps: u16,
+        odds: u32,
+    }
+    KeeperAdded 1 {
+        keeper: Pubkey,
+    }
+    KeeperAllowlistToggled 1 {
+        enabled: bool,
+    }
+    KeeperRemoved 1 {
+        keeper: Pubkey,
+    }
+    KeeperTipPaid 1 {
+        round_id: u32,
+        keeper: Pubkey,
+        amount: u64,
+    }
+    KeeperTipUpdated 1 {
+        keeper_tip: u64,
+    }
+    LoyaltyConfigUpdated 1 {
+        token: Pubkey,
+        points_per_sol: u64,
+        points_per_ticket: u64,
+    }
+    LoyaltyPointsRedeemed 1 {
+        token: Pubkey,
+        round_id: u32,
+        player: Pubkey,
+        purchase_index: u32,
+        count: u32,
+        points_spent: u64,
+        points_left: u64,
+        reward_value: u64,
+        timestamp: i64,
+    }
+    MaintenanceScheduled 1 {
+        start: i64,
+        end: i64,
+    }
+    MaxPurchasesPerWalletUpdated 1 {
+        token_raffle: Pubkey,
+        max_purchases: u32,
+    }
+    MegaJackpotWon 1 {
+        round_id: u32,
+        amount: u64,
+        prize_amount: u64,
+        timestamp: i64,
+    }
+    MegaRoundConfigUpdated 1 {
+        cadence_id: u8,
+        weekday: Option<u8>,
+        fee_percentage: u8,
+        duration: i64,
+        extra_jackpot_bps: u16,
+    }
+    MinPrizeToppedUp 1 {
+        round_id: u32,
+        from_insurance: u64,
+        from_jackpot: u64,
+        prize_amount: u64,
+        min_prize_lamports: u64,
+    }
+    MinPrizeUpdated 1 {
+        cadence_id: u8,
+        min_prize_lamports: u64,
+    }
+    MinPurchaseUpdated 1 {
+        token_raffle: Pubkey,
+        min_tickets: u32,
+        min_lamports: u64,
+    }
+    OperatorChanged 1 {
+        old_operator: Pubkey,
+        new_operator: Pubkey,
+    }
+    OracleFailure 1 {
+        feed: Pubkey,
+        reason: OracleFailureReason,
+        slot: u64,
+    }
+    PriceCacheRefreshed 1 {
+        feeds: u8,
+        slot: u64,
+    }
+    PriceCircuitBreakerUpdated 1 {
+        max_deviation_bps: u16,
+    }
+    PriceConfidenceUpdated 1 {
+        max_confidence_bps: u16,
+    }
+    PriceDecayUpdated 1 {
+        token_raffle: Pubkey,
+        mode: PriceDecayMode,
+        start_bps: u16,
+        end_bps: u16,
+        steps: u8,
+    }
+    PriceFeedRegistered 1 {
+        mint: Pubkey,
+        feed: Pubkey,
+    }
+    PriceFeedUnregistered 1 {
+        mint: Pubkey,
+    }
+    PriceSamplingUpdated 1 {
+        token_raffle: Pubkey,
+        mode: PriceSampleMode,
+        count: u8,
+    }
+    PricingModeUpdated 1 {
+        token_raffle: Pubkey,
+        mode: PricingMode,
+        fixed_ticket_price_usd: u64,
+    }
+    PrizeBoosted 1 {
+        token: Pubkey,
+        round_id: u32,
+        sponsor: Pubkey,
+        amount: u64,
+        sponsored_amount: u64,
+        prize_amount: u64,
+        timestamp: i64,
+    }
+    PrizeCapUpdated 1 {
+        cadence_id: u8,
+        prize_cap_lamports: Option<u64>,
+    }
+    PrizeClaimed 1 {
+        round_id: u32,
+        winner: Pubkey,
+        recipient: Pubkey,
+        prize_amount: u64,
+        commission_amount: u64,
+        beneficiary: Pubkey,
+        pushed: bool,
+        timestamp: i64,
+    }
+    PrizePoolStaked 1 {
+        round_id: u32,
+        amount: u64,
+        pool_tokens: u64,
+        staked_lamports: u64,
+        timestamp: i64,
+    }
+    PrizePoolUnstaked 1 {
+        round_id: u32,
+        principal: u64,
+        received: u64,
+        yield_amount: u64,
+        loss: u64,
+        to_insurance: bool,
+        prize_amount: u64,
+        total_principal: u64,
+        unswept_yield: u64,
+        timestamp: i64,
+    }
+    PrizeRolledIntoTickets 1 {
+        round_id: u32,
+        target_round_id: u32,
+        winner: Pubkey,
+        tickets_count: u32,
+        rolled_amount: u64,
+        paid_out_amount: u64,
+        timestamp: i64,
+    }
+    PrizeTargetReached 1 {
+        cadence_id: u8,
+        round_id: u32,
+        prize_amount: u64,
+        prize_target_lamports: u64,
+        timestamp: i64,
+    }
+    PrizeTargetUpdated 1 {
+        cadence_id: u8,
+        prize_target_lamports: Option<u64>,
+    }
+    ProgramMetadataUpdated 1 {
+        name: String,
+        version: String,
+        config_uri: String,
+        support_contact: String,
+    }
+    PromoTicketsGranted 1 {
+        token: Pubkey,
+        round_id: u32,
+        player: Pubkey,
+        operator: Pubkey,
+        purchase_index: u32,
+        count: u32,
+        promo_value: u64,
+        timestamp: i64,
+    }
+    PurchaseCancelled 1 {
+        token: Pubkey,
+        round_id: u32,
+        purchase_index: u32,
+        player: Pubkey,
+        tickets_count: u32,
+        refund: u64,
+        penalty: u64,
+        timestamp: i64,
+    }
+    PurchaseTransferred 1 {
+        token: Pubkey,
+        round_id: u32,
+        purchase_index: u32,
+        from: Pubkey,
+        to: Pubkey,
+        timestamp: i64,
+    }
+    RaffleMetadataUpdated 1 {
+        token_raffle: Pubkey,
+        name: String,
+        description: String,
+        image_uri: String,
+    }
+    RandomnessRateLimitUpdated 1 {
+        cooldown_seconds: i64,
+        max_requests: u8,
+    }
+    RandomnessRequestsReset 1 {
+        round_id: u32,
+    }
+    RecordBroken 1 {
+        kind: RecordKind,
+        holder: Pubkey,
+        token_raffle: Pubkey,
+        round_id: u32,
+        value: u64,
+        timestamp: i64,
+    }
+    RedrawConfigUpdated 1 {
+        cadence_id: u8,
+        redraw_after_seconds: i64,
+    }
+    RefundClaimed 1 {
+        token: Pubkey,
+        round_id: u32,
+        purchase_index: u32,
+        player: Pubkey,
+        refund: u64,
+        timestamp: i64,
+    }
+    RoundCancelled 1 {
+        token: Pubkey,
+        round_id: u32,
+        carried_over: u64,
+        jackpot_kept: u64,
+        commission_kept: u64,
+        timestamp: i64,
+    }
+    RoundCreated 1 {
+        token: Pubkey,
+        cadence_id: u8,
+        round_id: u32,
+        start_time: i64,
+        end_time: i64,
+        mega_round: bool,
+        carryover: u64,
+    }
+    RoundDurationChanged 1 {
+        cadence_id: u8,
+        old_duration: i64,
+        new_duration: i64,
+    }
+    RoundFrozen 1 {
+        round_id: u32,
+        by: Pubkey,
+        timestamp: i64,
+    }
+    RoundLocked 1 {
+        round_id: u32,
+        total_tickets: u32,
+        purchases_count: u32,
+        prize_amount: u64,
+        timestamp: i64,
+    }
+    RoundRedrawRequested 1 {
+        round_id: u32,
+        first_winner_purchase_index: Option<u32>,
+        first_winner_address: Option<Pubkey>,
+        timestamp: i64,
+    }
+    RoundSettled 1 {
+        round_id: u32,
+        winner: Pubkey,
+        keeper: Pubkey,
+        prize_amount: u64,
+        commission_amount: u64,
+        keeper_tip: u64,
+    }
+    RoundUnfrozen 1 {
+        round_id: u32,
+        timestamp: i64,
+    }
+    SalesPauseChanged 1 {
+        token_raffle: Pubkey,
+        paused: bool,
+        timestamp: i64,
+    }
+    SchemaMigrated 1 {
+        account: Pubkey,
+        from_version: u16,
+        to_version: u16,
+    }
+    SlippageExceeded 1 {
+        token: Pubkey,
+        round_id: u32,
+        buyer: Pubkey,
+        count: u32,
+        ticket_price: u64,
+        max_price_per_ticket: u64,
+        cost: u64,
+    }
+    SponsorMatchClosed 1 {
+        round_id: u32,
+        sponsor: Pubkey,
+        matched: u64,
+        refunded: u64,
+    }
+    SponsorMatchCreated 1 {
+        round_id: u32,
+        sponsor: Pubkey,
+        ratio_bps: u16,
+        cap: u64,
+    }
+    SponsorMatched 1 {
+        round_id: u32,
+        sponsor: Pubkey,
+        contribution: u64,
+        matched: u64,
+        total_matched: u64,
+    }
+    StakingModeUpdated 1 {
+        token_raffle: Pubkey,
+        enabled: bool,
+        yield_to_insurance: bool,
+    }
+    StakingYieldSwept 1 {
+        amount: u64,
+        total_yield_realized: u64,
+        total_yield_swept: u64,
+        insurance_balance: u64,
+        timestamp: i64,
+    }
+    StatusChanged 1 {
+        round_id: u32,
+        old_status: RoundStatus,
+        new_status: RoundStatus,
+        timestamp: i64,
+    }
+    StreakBonusAwarded 1 {
+        token: Pubkey,
+        round_id: u32,
+        player: Pubkey,
+        streak: u32,
+        bonus_tickets: u32,
+    }
+    StreakRewardsUpdated 1 {
+        token: Pubkey,
+        every: u32,
+        bonus_tickets: u32,
+    }
+    Ticked 1 {
+        keeper: Pubkey,
+        enqueued: Option<u32>,
+        randomness_requested: Option<u32>,
+        expired: Option<u32>,
+        settled: Option<u32>,
+        timestamp: i64,
+    }
+    TicketPegUpdated 1 {
+        components: Vec<PegComponent>,
+    }
+    TicketPriceUpdated 1 {
+        old_satoshis: u64,
+        new_satoshis: u64,
+    }
+    TicketPurchased 2 {
+        token: Pubkey,
+        round_id: u32,
+        buyer: Pubkey,
+        count: u32,
+        ticket_price: u64,
+        oracle_price: u64,
+        price_slot: u64,
+        total_amount: u64,
+        prize_amount: u64,
+        commission_amount: u64,
+        fee_bps: u16,
+        timestamp: i64,
+        purchase_index: u32,
+        ticket_start: u32,
+        ticket_end: u32,
+        effective_ticket_price: u64,
+        total_tickets: u32,
+    }
+    TreasurerChanged 1 {
+        old_treasurer: Pubkey,
+        new_treasurer: Pubkey,
+    }
+    TreasuryDeposited 1 {
+        treasurer: Pubkey,
+        amount: u64,
+        pool_tokens: u64,
+        principal: u64,
+        commission_balance: u64,
+        timestamp: i64,
+    }
+    TreasuryWithdrawn 1 {
+        treasurer: Pubkey,
+        pool_tokens: u64,
+        principal: u64,
+        received: u64,
+        yield_amount: u64,
+        loss: u64,
+        commission_balance: u64,
+        timestamp: i64,
+    }
+    TukTukTaskQueued 1 {
+        token_raffle: Pubkey,
+        task: Pubkey,
+        task_id: u16,
+        trigger_time: i64,
+        crank_reward: u64,
+    }
+    VaultInsolvent 2 {
+        vault_balance: u64,
+        outstanding: u64,
+        shortfall: u64,
+        timestamp: i64,
+    }
+    VestedPrizeClaimed 1 {
+        round_id: u32,
+        winner: Pubkey,
+        amount: u64,
+        claimed_amount: u64,
+        total_amount: u64,
+    }
+    VestingConfigUpdated 1 {
+        threshold: u64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+    }
+    VestingStarted 1 {
+        round_id: u32,
+        winner: Pubkey,
+        total_amount: u64,
+        cliff_time: i64,
+        end_time: i64,
+    }
+    VolumeBonusAwarded 1 {
+        token: Pubkey,
+        round_id: u32,
+        buyer: Pubkey,
+        purchased: u32,
+        bonus_tickets: u32,
+    }
+    VolumeBonusUpdated 1 {
+        token_raffle: Pubkey,
+        per_tickets: u32,
+        bonus_tickets: u32,
+    }
+    WatchtowerChanged 1 {
+        old_watchtower: Pubkey,
+        new_watchtower: Pubkey,
+    }
+    WinnerPicked 1 {
+        token: Pubkey,
+        round: Pubkey,
+        round_id: u32,
+        winner_purchase_index: u32,
+        winner_ticket_index: u32,
+        prize_amount: u64,
+        carryover: u64,
+        timestamp: i64,
+    }
+    YieldSweepIntervalUpdated 1 {
+        interval_seconds: i64,
+    }
+}
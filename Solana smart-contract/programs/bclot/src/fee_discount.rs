@@ -0,0 +1,361 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::{
+    RaffleError,
+    RaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+const MAX_BPS: u16 = 10_000;
+
+// Only authority. Wallets holding at least `min_stake` governance tokens in their
+// StakerPosition pay `discount_bps` less entrance fee
+pub fn initialize_fee_discount(
+    ctx: Context<InitializeFeeDiscount>,
+    min_stake: u64,
+    discount_bps: u16,
+) -> Result<()> {
+    require!(discount_bps <= MAX_BPS, RaffleError::InvalidBps);
+
+    let fee_discount_config = &mut ctx.accounts.fee_discount_config;
+    fee_discount_config.governance_mint = ctx.accounts.governance_mint.key();
+    fee_discount_config.min_stake = min_stake;
+    fee_discount_config.discount_bps = discount_bps;
+    fee_discount_config.total_staked = 0;
+    fee_discount_config.bump = ctx.bumps.fee_discount_config;
+
+    msg!("✅ Fee discount initialized for mint {}", fee_discount_config.governance_mint);
+
+    Ok(())
+}
+
+// Only authority. Zero discount_bps switches discounts off, positions stay withdrawable
+pub fn set_fee_discount(ctx: Context<SetFeeDiscount>, min_stake: u64, discount_bps: u16) -> Result<()> {
+    require!(discount_bps <= MAX_BPS, RaffleError::InvalidBps);
+
+    let fee_discount_config = &mut ctx.accounts.fee_discount_config;
+    fee_discount_config.min_stake = min_stake;
+    fee_discount_config.discount_bps = discount_bps;
+
+    emit!(FeeDiscountUpdated { min_stake, discount_bps });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::FeeDiscountChanged,
+        discount_bps as u64,
+    )?;
+
+    Ok(())
+}
+
+pub fn stake_governance_tokens(ctx: Context<StakeGovernanceTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    token::transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.staker_token_account.to_account_info(),
+                to: ctx.accounts.governance_vault.to_account_info(),
+                authority: ctx.accounts.staker.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let staker_position = &mut ctx.accounts.staker_position;
+    staker_position.owner = ctx.accounts.staker.key();
+    staker_position.amount = staker_position
+        .amount
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    staker_position.last_stake_time = Clock::get()?.unix_timestamp;
+    staker_position.bump = ctx.bumps.staker_position;
+
+    let fee_discount_config = &mut ctx.accounts.fee_discount_config;
+    fee_discount_config.total_staked = fee_discount_config
+        .total_staked
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    emit!(GovernanceTokensStaked {
+        staker: staker_position.owner,
+        amount,
+        position_amount: staker_position.amount,
+    });
+
+    Ok(())
+}
+
+pub fn unstake_governance_tokens(ctx: Context<UnstakeGovernanceTokens>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    let staker_position = &mut ctx.accounts.staker_position;
+    require!(staker_position.amount >= amount, RaffleError::InsufficientFunds);
+
+    let config_seeds: &[&[&[u8]]] = &[&[b"fee_discount_config", &[ctx.accounts.fee_discount_config.bump]]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.governance_vault.to_account_info(),
+                to: ctx.accounts.staker_token_account.to_account_info(),
+                authority: ctx.accounts.fee_discount_config.to_account_info(),
+            },
+            config_seeds,
+        ),
+        amount,
+    )?;
+
+    staker_position.amount -= amount;
+
+    let fee_discount_config = &mut ctx.accounts.fee_discount_config;
+    fee_discount_config.total_staked = fee_discount_config.total_staked.saturating_sub(amount);
+
+    emit!(GovernanceTokensUnstaked {
+        staker: staker_position.owner,
+        amount,
+        position_amount: staker_position.amount,
+    });
+
+    Ok(())
+}
+
+pub struct FeeDiscountAccounts<'info> {
+    pub fee_discount_config: Option<Account<'info, FeeDiscountConfig>>,
+    pub staker_position: Option<Account<'info, StakerPosition>>,
+}
+
+// The discount pair of a purchase goes ahead of the price feeds in remaining_accounts:
+// FeeDiscountConfig, then the buyer's StakerPosition. Feeds are never owned by this
+// program, so a leading account it owns starts the pair. Returns the pair, if passed,
+// and the accounts left for the feeds
+pub fn split_fee_discount_accounts<'info>(
+    remaining_accounts: &'info [AccountInfo<'info>],
+    player: &Pubkey,
+) -> Result<(FeeDiscountAccounts<'info>, &'info [AccountInfo<'info>])> {
+    match remaining_accounts {
+        [config, position, rest @ ..] if *config.owner == crate::id() => {
+            let fee_discount_config = Account::<FeeDiscountConfig>::try_from(config)?;
+            let staker_position = Account::<StakerPosition>::try_from(position)?;
+            require_keys_eq!(staker_position.owner, *player, RaffleError::Unauthorized);
+            let fee_discount = FeeDiscountAccounts {
+                fee_discount_config: Some(fee_discount_config),
+                staker_position: Some(staker_position),
+            };
+            Ok((fee_discount, rest))
+        }
+        _ => {
+            let fee_discount = FeeDiscountAccounts {
+                fee_discount_config: None,
+                staker_position: None,
+            };
+            Ok((fee_discount, remaining_accounts))
+        }
+    }
+}
+
+// Stake added after the round opened doesn't count, so a position can't be topped up
+// just for one purchase and withdrawn right after
+pub fn discounted_fee_bps(
+    fee_bps: u16,
+    fee_discount_config: Option<&Account<FeeDiscountConfig>>,
+    staker_position: Option<&Account<StakerPosition>>,
+    round_start_time: i64,
+) -> u16 {
+    let (Some(config), Some(position)) = (fee_discount_config, staker_position) else {
+        return fee_bps;
+    };
+
+    if config.discount_bps == 0
+        || position.amount < config.min_stake
+        || position.last_stake_time >= round_start_time
+    {
+        return fee_bps;
+    }
+
+    let discount = (fee_bps as u32 * config.discount_bps as u32 / MAX_BPS as u32) as u16;
+    fee_bps - discount
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeFeeDiscount<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeeDiscountConfig::INIT_SPACE,
+        seeds = [b"fee_discount_config"],
+        bump
+    )]
+    pub fee_discount_config: Account<'info, FeeDiscountConfig>,
+
+    pub governance_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"governance_vault"],
+        bump,
+        token::mint = governance_mint,
+        token::authority = fee_discount_config
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetFeeDiscount<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"fee_discount_config"],
+        bump = fee_discount_config.bump
+    )]
+    pub fee_discount_config: Account<'info, FeeDiscountConfig>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+pub struct StakeGovernanceTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_discount_config"],
+        bump = fee_discount_config.bump
+    )]
+    pub fee_discount_config: Account<'info, FeeDiscountConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = staker,
+        space = 8 + StakerPosition::INIT_SPACE,
+        seeds = [b"staker_position", staker.key().as_ref()],
+        bump
+    )]
+    pub staker_position: Account<'info, StakerPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_vault"],
+        bump
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = fee_discount_config.governance_mint,
+        token::authority = staker
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeGovernanceTokens<'info> {
+    #[account(
+        mut,
+        seeds = [b"fee_discount_config"],
+        bump = fee_discount_config.bump
+    )]
+    pub fee_discount_config: Account<'info, FeeDiscountConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"staker_position", staker.key().as_ref()],
+        bump = staker_position.bump
+    )]
+    pub staker_position: Account<'info, StakerPosition>,
+
+    #[account(
+        mut,
+        seeds = [b"governance_vault"],
+        bump
+    )]
+    pub governance_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = fee_discount_config.governance_mint
+    )]
+    pub staker_token_account: Account<'info, TokenAccount>,
+
+    pub staker: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct FeeDiscountConfig {
+    pub governance_mint: Pubkey,
+    pub min_stake: u64, // governance token base units
+    pub discount_bps: u16, // of the entrance fee, 0 - disabled
+    pub total_staked: u64,
+    pub bump: u8,
+}
+
+// Governance tokens a wallet locked in governance_vault
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct StakerPosition {
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub last_stake_time: i64, // discounts apply in rounds opened after it
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct FeeDiscountUpdated {
+    pub min_stake: u64,
+    pub discount_bps: u16,
+}
+
+#[event]
+pub struct GovernanceTokensStaked {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub position_amount: u64,
+}
+
+#[event]
+pub struct GovernanceTokensUnstaked {
+    pub staker: Pubkey,
+    pub amount: u64,
+    pub position_amount: u64,
+}
@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+    UpdateRaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+// Config mutations below touch RaffleState only and need no payer, so the authority
+// can be an SPL Governance PDA executing them from a passed proposal.
+
+// Only authority
+pub fn set_entrance_fee(ctx: Context<UpdateRaffleState>, entrance_fee_percentage: u8) -> Result<()> {
+    require!(entrance_fee_percentage <= 100, RaffleError::InvalidFeePercentage);
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    let old_percentage = raffle_state.entrance_fee_percentage;
+    raffle_state.entrance_fee_percentage = entrance_fee_percentage;
+
+    emit!(EntranceFeeChanged {
+        old_percentage,
+        new_percentage: entrance_fee_percentage,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::EntranceFeeChanged,
+        entrance_fee_percentage as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn set_beneficiary(ctx: Context<UpdateRaffleState>, beneficiary: Pubkey) -> Result<()> {
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    let old_beneficiary = raffle_state.beneficiary;
+    raffle_state.beneficiary = beneficiary;
+
+    emit!(BeneficiaryChanged {
+        old_beneficiary,
+        new_beneficiary: beneficiary,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::BeneficiaryChanged,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. First step of the handover, the new authority has to accept
+pub fn propose_authority(ctx: Context<UpdateRaffleState>, new_authority: Pubkey) -> Result<()> {
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    raffle_state.pending_authority = new_authority;
+
+    emit!(AuthorityTransferProposed {
+        authority: raffle_state.authority,
+        pending_authority: new_authority,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::AuthorityProposed,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only pending authority
+pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    let old_authority = raffle_state.authority;
+    raffle_state.authority = raffle_state.pending_authority;
+    raffle_state.pending_authority = Pubkey::default();
+
+    msg!("✅ Authority transferred to {}", raffle_state.authority);
+
+    emit!(AuthorityTransferred {
+        old_authority,
+        new_authority: raffle_state.authority,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.pending_authority.key(),
+        AuditAction::AuthorityAccepted,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        constraint = raffle_state.pending_authority != Pubkey::default()
+            && raffle_state.pending_authority == pending_authority.key() @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    pub pending_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Events
+#[event]
+pub struct EntranceFeeChanged {
+    pub old_percentage: u8,
+    pub new_percentage: u8,
+}
+
+#[event]
+pub struct BeneficiaryChanged {
+    pub old_beneficiary: Pubkey,
+    pub new_beneficiary: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
@@ -0,0 +1,131 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RaffleError,
+    TokenRaffle,
+    UpdateTokenRaffle,
+};
+use crate::audit::{record_audit, AuditAction};
+use crate::jackpot::MegaJackpot;
+use crate::prize_limits::pass_on_carryover;
+use crate::solvency::require_solvent;
+
+// Only authority. Rounds that sold fewer than `threshold` tickets get a house purchase of
+// `house_tickets` owned by the beneficiary when they move to Drawing. 0 - disabled
+pub fn set_house_ticket(ctx: Context<UpdateTokenRaffle>, threshold: u32, house_tickets: u32) -> Result<()> {
+    require!(
+        (threshold == 0) == (house_tickets == 0),
+        RaffleError::InvalidHouseTicketConfig
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.house_ticket_threshold = threshold;
+    sol_raffle.house_tickets = house_tickets;
+
+    emit!(HouseTicketConfigUpdated {
+        cadence_id: sol_raffle.cadence_id,
+        threshold,
+        house_tickets,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::HouseTicketChanged,
+        threshold as u64,
+    )?;
+
+    Ok(())
+}
+
+// The house purchase has no RoundTicketsPurchase or RoundTickets entry, its tickets follow
+// the last sold one and its purchase index is purchases_count, so set_winner_address can't
+// resolve it
+pub fn add_house_ticket(sol_raffle: &TokenRaffle, round: &mut Round, beneficiary: Pubkey) {
+    if sol_raffle.house_ticket_threshold == 0 || round.total_tickets >= sol_raffle.house_ticket_threshold {
+        return;
+    }
+
+    round.house_tickets = sol_raffle.house_tickets;
+    round.house_owner = beneficiary;
+
+    msg!("🏠 Round {} sold {} tickets, house holds {}", round.round_id, round.total_tickets, round.house_tickets);
+}
+
+pub fn is_house_ticket(round: &Round, winner_ticket_index: u32) -> bool {
+    winner_ticket_index >= round.total_tickets
+}
+
+// Run from the VRF callback when the house purchase wins. Nobody is paid: the pot goes to
+// the mega jackpot, or to the next round if there is none. The beneficiary is recorded as
+// winner of a zero prize so claims and settlement still release the commission.
+// Returns the lamports moved out of sol_vault
+pub fn roll_house_win<'info>(
+    sol_raffle: &mut TokenRaffle,
+    round: &mut Round,
+    mega_jackpot_info: Option<&AccountInfo<'info>>,
+    vault: Option<&AccountInfo<'info>>,
+    vault_ledger: Option<&AccountInfo<'info>>,
+) -> Result<u64> {
+    let amount = round.prize_amount;
+    round.winner_address = Some(round.house_owner);
+    round.prize_amount = 0;
+    round.house_rolled = amount;
+
+    let jackpot = mega_jackpot_info
+        .filter(|info| !info.data_is_empty() && info.owner == &crate::id())
+        .zip(vault);
+
+    let moved = if let Some((mega_jackpot_info, vault)) = jackpot {
+        let mut mega_jackpot =
+            MegaJackpot::try_deserialize(&mut &mega_jackpot_info.try_borrow_data()?[..])?;
+
+        if let Some(vault_ledger) = vault_ledger {
+            require_solvent(vault_ledger, vault, round.round_id)?;
+        }
+        let vault_lamports = vault
+            .lamports()
+            .checked_sub(amount)
+            .ok_or(RaffleError::InsufficientVaultBalance)?;
+        **vault.try_borrow_mut_lamports()? = vault_lamports;
+        **mega_jackpot_info.try_borrow_mut_lamports()? += amount;
+
+        mega_jackpot.balance = mega_jackpot
+            .balance
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        mega_jackpot.try_serialize(&mut &mut mega_jackpot_info.try_borrow_mut_data()?[..])?;
+
+        amount
+    } else {
+        pass_on_carryover(sol_raffle, amount)?;
+        0
+    };
+
+    msg!("🏠 House won round {}, {} lamports rolled over", round.round_id, amount);
+
+    emit!(HouseWon {
+        round_id: round.round_id,
+        amount,
+        to_jackpot: moved > 0,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(moved)
+}
+
+// Events
+#[event]
+pub struct HouseTicketConfigUpdated {
+    pub cadence_id: u8,
+    pub threshold: u32,
+    pub house_tickets: u32,
+}
+
+#[event]
+pub struct HouseWon {
+    pub round_id: u32,
+    pub amount: u64,
+    pub to_jackpot: bool, // false - carried over to the next round
+    pub timestamp: i64,
+}
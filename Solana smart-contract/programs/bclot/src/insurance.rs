@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+    UpdateRaffleState,
+    transfer,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+const MAX_INSURANCE_BPS: u16 = 5_000;
+const MAX_BPS: u16 = 10_000;
+
+// `insurance_bps` of a commission payout. insurance_vault is a system account, a skim that
+// would leave it short of rent exemption fails the transfer, so it stays with the beneficiary
+// until the vault holds enough
+pub fn insurance_share(raffle_state: &RaffleState, insurance_vault: &AccountInfo, amount: u64) -> Result<u64> {
+    let share = ((amount as u128)
+        .checked_mul(raffle_state.insurance_bps as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / MAX_BPS as u128) as u64;
+
+    if insurance_vault.lamports().saturating_add(share) < Rent::get()?.minimum_balance(0) {
+        return Ok(0);
+    }
+    Ok(share)
+}
+
+// Only authority. Share of every round's commission routed into insurance_vault
+pub fn set_insurance_bps(ctx: Context<UpdateRaffleState>, insurance_bps: u16) -> Result<()> {
+    require!(insurance_bps <= MAX_INSURANCE_BPS, RaffleError::InvalidBps);
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    raffle_state.insurance_bps = insurance_bps;
+
+    emit!(InsuranceBpsUpdated { insurance_bps });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::InsuranceBpsChanged,
+        insurance_bps as u64,
+    )?;
+
+    Ok(())
+}
+
+pub fn fund_insurance_vault(ctx: Context<FundInsuranceVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.funder.to_account_info(),
+        ctx.accounts.insurance_vault.to_account_info(),
+        amount,
+        None,
+    )?;
+
+    emit!(InsuranceFunded {
+        funder: ctx.accounts.funder.key(),
+        amount,
+        balance: ctx.accounts.insurance_vault.lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Only authority. Covers shortfalls (e.g. sol_vault discrepancies) or goodwill refunds
+pub fn insurance_payout(
+    ctx: Context<InsurancePayout>,
+    amount: u64,
+    reason: u8,
+) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+    // The vault stays rent exempt
+    require!(
+        ctx.accounts.insurance_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)) >= amount,
+        RaffleError::InsufficientFunds
+    );
+
+    let insurance_vault_seeds: &[&[&[u8]]] =
+        &[&[b"insurance_vault", &[ctx.bumps.insurance_vault]]];
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.insurance_vault.to_account_info(),
+        ctx.accounts.recipient.to_account_info(),
+        amount,
+        Some(insurance_vault_seeds),
+    )?;
+
+    msg!("Insurance paid {} lamports to {} (reason {})", amount, ctx.accounts.recipient.key(), reason);
+
+    emit!(InsurancePaidOut {
+        authority: ctx.accounts.authority.key(),
+        recipient: ctx.accounts.recipient.key(),
+        amount,
+        reason,
+        balance: ctx.accounts.insurance_vault.lamports(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::InsurancePaidOut,
+        amount,
+    )?;
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct FundInsuranceVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// PDA for storing the insurance fund
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InsurancePayout<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    /// CHECK: any account receiving the payout, e.g. sol_vault or a refunded player
+    #[account(mut)]
+    pub recipient: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct InsuranceBpsUpdated {
+    pub insurance_bps: u16,
+}
+
+#[event]
+pub struct InsuranceFunded {
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceSkimmed {
+    pub round_id: u32,
+    pub commission_amount: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct InsurancePaidOut {
+    pub authority: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub reason: u8,
+    pub balance: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RaffleError,
+    RaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+pub const MAX_JACKPOT_BPS: u16 = 1_000;
+
+pub fn initialize_mega_jackpot(ctx: Context<InitializeMegaJackpot>) -> Result<()> {
+    let mega_jackpot = &mut ctx.accounts.mega_jackpot;
+    mega_jackpot.bump = ctx.bumps.mega_jackpot;
+
+    msg!("✅ Mega jackpot initialized");
+    msg!("📍 Mega jackpot PDA: {}", mega_jackpot.key());
+
+    Ok(())
+}
+
+// Only authority. `odds` is 1 in N per drawn round, 0 disables the secondary draw
+pub fn set_jackpot_config(
+    ctx: Context<SetJackpotConfig>,
+    jackpot_bps: u16,
+    odds: u32,
+) -> Result<()> {
+    require!(jackpot_bps <= MAX_JACKPOT_BPS, RaffleError::InvalidBps);
+
+    ctx.accounts.raffle_state.jackpot_bps = jackpot_bps;
+    ctx.accounts.mega_jackpot.odds = odds;
+
+    emit!(JackpotConfigUpdated { jackpot_bps, odds });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::JackpotConfigChanged,
+        jackpot_bps as u64,
+    )?;
+
+    Ok(())
+}
+
+// Part of a purchase cost fed into the mega jackpot
+pub fn jackpot_contribution(jackpot_bps: u16, cost: u64) -> Result<u64> {
+    let contribution = (cost as u128)
+        .checked_mul(jackpot_bps as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / 10_000;
+
+    Ok(contribution as u64)
+}
+
+pub fn contribute_to_jackpot<'info>(
+    mega_jackpot: &mut Account<'info, MegaJackpot>,
+    player: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            system_program.clone(),
+            anchor_lang::system_program::Transfer {
+                from: player.clone(),
+                to: mega_jackpot.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    mega_jackpot.add_contribution(amount)
+}
+
+// Same for purchases paid out of sol_vault, like a prize rolled into tickets
+pub fn contribute_to_jackpot_from_vault<'info>(
+    mega_jackpot: &mut Account<'info, MegaJackpot>,
+    vault: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    **vault.try_borrow_mut_lamports()? -= amount;
+    **mega_jackpot.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    mega_jackpot.add_contribution(amount)
+}
+
+// Secondary draw run from the VRF callback. On a hit the whole jackpot moves into
+// sol_vault on top of the round's prize and is paid by the regular claim. Returns the amount won
+pub fn try_award_jackpot<'info>(
+    mega_jackpot_info: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    round: &mut Round,
+    random_number: u64,
+) -> Result<u64> {
+    if mega_jackpot_info.data_is_empty() || mega_jackpot_info.owner != &crate::id() {
+        return Ok(0);
+    }
+
+    let mut mega_jackpot =
+        MegaJackpot::try_deserialize(&mut &mega_jackpot_info.try_borrow_data()?[..])?;
+    if mega_jackpot.odds == 0 {
+        return Ok(0);
+    }
+
+    if !random_number.is_multiple_of(mega_jackpot.odds as u64) {
+        msg!("Mega jackpot not hit for round {}", round.round_id);
+        return Ok(0);
+    }
+
+    let amount = mega_jackpot.balance;
+    if amount == 0 {
+        return Ok(0);
+    }
+
+    **mega_jackpot_info.try_borrow_mut_lamports()? -= amount;
+    **vault.try_borrow_mut_lamports()? += amount;
+
+    mega_jackpot.balance = 0;
+    mega_jackpot.total_paid = mega_jackpot
+        .total_paid
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    mega_jackpot.last_won_round = Some(round.round_id);
+    mega_jackpot.try_serialize(&mut &mut mega_jackpot_info.try_borrow_mut_data()?[..])?;
+
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.jackpot_amount = amount;
+
+    msg!("🎰 Mega jackpot of {} lamports won in round {}", amount, round.round_id);
+
+    emit!(MegaJackpotWon {
+        round_id: round.round_id,
+        amount,
+        prize_amount: round.prize_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(amount)
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeMegaJackpot<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + MegaJackpot::INIT_SPACE,
+        seeds = [b"mega_jackpot"],
+        bump
+    )]
+    pub mega_jackpot: Account<'info, MegaJackpot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetJackpotConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"mega_jackpot"],
+        bump = mega_jackpot.bump
+    )]
+    pub mega_jackpot: Account<'info, MegaJackpot>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct MegaJackpot {
+    pub balance: u64, // lamports held on top of rent
+    pub total_contributed: u64,
+    pub total_paid: u64,
+    pub last_won_round: Option<u32>,
+    pub odds: u32, // 1 in N drawn rounds wins the jackpot, 0 - disabled
+    pub bump: u8,
+}
+
+impl MegaJackpot {
+    pub fn add_contribution(&mut self, amount: u64) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_contributed = self
+            .total_contributed
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Events
+#[event]
+pub struct JackpotConfigUpdated {
+    pub jackpot_bps: u16,
+    pub odds: u32,
+}
+
+#[event]
+pub struct MegaJackpotWon {
+    pub round_id: u32,
+    pub amount: u64,
+    pub prize_amount: u64,
+    pub timestamp: i64,
+}
@@ -0,0 +1,1104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::memo::Memo;
+use orao_solana_vrf_cb::{
+    program::OraoVrfCb,
+    state::{client::Client, network_state::NetworkState},
+    CB_CLIENT_ACCOUNT_SEED, CB_CONFIG_ACCOUNT_SEED,
+};
+use crate::{
+    ClientState,
+    PendingQueue,
+    Round,
+    RoundStatus,
+    RoundTickets,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateRaffleState,
+    PrizeClaimed,
+    RoundAccounts,
+    RoundBumps,
+    RequestFundingAccounts,
+    SettlementAccounts,
+    VrfRequestAccounts,
+    CLIENT_STATE_SEED,
+    attach_memo,
+    create_round_accounts,
+    prepare_round_request,
+    submit_vrf_request,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::blocklist::{is_blocked, require_not_blocked, Blocklist};
+use crate::cluster_profile::ClusterProfile;
+use crate::commission::settle_sol_commission;
+use crate::daily_stats::{utc_day, DailyStats};
+use crate::jackpot::MegaJackpot;
+use crate::lifecycle::lock_round_totals;
+use crate::migration::Versioned;
+use crate::queue::enqueue_expired_round;
+use crate::raffle_stats::{record_payout, PayoutStats, RaffleStats};
+use crate::solvency::{release_in_ledger_account, require_solvent};
+use crate::vrf::is_randomness_request_due;
+
+pub const MAX_KEEPERS: usize = 16;
+
+// Only authority. Lamports paid from round commission to whoever runs a settlement crank
+pub fn set_keeper_tip(ctx: Context<UpdateRaffleState>, keeper_tip: u64) -> Result<()> {
+    ctx.accounts.raffle_state.keeper_tip = keeper_tip;
+
+    emit!(KeeperTipUpdated { keeper_tip });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::KeeperTipChanged,
+        keeper_tip,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn initialize_keeper_registry(ctx: Context<InitializeKeeperRegistry>) -> Result<()> {
+    let keeper_registry = &mut ctx.accounts.keeper_registry;
+    keeper_registry.keepers = Vec::new();
+    keeper_registry.bump = ctx.bumps.keeper_registry;
+
+    msg!("✅ Keeper registry initialized");
+
+    Ok(())
+}
+
+// Only authority. Switches crank instructions between permissionless and allowlisted
+pub fn set_keeper_allowlist_enabled(ctx: Context<UpdateRaffleState>, enabled: bool) -> Result<()> {
+    ctx.accounts.raffle_state.keeper_allowlist_enabled = enabled;
+
+    emit!(KeeperAllowlistToggled { enabled });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::KeeperAllowlistToggled,
+        enabled as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn add_keeper(ctx: Context<UpdateKeeperRegistry>, keeper: Pubkey) -> Result<()> {
+    let keeper_registry = &mut ctx.accounts.keeper_registry;
+    require!(
+        !keeper_registry.keepers.contains(&keeper),
+        RaffleError::KeeperAlreadyRegistered
+    );
+    require!(
+        keeper_registry.keepers.len() < MAX_KEEPERS,
+        RaffleError::KeeperRegistryFull
+    );
+
+    keeper_registry.keepers.push(keeper);
+
+    emit!(KeeperAdded { keeper });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::KeeperAdded,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn remove_keeper(ctx: Context<UpdateKeeperRegistry>, keeper: Pubkey) -> Result<()> {
+    let keeper_registry = &mut ctx.accounts.keeper_registry;
+    let len_before = keeper_registry.keepers.len();
+    keeper_registry.keepers.retain(|k| *k != keeper);
+    require!(
+        keeper_registry.keepers.len() < len_before,
+        RaffleError::KeeperNotRegistered
+    );
+
+    emit!(KeeperRemoved { keeper });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::KeeperRemoved,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// With the allowlist enabled the registry must be passed and contain the caller
+pub fn require_keeper(
+    raffle_state: &RaffleState,
+    keeper_registry: Option<&Account<KeeperRegistry>>,
+    keeper: &Pubkey,
+) -> Result<()> {
+    if !raffle_state.keeper_allowlist_enabled {
+        return Ok(());
+    }
+
+    let keeper_registry = keeper_registry.ok_or(RaffleError::KeeperNotRegistered)?;
+    require!(
+        keeper_registry.keepers.contains(keeper),
+        RaffleError::KeeperNotRegistered
+    );
+
+    Ok(())
+}
+
+// Takes the tip out of the round's commission, never more than what is left
+pub fn pay_keeper_tip<'info>(
+    raffle_state: &RaffleState,
+    round: &mut Round,
+    vault: &AccountInfo<'info>,
+    vault_ledger: &AccountInfo<'info>,
+    keeper: &AccountInfo<'info>,
+) -> Result<u64> {
+    let tip = raffle_state.keeper_tip.min(round.commission_balance);
+    if tip == 0 {
+        return Ok(0);
+    }
+
+    **vault.try_borrow_mut_lamports()? -= tip;
+    **keeper.try_borrow_mut_lamports()? += tip;
+
+    round.commission_balance -= tip;
+    release_in_ledger_account(vault_ledger, tip)?;
+    round.keeper_tips_paid = round
+        .keeper_tips_paid
+        .checked_add(tip)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    emit!(KeeperTipPaid {
+        round_id: round.round_id,
+        keeper: keeper.key(),
+        amount: tip,
+    });
+
+    Ok(tip)
+}
+
+// Permissionless. Closes sales of an ended round and fixes its totals before randomness is
+// requested, request_randomness and tick lock a round still open themselves
+pub fn lock_round(ctx: Context<LockRound>, round_id: u32) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+
+    let round = &mut ctx.accounts.round;
+    round.check_version()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require!(now >= round.end_time, RaffleError::RoundNotEndedYet);
+
+    lock_round_totals(&mut ctx.accounts.sol_raffle, round, now)?;
+
+    msg!("🔒 Round {} locked with {} tickets", round_id, round.locked_total_tickets);
+
+    Ok(())
+}
+
+// Permissionless push payout of a completed round: prize to the winner, commission to beneficiary
+pub fn settle_round(ctx: Context<SettleRound>, _round_id: u32, memo: Option<String>) -> Result<()> {
+    let raffle_state = &ctx.accounts.raffle_state;
+    require_keeper(
+        raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+    require_not_blocked(raffle_state, ctx.accounts.blocklist.as_ref(), &ctx.accounts.winner.key())?;
+
+    let round = &mut ctx.accounts.round;
+
+    round.check_version()?;
+    require!(
+        round.status == RoundStatus::Completed,
+        RaffleError::RoundNotCompleted
+    );
+    require!(!round.prize_claimed, RaffleError::PrizeAlreadyClaimed);
+    require!(!round.frozen, RaffleError::RoundFrozen);
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+    require!(
+        !raffle_state.is_vested_prize(round.prize_amount),
+        RaffleError::PrizeSubjectToVesting
+    );
+
+    settle_completed_round(
+        raffle_state,
+        round,
+        &ctx.accounts.winner,
+        &ctx.accounts.keeper.to_account_info(),
+        SettlementAccounts {
+            vault: &ctx.accounts.vault,
+            vault_ledger: &ctx.accounts.vault_ledger,
+            beneficiary: &ctx.accounts.beneficiary,
+            insurance_vault: &ctx.accounts.insurance_vault,
+        },
+    )?;
+    record_payout(
+        PayoutStats {
+            raffle_stats: &mut ctx.accounts.raffle_stats,
+            raffle_stats_bump: ctx.bumps.raffle_stats,
+            daily_stats: &mut ctx.accounts.daily_stats,
+            daily_stats_bump: ctx.bumps.daily_stats,
+            winner_history: &ctx.accounts.winner_history,
+        },
+        &ctx.accounts.round,
+        ctx.accounts.round.prize_amount,
+        true,
+    )?;
+
+    attach_memo(ctx.accounts.memo_program.as_ref(), memo)
+}
+
+// Settled by a crank unless frozen or vested, vested prizes go through start_vesting
+pub fn is_settleable(raffle_state: &RaffleState, round: &Round) -> bool {
+    round.status == RoundStatus::Completed
+        && round.winner_address.is_some()
+        && !round.prize_claimed
+        && !round.frozen
+        && round.staked_lamports == 0
+        && !raffle_state.is_vested_prize(round.prize_amount)
+}
+
+// Tip, prize and commission payouts shared by settle_round and tick
+pub fn settle_completed_round<'info>(
+    raffle_state: &RaffleState,
+    round: &mut Round,
+    winner: &AccountInfo<'info>,
+    keeper: &AccountInfo<'info>,
+    accounts: SettlementAccounts<'_, 'info>,
+) -> Result<()> {
+    let SettlementAccounts {
+        vault,
+        vault_ledger,
+        beneficiary,
+        insurance_vault,
+    } = accounts;
+
+    require!(
+        round.winner_address == Some(winner.key()),
+        RaffleError::NotTheWinner
+    );
+    require_solvent(vault_ledger, vault, round.round_id)?;
+
+    let tip = pay_keeper_tip(raffle_state, round, vault, vault_ledger, keeper)?;
+
+    let round_id = round.round_id;
+    let prize_amount = round.prize_amount;
+    let commission_amount = round.commission_balance;
+    let total_required = prize_amount
+        .checked_add(commission_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(
+        vault.lamports() >= total_required,
+        RaffleError::InsufficientVaultBalance
+    );
+
+    **vault.try_borrow_mut_lamports()? -= prize_amount;
+    **winner.try_borrow_mut_lamports()? += prize_amount;
+    release_in_ledger_account(vault_ledger, prize_amount)?;
+
+    settle_sol_commission(
+        raffle_state,
+        vault,
+        vault_ledger,
+        beneficiary,
+        insurance_vault,
+        round_id,
+        commission_amount,
+    )?;
+
+    round.prize_claimed = true;
+    round.commission_settled = true;
+
+    msg!("✅ Round {} settled, {} lamports sent to winner", round_id, prize_amount);
+
+    emit!(PrizeClaimed {
+        round_id,
+        winner: winner.key(),
+        recipient: winner.key(),
+        prize_amount,
+        commission_amount,
+        beneficiary: beneficiary.key(),
+        pushed: true,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    emit!(RoundSettled {
+        round_id,
+        winner: winner.key(),
+        keeper: keeper.key(),
+        prize_amount,
+        commission_amount,
+        keeper_tip: tip,
+    });
+
+    Ok(())
+}
+
+// Permissionless. Queues the expired round for the draw, creates the next round's accounts
+// from rent_vault and updates TokenRaffle in one transaction
+pub fn rollover(ctx: Context<Rollover>, round_id_prev: u32, round_id_next: u32) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+        RaffleError::MaintenanceMode
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.check_version()?;
+
+    // No previous round before the first rollover
+    require!(
+        sol_raffle.current_round_id.is_none_or(|id| id == round_id_prev),
+        RaffleError::RoundNotAvailable
+    );
+    if let Some(end_time) = sol_raffle.current_round_end_time {
+        require!(current_time >= end_time, RaffleError::RoundNotEndedYet);
+    }
+    require!(
+        ctx.accounts.next_round.data_len() == 0,
+        RaffleError::RoundAlreadyExists
+    );
+
+    create_round_accounts(
+        ctx.program_id,
+        sol_raffle,
+        &mut *ctx.accounts.pending_queue.load_mut()?,
+        RoundAccounts {
+            round: &ctx.accounts.next_round,
+            round_tickets: &ctx.accounts.next_round_tickets,
+            rent_vault: &ctx.accounts.rent_vault,
+            system_program: &ctx.accounts.system_program,
+        },
+        round_id_next,
+        RoundBumps {
+            round: ctx.bumps.next_round,
+            round_tickets: ctx.bumps.next_round_tickets,
+            rent_vault: ctx.bumps.rent_vault,
+        },
+        current_time,
+    )?;
+
+    msg!("✅ Rolled over round {} -> {}", round_id_prev, round_id_next);
+
+    Ok(())
+}
+
+// Permissionless and idempotent. Queues the expired round, then settles the given round if it is
+// completed or requests randomness for it if it is first in the queue, an empty round expires instead.
+// Optional account groups that are not passed skip the matching step
+pub fn tick<'info>(ctx: Context<'_, '_, '_, 'info, Tick<'info>>, seeds: Vec<[u8; 32]>) -> Result<()> {
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    require_keeper(
+        raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.check_version()?;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    let (enqueued, front, pending_queue_bump) = {
+        let mut pending_queue = ctx.accounts.pending_queue.load_mut()?;
+        let enqueued = enqueue_expired_round(sol_raffle, &mut pending_queue, now)?;
+        (enqueued, pending_queue.front(), pending_queue.bump)
+    };
+
+    let round_info = ctx.accounts.round.to_account_info();
+    let mut round = Round::try_deserialize(&mut &round_info.try_borrow_data()?[..])?;
+    round.check_version()?;
+
+    let round_id = round.round_id;
+    let (round_pubkey, _) = Pubkey::find_program_address(
+        &[b"round", sol_raffle.key().as_ref(), &round_id.to_le_bytes()],
+        &crate::id(),
+    );
+    require_keys_eq!(round_info.key(), round_pubkey, RaffleError::RoundNotAvailable);
+
+    let mut settled = None;
+    let mut randomness_requested = None;
+    let mut expired = None;
+
+    if is_settleable(raffle_state, &round) {
+        if let (
+            Some(round_tickets),
+            Some(winner),
+            Some(rent_vault),
+            Some(beneficiary),
+            Some(insurance_vault),
+            Some(raffle_stats),
+            Some(raffle_stats_bump),
+            Some(daily_stats),
+            Some(daily_stats_bump),
+            Some(winner_history),
+        ) = (
+            &ctx.accounts.round_tickets,
+            &ctx.accounts.winner,
+            &ctx.accounts.rent_vault,
+            &ctx.accounts.beneficiary,
+            &ctx.accounts.insurance_vault,
+            ctx.accounts.raffle_stats.as_mut(),
+            ctx.bumps.raffle_stats,
+            ctx.accounts.daily_stats.as_mut(),
+            ctx.bumps.daily_stats,
+            &ctx.accounts.winner_history,
+        ) {
+            // A blocked winner's prize stays in the vault, skipping keeps the crank moving
+            if is_blocked(raffle_state, ctx.accounts.blocklist.as_ref(), &winner.key())? {
+                msg!("🚨 Winner of round {} is blocked, settlement skipped", round_id);
+            } else {
+                settle_completed_round(
+                    raffle_state,
+                    &mut round,
+                    winner,
+                    &ctx.accounts.keeper.to_account_info(),
+                    SettlementAccounts {
+                        vault: &ctx.accounts.vault,
+                        vault_ledger: &ctx.accounts.vault_ledger,
+                        beneficiary,
+                        insurance_vault,
+                    },
+                )?;
+                record_payout(
+                    PayoutStats {
+                        raffle_stats,
+                        raffle_stats_bump,
+                        daily_stats,
+                        daily_stats_bump,
+                        winner_history,
+                    },
+                    &round,
+                    round.prize_amount,
+                    true,
+                )?;
+
+                let mut round_data = round_info.try_borrow_mut_data()?;
+                let mut writer = &mut round_data[..];
+                round.try_serialize(&mut writer)?;
+                drop(round_data);
+
+                round_tickets.close(rent_vault.to_account_info())?;
+                settled = Some(round_id);
+            }
+        }
+    } else if front == Some(round_id)
+        && !round.frozen
+        && is_randomness_request_due(raffle_state, &round, now)
+    {
+        if let (Some(vrf), Some(vrf_fee_vault), Some(client_state), Some(client), Some(network_state), Some(treasury)) = (
+            &ctx.accounts.vrf,
+            &ctx.accounts.vrf_fee_vault,
+            &ctx.accounts.client_state,
+            &ctx.accounts.client,
+            &ctx.accounts.network_state,
+            &ctx.accounts.treasury,
+        ) {
+            require_eq!(
+                ctx.remaining_accounts.len(),
+                seeds.len(),
+                RaffleError::SeedMismatch
+            );
+
+            let draw = prepare_round_request(
+                raffle_state,
+                sol_raffle,
+                &round_info,
+                RequestFundingAccounts {
+                    vault: &ctx.accounts.vault,
+                    vault_ledger: &ctx.accounts.vault_ledger,
+                    keeper: &ctx.accounts.keeper.to_account_info(),
+                    insurance_vault: ctx.accounts.insurance_vault.as_ref().map(|v| v.as_ref()),
+                    mega_jackpot: ctx.accounts.mega_jackpot.as_mut(),
+                    system_program: &ctx.accounts.system_program.to_account_info(),
+                },
+                now,
+            )?;
+
+            if draw {
+                submit_vrf_request(
+                    raffle_state,
+                    ctx.accounts.cluster_profile.as_deref(),
+                    sol_raffle,
+                    round_id,
+                    VrfRequestAccounts {
+                        pending_queue: ctx.accounts.pending_queue.to_account_info(),
+                        pending_queue_bump,
+                        vrf: vrf.to_account_info(),
+                        vrf_fee_vault: vrf_fee_vault.to_account_info(),
+                        vrf_fee_vault_bump: ctx.bumps.vrf_fee_vault.ok_or(RaffleError::NotEnoughRemainingAccounts)?,
+                        client_state: client_state.to_account_info(),
+                        client_state_bump: client_state.bump,
+                        client: client.to_account_info(),
+                        network_state: network_state.to_account_info(),
+                        treasury: treasury.to_account_info(),
+                        system_program: ctx.accounts.system_program.to_account_info(),
+                    },
+                    ctx.remaining_accounts,
+                    &seeds,
+                )?;
+
+                randomness_requested = Some(round_id);
+            } else {
+                ctx.accounts.pending_queue.load_mut()?.remove(round_id);
+                expired = Some(round_id);
+            }
+        }
+    }
+
+    emit!(Ticked {
+        keeper: ctx.accounts.keeper.key(),
+        enqueued,
+        randomness_requested,
+        expired,
+        settled,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct LockRound<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    pub keeper: Signer<'info>,
+
+    // Required while the keeper allowlist is enabled
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct SettleRound<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = rent_vault,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+
+    /// CHECK: checked against round.winner_address
+    #[account(mut)]
+    pub winner: AccountInfo<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    // Required while the keeper allowlist is enabled
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_vault"],
+        bump
+    )]
+    pub rent_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = beneficiary.key() == raffle_state.beneficiary @ RaffleError::InvalidBeneficiary
+    )]
+    /// CHECK: key validated via constraint
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    // Lifetime prize totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: WinnerHistory PDA for the recent winners widget, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    // Required with a memo
+    pub memo_program: Option<Program<'info, Memo>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id_prev: u32, round_id_next: u32)]
+pub struct Rollover<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump = pending_queue.load()?.bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    /// CHECK: created via CPI, must not exist yet
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id_next.to_le_bytes()
+        ],
+        bump
+    )]
+    pub next_round: UncheckedAccount<'info>,
+
+    /// CHECK: created via CPI
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            next_round.key().as_ref()
+        ],
+        bump
+    )]
+    pub next_round_tickets: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_vault"],
+        bump
+    )]
+    pub rent_vault: SystemAccount<'info>,
+
+    pub keeper: Signer<'info>,
+
+    // Required while the keeper allowlist is enabled
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct Tick<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump = pending_queue.load()?.bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    /// CHECK: the round to settle or draw, key checked in the handler
+    #[account(mut, owner = crate::id())]
+    pub round: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    // Required while the keeper allowlist is enabled
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
+    // Randomness request accounts, see RequestRandomness
+    #[account(
+        mut,
+        seeds = [b"vrf_fee_vault"],
+        bump
+    )]
+    pub vrf_fee_vault: Option<SystemAccount<'info>>,
+
+    pub vrf: Option<Program<'info, OraoVrfCb>>,
+
+    #[account(
+        mut,
+        seeds = [CLIENT_STATE_SEED],
+        bump = client_state.bump
+    )]
+    pub client_state: Option<Account<'info, ClientState>>,
+
+    #[account(
+        mut,
+        seeds = [CB_CLIENT_ACCOUNT_SEED, crate::id().as_ref(), client.state.as_ref()],
+        seeds::program = orao_solana_vrf_cb::id(),
+        bump = client.bump,
+    )]
+    pub client: Option<Account<'info, Client>>,
+
+    #[account(
+        mut,
+        seeds = [CB_CONFIG_ACCOUNT_SEED],
+        seeds::program = orao_solana_vrf_cb::id(),
+        bump = network_state.bump,
+    )]
+    pub network_state: Option<Account<'info, NetworkState>>,
+
+    /// CHECK: Treasury ORAO VRF
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    // Required once the cluster profile is set up
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Settlement accounts, see SettleRound
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: Option<AccountLoader<'info, RoundTickets>>,
+
+    /// CHECK: checked against round.winner_address
+    #[account(mut)]
+    pub winner: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_vault"],
+        bump
+    )]
+    pub rent_vault: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        constraint = beneficiary.key() == raffle_state.beneficiary @ RaffleError::InvalidBeneficiary
+    )]
+    /// CHECK: key validated via constraint
+    pub beneficiary: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    // Lifetime prize totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Option<Account<'info, RaffleStats>>,
+
+    /// CHECK: WinnerHistory PDA for the recent winners widget, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: Option<UncheckedAccount<'info>>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Option<Account<'info, DailyStats>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    // With insurance_vault, covers the prize floor when a round moves to Drawing
+    #[account(
+        mut,
+        seeds = [b"mega_jackpot"],
+        bump = mega_jackpot.bump
+    )]
+    pub mega_jackpot: Option<Account<'info, MegaJackpot>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeKeeperRegistry<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + KeeperRegistry::INIT_SPACE,
+        seeds = [b"keeper_registry"],
+        bump
+    )]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateKeeperRegistry<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Account<'info, KeeperRegistry>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct KeeperRegistry {
+    #[max_len(MAX_KEEPERS)]
+    pub keepers: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct KeeperAllowlistToggled {
+    pub enabled: bool,
+}
+
+#[event]
+pub struct KeeperAdded {
+    pub keeper: Pubkey,
+}
+
+#[event]
+pub struct KeeperRemoved {
+    pub keeper: Pubkey,
+}
+
+#[event]
+pub struct Ticked {
+    pub keeper: Pubkey,
+    pub enqueued: Option<u32>,
+    pub randomness_requested: Option<u32>,
+    pub expired: Option<u32>, // ended without tickets, dropped from the queue
+    pub settled: Option<u32>,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct KeeperTipUpdated {
+    pub keeper_tip: u64,
+}
+
+#[event]
+pub struct KeeperTipPaid {
+    pub round_id: u32,
+    pub keeper: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct RoundSettled {
+    pub round_id: u32,
+    pub winner: Pubkey,
+    pub keeper: Pubkey,
+    pub prize_amount: u64,
+    pub commission_amount: u64,
+    pub keeper_tip: u64,
+}
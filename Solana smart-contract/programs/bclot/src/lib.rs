@@ -3,42 +3,119 @@ use anchor_lang::solana_program::{
     program::invoke_signed,
     system_instruction,
 };
-use anchor_lang::system_program::{self, CreateAccount};
 use anchor_lang::Discriminator;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
-use rust_decimal::prelude::ToPrimitive;
-use rust_decimal::Decimal;
-use std::str::FromStr;
-use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
+use anchor_spl::memo::{self, BuildMemo, Memo};
+use anchor_spl::token::Mint;
 use orao_solana_vrf_cb::{
-    cpi,
+    cpi as orao_cpi,
     program::OraoVrfCb,
     state::{
         client::{Callback, Client, RemainingAccount},
         network_state::NetworkState,
-        request::RequestAccount,
     },
-    RequestParams, CB_CLIENT_ACCOUNT_SEED, CB_CONFIG_ACCOUNT_SEED, CB_REQUEST_ACCOUNT_SEED,
+    RequestParams, CB_CLIENT_ACCOUNT_SEED, CB_CONFIG_ACCOUNT_SEED,
 };
-use bytemuck::{Pod, Zeroable};
-use std::cell::RefMut; 
 
 declare_id!("31qdCe9TKthjQGPmZ8ZzoU7KD8vbq1F6Zmo2K4wfERHh");
 
+pub mod access_list;
 pub mod admin;
+pub mod attestation;
+pub mod audit;
+pub mod blocklist;
+pub mod cancellation;
+pub mod cluster_profile;
+pub mod commission;
+pub mod compound;
+pub mod daily_stats;
+pub mod delegate;
+pub mod draw_proof;
+#[cfg(feature = "no-entrypoint")]
+pub mod event_schema;
+pub mod fee_discount;
+pub mod governance;
+pub mod house_ticket;
+pub mod insurance;
+pub mod jackpot;
+pub mod keeper;
+pub mod lifecycle;
+pub mod metadata;
+pub mod migration;
+pub mod oracle_failover;
+pub mod ownership;
+pub mod player_stats;
+pub mod price_cache;
 pub mod price_feeds;
+pub mod prize_limits;
+pub mod promo;
+pub mod purchase_limits;
+pub mod raffle_stats;
+pub mod queue;
+pub mod records;
+pub mod redraw;
+pub mod schedule;
+pub mod solvency;
+pub mod sponsor;
+pub mod staking;
+pub mod state;
+pub mod treasury;
+pub mod tuktuk;
+pub mod vesting;
 pub mod vrf;
+pub mod watchtower;
+pub mod winner_history;
 
+use access_list::*;
 use admin::*;
+use audit::*;
+use blocklist::*;
+use cancellation::*;
+use cluster_profile::*;
+use commission::*;
+use compound::*;
+use daily_stats::*;
+use delegate::*;
+use draw_proof::*;
+use fee_discount::*;
+use governance::*;
+use house_ticket::*;
+use insurance::*;
+use jackpot::*;
+use keeper::*;
+use lifecycle::*;
+use metadata::*;
+use migration::*;
+use oracle_failover::*;
+use ownership::*;
+use player_stats::*;
+use price_cache::*;
 use price_feeds::*;
+use prize_limits::*;
+use promo::*;
+use purchase_limits::*;
+use raffle_stats::*;
+use queue::*;
+use records::*;
+use redraw::*;
+use schedule::*;
+use solvency::*;
+use sponsor::*;
+use staking::*;
+use treasury::*;
+use tuktuk::*;
+use vesting::*;
 use vrf::*;
+use watchtower::*;
+use winner_history::*;
 
 // Constants
 const CLIENT_STATE_SEED: &[u8] = b"CLIENT_STATE";
 const SECONDS_IN_DAY: i64 = 86400;
 const NY_OFFSET: i64 = 4 * 3600; // UTC-4
-const ROUND_DURATION: i64 = 43200; // 12 hours
+const ROUND_DURATION: i64 = 43200; // 12 hours, default cadence
 const MAX_TICKETS: usize = 2048; // 1024 + 1024
+const MAX_MEMO_LEN: usize = 128;
+const SOL_DECIMALS: u8 = 9;
 
 #[program]
 pub mod raffle {
@@ -48,19 +125,43 @@ pub mod raffle {
         admin::get_raffle_state(ctx)
     }
 
+    pub fn get_raffle_stats(ctx: Context<GetRaffleStats>) -> Result<RaffleStats> {
+        raffle_stats::get_raffle_stats(ctx)
+    }
+
+    // Permissionless, opens a raffle's stats for today or tomorrow (UTC)
+    pub fn initialize_daily_stats(ctx: Context<InitializeDailyStats>, day: i64) -> Result<()> {
+        daily_stats::initialize_daily_stats(ctx, day)
+    }
+
+    pub fn get_daily_stats(ctx: Context<GetDailyStats>, day: i64) -> Result<DailyStats> {
+        daily_stats::get_daily_stats(ctx, day)
+    }
+
     pub fn get_token_raffle(ctx: Context<GetTokenRaffle>) -> Result<TokenRaffleView> {
         admin::get_token_raffle(ctx)
     }
 
-    pub fn get_ticket_price_in_btc(ctx: Context<EmptyAccounts>) -> Result<u64> {
-        admin::get_ticket_price_in_btc(ctx)
+    pub fn get_ticket_reference_price(ctx: Context<GetRaffleState>) -> Result<Vec<PegComponent>> {
+        admin::get_ticket_reference_price(ctx)
+    }
+
+    pub fn get_ticket_price(ctx: Context<GetTicketPrice>, round_id: u32) -> Result<u64> {
+        admin::get_ticket_price(ctx, round_id)
     }
 
-    pub fn get_ticket_price(ctx: Context<GetTicketPrice>) -> Result<u64> {
-        admin::get_ticket_price(ctx)
+    pub fn get_price_breakdown(ctx: Context<GetTicketPrice>, round_id: u32) -> Result<PriceBreakdownView> {
+        admin::get_price_breakdown(ctx, round_id)
     }
 
-    pub fn get_raffle_round_result(ctx: Context<GetRoundAccounts>, round_id: u32) -> Result<RoundResultView> {
+    pub fn get_state_bundle(ctx: Context<GetStateBundle>) -> Result<StateBundleView> {
+        admin::get_state_bundle(ctx)
+    }
+
+    pub fn get_raffle_round_result<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetRoundAccounts<'info>>,
+        round_id: u32,
+    ) -> Result<RoundResultView> {
         admin::get_raffle_round_result(ctx, round_id)
     }
 
@@ -68,6 +169,30 @@ pub mod raffle {
         admin::get_raffle_round_data(ctx, round_id)
     }
 
+    pub fn get_round_purchases<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetRoundPurchases<'info>>,
+        round_id: u32,
+        start_index: u32,
+        limit: u8,
+    ) -> Result<Vec<RoundPurchaseView>> {
+        admin::get_round_purchases(ctx, round_id, start_index, limit)
+    }
+
+    pub fn get_round_addresses(
+        ctx: Context<GetTokenRaffle>,
+        start_round_id: u32,
+        limit: u8,
+    ) -> Result<Vec<RoundAddressesView>> {
+        admin::get_round_addresses(ctx, start_round_id, limit)
+    }
+
+    pub fn verify_round_invariants<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetRoundAccounts<'info>>,
+        round_id: u32,
+    ) -> Result<RoundInvariantsView> {
+        admin::verify_round_invariants(ctx, round_id)
+    }
+
     pub fn get_current_raffle_round_id(ctx: Context<GetTockenRaffle>) -> Result<u32> {
         admin::get_current_raffle_round_id(ctx)
     }
@@ -88,6 +213,8 @@ pub mod raffle {
         raffle_state.beneficiary = beneficiary;
         raffle_state.created_at = Clock::get()?.unix_timestamp;
         raffle_state.vrf_request_counter = 0;
+        raffle_state.ticket_price_satoshis = DEFAULT_TICKET_SATOSHIS;
+        raffle_state.version = RaffleState::VERSION;
         raffle_state.bump = ctx.bumps.raffle_state;
 
         msg!("RaffleState initialized successfully");
@@ -96,19 +223,214 @@ pub mod raffle {
         Ok(())
     }
 
-    pub fn initialize_sol_raffle(ctx: Context<InitializeSolRaffle>) -> Result<()> {
+    // Every cadence is a separate TokenRaffle sharing price feeds, vaults and the VRF client.
+    // Zero round_duration falls back to the default 12 hours cadence
+    pub fn initialize_sol_raffle(
+        ctx: Context<InitializeSolRaffle>,
+        cadence_id: u8,
+        round_duration: i64,
+    ) -> Result<()> {
+        let round_duration = if round_duration == 0 { ROUND_DURATION } else { round_duration };
+        require!(
+            is_valid_round_duration(round_duration),
+            RaffleError::InvalidRoundDuration
+        );
+
         let sol_raffle = &mut ctx.accounts.sol_raffle;
         
         sol_raffle.authority = ctx.accounts.authority.key();
         sol_raffle.total_rounds = 0;
+        sol_raffle.cadence_id = cadence_id;
+        sol_raffle.round_duration = round_duration;
+        sol_raffle.token_decimals = SOL_DECIMALS;
+        sol_raffle.version = TokenRaffle::VERSION;
         sol_raffle.bump = ctx.bumps.sol_raffle;
 
+        let mut pending_queue = ctx.accounts.pending_queue.load_init()?;
+        pending_queue.token_raffle = sol_raffle.key();
+        pending_queue.bump = ctx.bumps.pending_queue;
+
         msg!("TokenRaffle initialized successfully");
-        msg!("Sol Raffle PDA: {}", sol_raffle.key());
+        msg!("Sol Raffle PDA: {} (cadence {}, {}s rounds)", sol_raffle.key(), cadence_id, round_duration);
         
         Ok(())
     }
 
+    // Sets up a raffle for any asset with a registered price feed. SOL raffles pass no mint.
+    // Seeds stay [b"sol_raffle", cadence_id] for every asset, the rest of the program derives them that way
+    pub fn initialize_token_raffle(
+        ctx: Context<InitializeTokenRaffle>,
+        cadence_id: u8,
+        args: TokenRaffleArgs,
+    ) -> Result<()> {
+        let round_duration = if args.round_duration == 0 { ROUND_DURATION } else { args.round_duration };
+        require!(
+            is_valid_round_duration(round_duration),
+            RaffleError::InvalidRoundDuration
+        );
+        if let Some(fee_percentage) = args.fee_percentage {
+            require!(fee_percentage <= 100, RaffleError::InvalidFeePercentage);
+        }
+
+        let (token_mint, mint_decimals) = match &ctx.accounts.token_mint {
+            Some(mint) => (mint.key(), mint.decimals),
+            None => (Pubkey::default(), SOL_DECIMALS),
+        };
+        require!(args.decimals == mint_decimals, RaffleError::InvalidTokenDecimals);
+
+        let price_feed = ctx.accounts.price_feed.key();
+        require!(
+            ctx.accounts.feed_registry.is_registered(&token_mint, &price_feed),
+            RaffleError::FeedNotRegistered
+        );
+        check_feed_account(&ctx.accounts.price_feed)?;
+
+        let sol_raffle = &mut ctx.accounts.sol_raffle;
+
+        sol_raffle.authority = ctx.accounts.authority.key();
+        sol_raffle.token_mint = token_mint;
+        sol_raffle.price_feed = price_feed;
+        sol_raffle.token_decimals = args.decimals;
+        sol_raffle.fee_percentage = args.fee_percentage;
+        sol_raffle.total_rounds = 0;
+        sol_raffle.cadence_id = cadence_id;
+        sol_raffle.round_duration = round_duration;
+        sol_raffle.version = TokenRaffle::VERSION;
+        sol_raffle.bump = ctx.bumps.sol_raffle;
+
+        let mut pending_queue = ctx.accounts.pending_queue.load_init()?;
+        pending_queue.token_raffle = sol_raffle.key();
+        pending_queue.bump = ctx.bumps.pending_queue;
+
+        msg!("✅ TokenRaffle initialized for mint {}", token_mint);
+        msg!("Sol Raffle PDA: {} (cadence {}, {}s rounds)", sol_raffle.key(), cadence_id, round_duration);
+
+        Ok(())
+    }
+
+    // Permissionless, creates the PendingQueue of a raffle initialized before it existed
+    pub fn initialize_pending_queue(ctx: Context<InitializePendingQueue>) -> Result<()> {
+        queue::initialize_pending_queue(ctx)
+    }
+
+    // Only authority
+    pub fn initialize_cluster_profile(
+        ctx: Context<InitializeClusterProfile>,
+        cluster: Cluster,
+        args: ClusterProfileArgs,
+    ) -> Result<()> {
+        cluster_profile::initialize_cluster_profile(ctx, cluster, args)
+    }
+
+    // Only authority
+    pub fn set_cluster_profile(ctx: Context<UpdateClusterProfile>, args: ClusterProfileArgs) -> Result<()> {
+        cluster_profile::set_cluster_profile(ctx, args)
+    }
+
+    // Only authority
+    pub fn initialize_feed_registry(ctx: Context<InitializeFeedRegistry>) -> Result<()> {
+        price_feeds::initialize_feed_registry(ctx)
+    }
+
+    // Only authority
+    pub fn register_price_feed(ctx: Context<UpdateFeedRegistry>, mint: Pubkey, feed: Pubkey) -> Result<()> {
+        price_feeds::register_price_feed(ctx, mint, feed)
+    }
+
+    // Only authority
+    pub fn unregister_price_feed(ctx: Context<UpdateFeedRegistry>, mint: Pubkey) -> Result<()> {
+        price_feeds::unregister_price_feed(ctx, mint)
+    }
+
+    // Only authority
+    pub fn set_feed_read_limits(
+        ctx: Context<UpdateFeedRegistry>,
+        mint: Pubkey,
+        max_staleness_slots: u64,
+        min_samples: u8,
+    ) -> Result<()> {
+        price_feeds::set_feed_read_limits(ctx, mint, max_staleness_slots, min_samples)
+    }
+
+    // Only authority
+    pub fn initialize_feed_failover(ctx: Context<InitializeFeedFailover>) -> Result<()> {
+        oracle_failover::initialize_feed_failover(ctx)
+    }
+
+    // Only authority. remaining_accounts: every fallback feed, in order
+    pub fn set_feed_failover(ctx: Context<UpdateFeedFailover>, primary: Pubkey, fallbacks: Vec<Pubkey>) -> Result<()> {
+        oracle_failover::set_feed_failover(ctx, primary, fallbacks)
+    }
+
+    // Only authority, meant to be the SPL Governance PDA. Used only once every oracle fails
+    pub fn set_emergency_price(
+        ctx: Context<SetEmergencyPrice>,
+        reference_usd: u64,
+        sol_usd: u64,
+        expiry_slot: u64,
+    ) -> Result<()> {
+        oracle_failover::set_emergency_price(ctx, reference_usd, sol_usd, expiry_slot)
+    }
+
+    // Only authority
+    pub fn clear_emergency_price(ctx: Context<UpdateFeedFailover>) -> Result<()> {
+        oracle_failover::clear_emergency_price(ctx)
+    }
+
+    // Only authority
+    pub fn initialize_price_cache(ctx: Context<InitializePriceCache>) -> Result<()> {
+        price_cache::initialize_price_cache(ctx)
+    }
+
+    // Only authority
+    pub fn set_cached_feeds(
+        ctx: Context<UpdatePriceCache>,
+        sol_feed: Pubkey,
+        reference_feeds: Vec<Pubkey>,
+        max_age_slots: u64,
+    ) -> Result<()> {
+        price_cache::set_cached_feeds(ctx, sol_feed, reference_feeds, max_age_slots)
+    }
+
+    // Permissionless. remaining_accounts: the cached feeds to refresh
+    pub fn refresh_price(ctx: Context<RefreshPrice>) -> Result<()> {
+        price_cache::refresh_price(ctx)
+    }
+
+    // Only authority
+    pub fn initialize_program_version(ctx: Context<InitializeProgramVersion>) -> Result<()> {
+        migration::initialize_program_version(ctx)
+    }
+
+    // Only authority
+    pub fn migrate_raffle_state(ctx: Context<MigrateRaffleState>) -> Result<()> {
+        migration::migrate_raffle_state(ctx)
+    }
+
+    // Permissionless, the payer funds the extra rent. Grows an account created before its latest
+    // fields were appended
+    pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+        migration::migrate_account(ctx)
+    }
+
+    // Only authority
+    pub fn initialize_program_metadata(
+        ctx: Context<InitializeProgramMetadata>,
+        args: ProgramMetadataArgs,
+    ) -> Result<()> {
+        metadata::initialize_program_metadata(ctx, args)
+    }
+
+    // Only authority
+    pub fn set_program_metadata(ctx: Context<UpdateProgramMetadata>, args: ProgramMetadataArgs) -> Result<()> {
+        metadata::set_program_metadata(ctx, args)
+    }
+
+    // Only authority
+    pub fn set_raffle_metadata(ctx: Context<SetRaffleMetadata>, args: RaffleMetadataArgs) -> Result<()> {
+        metadata::set_raffle_metadata(ctx, args)
+    }
+
     pub fn initialize_client_state(ctx: Context<InitializeClientState>) -> Result<()> {
         vrf::initialize_client_state(ctx)
     }
@@ -150,13 +472,19 @@ pub mod raffle {
             vault_balance >= amount,
             RaffleError::InsufficientFunds
         );
-        
-        let vrf_vault_bump = ctx.bumps.vrf_fee_vault;
-        
+
         **ctx.accounts.vrf_fee_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
         **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
         
         msg!("Withdrawn {} lamports from VRF vault", amount);
+
+        record_audit(
+            &ctx.accounts.audit_log,
+            ctx.accounts.authority.key(),
+            AuditAction::VrfVaultWithdrawn,
+            amount,
+        )?;
+
         Ok(())
     }
 
@@ -197,181 +525,77 @@ pub mod raffle {
             vault_balance >= amount,
             RaffleError::InsufficientFunds
         );
-        
-        let vrf_vault_bump = ctx.bumps.rent_vault;
-        
+
         **ctx.accounts.rent_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
         **ctx.accounts.authority.to_account_info().try_borrow_mut_lamports()? += amount;
         
         msg!("Withdrawn {} lamports from VRF vault", amount);
+
+        record_audit(
+            &ctx.accounts.audit_log,
+            ctx.accounts.authority.key(),
+            AuditAction::RentVaultWithdrawn,
+            amount,
+        )?;
+
         Ok(())
     }
 
-    pub fn buy_tickets_sol(
-        ctx: Context<BuyTicketsSol>,
+    // remaining_accounts: the fee discount pair, if any, then the basket and fallback feeds,
+    // then the feed update's accounts
+    pub fn buy_tickets_sol<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTicketsSol<'info>>,
         round_id: u32,
         purchase_index: u32,
         count: u32,
-        max_cost: u64,
+        max_price_per_ticket: u64,
+        access_proof: Vec<[u8; 32]>, // empty unless the round is gated
+        feed_update: Option<FeedUpdate>, // None - the feeds are read as they are
     ) -> Result<()> {
         msg!("Tickets count: {}", count);
         require!(count > 0, RaffleError::InvalidTicketCount);
 
         let clock = Clock::get()?;
         let current_time = clock.unix_timestamp;
+        require!(
+            !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+            RaffleError::MaintenanceMode
+        );
+        require!(!ctx.accounts.sol_raffle.sales_paused, RaffleError::SalesPaused);
+
         let sol_raffle = &mut ctx.accounts.sol_raffle;
-        
+
+        // Rounds are opened by the rollover crank
         let round_exists = ctx.accounts.round.owner == ctx.program_id 
             && ctx.accounts.round.data_len() > 0;
-        
-        if !round_exists {
-            msg!("🆕 Round {} doesn't exist, creating...", round_id);
-            
-            require!(
-                purchase_index == 0,
-                RaffleError::InvalidPurchaseIndex
-            );
+        require!(round_exists, RaffleError::RoundNotCreated);
 
-            let rent = Rent::get()?;
-            let sol_raffle_key = sol_raffle.key();
-            
-            let vault_bump = ctx.bumps.rent_vault;
-            let round_bump = ctx.bumps.round;
-            let round_tickets_bump = ctx.bumps.round_tickets;
-            
-            let vault_seeds = &[b"rent_vault".as_ref(), &[vault_bump]];
-            
-            // ========== Create Round account ==========
-            let round_seeds = &[
-                b"round".as_ref(),
-                sol_raffle_key.as_ref(),
-                &round_id.to_le_bytes(),
-                &[round_bump],
-            ];
-            let round_space = 8 + Round::INIT_SPACE;
-            let round_lamports = rent.minimum_balance(round_space);
-
-            invoke_signed(
-                &system_instruction::create_account(
-                    &ctx.accounts.rent_vault.key(),
-                    &ctx.accounts.round.key(),
-                    round_lamports,
-                    round_space as u64,
-                    ctx.program_id,
-                ),
-                &[
-                    ctx.accounts.rent_vault.to_account_info(),
-                    ctx.accounts.round.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                &[vault_seeds, round_seeds],
-            )?;
-            
-            // Initialize Round
-            {
-                let round_end_time = get_next_ny_anchor_time(current_time);
-                let mut round_account_data = ctx.accounts.round.try_borrow_mut_data()?;
-                
-                let round_data = Round {
-                    initialized: true,
-                    token_raffle: sol_raffle_key,
-                    round_id,
-                    status: RoundStatus::Open,
-                    start_time: current_time,
-                    end_time: round_end_time,
-                    prize_amount: 0,
-                    commission_balance: 0,
-                    purchases_count: 0,
-                    total_tickets: 0,
-                    winner_ticket_index: None,
-                    winner_purchase_index: None,
-                    winner_address: None,
-                    prize_claimed: false,
-                    bump: round_bump,
-                };
-                
-                let mut writer = &mut round_account_data[..];
-                round_data.try_serialize(&mut writer)?;
-            }
-
-            // ========== Create RoundTickets account ==========
-            let round_key = ctx.accounts.round.key();
-            let round_tickets_seeds = &[
-                b"round_tickets".as_ref(),
-                round_key.as_ref(),
-                &[round_tickets_bump],
-            ];
-            let tickets_space = 8 + std::mem::size_of::<RoundTickets>();
-            let tickets_lamports = rent.minimum_balance(tickets_space);
-            
-            invoke_signed(
-                &system_instruction::create_account(
-                    &ctx.accounts.rent_vault.key(),
-                    &ctx.accounts.round_tickets.key(),
-                    tickets_lamports,
-                    tickets_space as u64,
-                    ctx.program_id,
-                ),
-                &[
-                    ctx.accounts.rent_vault.to_account_info(),
-                    ctx.accounts.round_tickets.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                &[vault_seeds, round_tickets_seeds],
-            )?;
-
-            // Initialize RoundTickets
-            {
-                let mut tickets_data = ctx.accounts.round_tickets.try_borrow_mut_data()?;
-            
-                let discriminator = RoundTickets::DISCRIMINATOR;
-                tickets_data[0..8].copy_from_slice(&discriminator);
-                
-                for byte in &mut tickets_data[8..] {
-                    *byte = 0;
-                }
-                
-                tickets_data[8..40].copy_from_slice(round_key.as_ref());
-                
-                let bump_offset = 8 + 32 + (1024 * 4) + (1024 * 4) + 4;
-                tickets_data[bump_offset] = round_tickets_bump;
-            }
-
-            // Update sol_raffle state
-            if let Some(prev_id) = sol_raffle.current_round_id {
-                let prev_round_end = sol_raffle.current_round_end_time.unwrap();
-
-                if current_time >= prev_round_end {
-                    if sol_raffle.current_round_status == RoundStatus::Open {
-                        if !sol_raffle.pending_rounds.contains(&prev_id) {
-                            sol_raffle.pending_rounds.push(prev_id);
-                            msg!("✅ Added round {} to pending", prev_id);
-                        }
-                    }
-                }
-            }
-
-            sol_raffle.current_round_id = Some(round_id);
-            sol_raffle.total_rounds = sol_raffle
-                .total_rounds
-                .checked_add(1)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
-            sol_raffle.current_round_status = RoundStatus::Open;
-            sol_raffle.current_round_end_time = Some(get_next_ny_anchor_time(current_time));
-            
-            msg!("✅ Round {} created", round_id);
-        }
-        
         // Load and deserialize Round
         let round_data = ctx.accounts.round.try_borrow_data()?;
         let mut round_reader = &round_data[..];
         let mut round = Round::try_deserialize(&mut round_reader)?;
         drop(round_data); // Release the borrow immediately
 
-        require!(
-            purchase_index == round.purchases_count,
-            RaffleError::InvalidPurchaseIndex
+        ctx.accounts.raffle_state.check_version()?;
+        sol_raffle.check_version()?;
+        round.check_version()?;
+
+        require_eq!(
+            purchase_index,
+            round.purchases_count,
+            RaffleError::PurchaseIndexMismatch
         );
+        require!(!round.free_entry, RaffleError::FreeEntryRound);
+        require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+        require_entry_allowed(
+            &ctx.accounts.raffle_state,
+            sol_raffle,
+            &round,
+            ctx.accounts.blocklist.as_ref(),
+            ctx.accounts.attestation.as_ref(),
+            &ctx.accounts.player.key(),
+            &access_proof,
+        )?;
 
         let current_round_id = sol_raffle.current_round_id.unwrap_or(0);
         let current_round_end_time = sol_raffle.current_round_end_time.unwrap_or(i64::MAX);
@@ -395,6 +619,7 @@ pub mod raffle {
                 tickets_round_key == ctx.accounts.round.key(),
                 RaffleError::RoundTicketsNotInitialized
             );
+            check_round_tickets_version(&tickets_data)?;
         }
 
         let round_tickets_purchase = &mut ctx.accounts.round_tickets_purchase;
@@ -410,115 +635,235 @@ pub mod raffle {
         )?;
 
         let raffle_state = &ctx.accounts.raffle_state;
-        let ticket_price: u64 = calculate_ticket_price_for_sol(
-                &ctx.accounts.btc_price_feed,
+        let cluster_profile = check_cluster_sol_feed(
+            raffle_state,
+            ctx.accounts.cluster_profile.as_deref(),
+            &ctx.accounts.sol_price_feed,
+        )?;
+        let (fee_discount, remaining_accounts) =
+            split_fee_discount_accounts(ctx.remaining_accounts, &ctx.accounts.player.key())?;
+        let price_accounts = update_feeds(
+            feed_update.as_ref(),
+            &ctx.accounts.sol_price_feed,
+            ctx.accounts.switchboard_program.as_ref(),
+            remaining_accounts,
+        )?;
+        let feed_reader = FeedReader::new(
+            require_feed_registry(raffle_state, ctx.accounts.feed_registry.as_deref())?,
+            ctx.accounts.feed_failover.as_deref(),
+            require_price_cache(raffle_state, ctx.accounts.price_cache.as_deref())?,
+            cluster_profile,
+            price_accounts,
+        );
+        let oracle_price: u64 = calculate_round_ticket_price(
+                raffle_state,
+                sol_raffle,
+                &round,
+                &feed_reader,
+                &ctx.accounts.reference_price_feed,
+                price_accounts,
                 &ctx.accounts.sol_price_feed,
             )?;
+        require!(oracle_price > 0, RaffleError::InvalidTicketPrice);
+        snapshot_open_prices(&mut round, &ctx.accounts.reference_price_feed, &ctx.accounts.sol_price_feed, &feed_reader)?;
+        check_price_circuit_breaker(&mut round, raffle_state.max_price_deviation_bps, oracle_price)?;
+        let ticket_price = apply_price_decay(sol_raffle, &round, oracle_price, current_time)?;
         require!(ticket_price > 0, RaffleError::InvalidTicketPrice);
+        // Slippage is bounded per ticket so the limit doesn't depend on count
+        if ticket_price > max_price_per_ticket {
+            msg!("Ticket price {} above max {}", ticket_price, max_price_per_ticket);
+            // Kept in the failed transaction's logs for the client to re-quote from
+            emit!(SlippageExceeded {
+                token: sol_raffle.token_mint,
+                round_id,
+                buyer: ctx.accounts.player.key(),
+                count,
+                ticket_price,
+                max_price_per_ticket,
+                cost: ticket_price.saturating_mul(count as u64),
+            });
+            return err!(RaffleError::InsufficientSlippage);
+        }
+        round_tickets_purchase.ticket_price = ticket_price;
+        round_tickets_purchase.oracle_price = oracle_price;
+        let price_slot = feed_reader.price_slot(&ctx.accounts.sol_price_feed, &PriceSampling::for_round(&round))?;
+        round_tickets_purchase.price_slot = price_slot;
+        let fee_bps = discounted_fee_bps(
+            round_fee_bps(raffle_state, sol_raffle, &round),
+            fee_discount.fee_discount_config.as_ref(),
+            fee_discount.staker_position.as_ref(),
+            round.start_time,
+        );
+        round_tickets_purchase.fee_bps = fee_bps;
 
         let cost = ticket_price
             .checked_mul(count as u64)
             .ok_or(ProgramError::ArithmeticOverflow)?;
-        require!(cost <= max_cost, RaffleError::InsufficientSlippage);
+        check_min_purchase(sol_raffle, count, cost)?;
 
         require!(
             ctx.accounts.player.lamports() >= cost,
             RaffleError::InsufficientFunds
         );
 
-        let commission_amount = cost
-            .checked_mul(raffle_state.entrance_fee_percentage as u64)
-            .ok_or(ProgramError::ArithmeticOverflow)?
-            .checked_div(100)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-
-        round.commission_balance = round
-            .commission_balance
-            .checked_add(commission_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-
-        let prize_amount = cost
-            .checked_sub(commission_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-
-        round.prize_amount = round
-            .prize_amount
-            .checked_add(prize_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let split = split_purchase(
+            raffle_state,
+            sol_raffle,
+            &mut round,
+            cost,
+            fee_bps,
+            ctx.accounts.mega_jackpot.is_some(),
+        )?;
+        round_tickets_purchase.jackpot_bps = Some(split.jackpot_bps);
+
+        // Immediate commission bypasses sol_vault
+        if split.immediate_commission > 0 {
+            let commission_vault = ctx
+                .accounts
+                .commission_vault
+                .as_mut()
+                .ok_or(RaffleError::MissingCommissionVault)?;
+            accrue_purchase_commission(
+                commission_vault,
+                &ctx.accounts.player,
+                &ctx.accounts.system_program,
+                &mut round,
+                split.immediate_commission,
+            )?;
+        }
+        let vault_amount = cost - split.jackpot_amount - split.immediate_commission;
 
-        let is_first_buyer = round.total_tickets == 0;
+        record_in_ledger_account(&ctx.accounts.vault_ledger, vault_amount)?;
 
         let mut new_total = round
             .total_tickets
             .checked_add(count)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
-        anchor_lang::system_program::transfer(
-            CpiContext::new(
-                ctx.accounts.system_program.to_account_info(),
-                anchor_lang::system_program::Transfer {
-                    from: ctx.accounts.player.to_account_info(),
-                    to: ctx.accounts.vault.to_account_info(),
-                },
-            ),
-            cost,
+        let best_streak = ctx.accounts.player_stats.best_streak;
+        let counted = ctx.accounts.player_stats.last_round_id == Some(round_id);
+        let streak_bonus = record_round_entry(
+            &mut ctx.accounts.player_stats,
+            sol_raffle,
+            ctx.accounts.player.key(),
+            round_id,
+            ctx.bumps.player_stats,
         )?;
+        // Kept so cancel_purchase can take the entry back
+        if !counted {
+            round_tickets_purchase.entry_streak = ctx.accounts.player_stats.current_streak;
+            round_tickets_purchase.best_streak_raised = ctx.accounts.player_stats.best_streak > best_streak;
+        }
+
+        new_total = add_bonus_tickets(round_tickets_purchase, new_total, streak_bonus)?;
 
-        if is_first_buyer {
-            new_total = new_total + 1;
+        let first_bonus = first_ticket_bonus(sol_raffle, purchase_index);
+        if first_bonus > 0 {
+            new_total = add_bonus_tickets(round_tickets_purchase, new_total, first_bonus)?;
 
-            round_tickets_purchase.tickets_count = round_tickets_purchase
-                .tickets_count
-                .checked_add(1)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
+            msg!("🎁 Purchase {} got {} bonus tickets", purchase_index, first_bonus);
 
             emit!(FirstTicketBonusAwarded {
                 token: sol_raffle.token_mint,
                 round_id: round.round_id,
                 buyer: ctx.accounts.player.key(),
+                purchase_index,
+                bonus_tickets: first_bonus,
                 timestamp: current_time,
                 round_start_time: round.start_time,
                 round_end_time: round.end_time,
             });
         }
 
+        let volume_bonus = volume_bonus_tickets(sol_raffle, count)?;
+        if volume_bonus > 0 {
+            new_total = add_bonus_tickets(round_tickets_purchase, new_total, volume_bonus)?;
+
+            emit!(VolumeBonusAwarded {
+                token: sol_raffle.token_mint,
+                round_id: round.round_id,
+                buyer: ctx.accounts.player.key(),
+                purchased: count,
+                bonus_tickets: volume_bonus,
+            });
+        }
+
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.player.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            vault_amount,
+        )?;
+
+        if let Some(mega_jackpot) = ctx.accounts.mega_jackpot.as_mut() {
+            contribute_to_jackpot(
+                mega_jackpot,
+                &ctx.accounts.player,
+                &ctx.accounts.system_program,
+                split.jackpot_amount,
+            )?;
+        }
+
+        round_tickets_purchase.loyalty_points =
+            accrue_loyalty_points(&mut ctx.accounts.player_stats, sol_raffle, cost)?;
+
         // Update round_tickets using zero-copy mutation
-        {
-            let mut tickets_data = ctx.accounts.round_tickets.try_borrow_mut_data()?;
-            
-            // Read current len (offset: 8 + 32 + 4096 + 4096 = 8232)
-            let len_offset = 8 + 32 + (1024 * 4) + (1024 * 4);
-            let len = u32::from_le_bytes([
-                tickets_data[len_offset],
-                tickets_data[len_offset + 1],
-                tickets_data[len_offset + 2],
-                tickets_data[len_offset + 3],
-            ]) as usize;
-            
-            require!(
-                len < MAX_TICKETS,
-                RaffleError::RoundTicketsFull
-            );
+        append_cumulative_tickets(&ctx.accounts.round_tickets, new_total)?;
 
-            // Write new cumulative value
-            let cumulative_bytes = new_total.to_le_bytes();
-            if len < 1024 {
-                // Write to cumulative_tickets_1 (offset: 8 + 32)
-                let ticket_offset = 8 + 32 + (len * 4);
-                tickets_data[ticket_offset..ticket_offset + 4].copy_from_slice(&cumulative_bytes);
-            } else {
-                // Write to cumulative_tickets_2 (offset: 8 + 32 + 4096)
-                let ticket_offset = 8 + 32 + (1024 * 4) + ((len - 1024) * 4);
-                tickets_data[ticket_offset..ticket_offset + 4].copy_from_slice(&cumulative_bytes);
-            }
-            
-            // Update len
-            let new_len = (len + 1) as u32;
-            tickets_data[len_offset..len_offset + 4].copy_from_slice(&new_len.to_le_bytes());
+        check_sponsor_match(&round, ctx.accounts.sponsor_match.as_ref().map(|sponsor_match| sponsor_match.key()))?;
+        if let Some(sponsor_match) = ctx.accounts.sponsor_match.as_mut() {
+            let matched = apply_sponsor_match(sponsor_match, &ctx.accounts.vault, &mut round, cost)?;
+            round_tickets_purchase.sponsor_matched = matched;
+            record_in_ledger_account(&ctx.accounts.vault_ledger, matched)?;
         }
 
+        record_wallet_purchase(
+            ctx.accounts.wallet_purchases.as_mut(),
+            ctx.accounts.round.key(),
+            ctx.accounts.player.key(),
+            ctx.bumps.wallet_purchases,
+            sol_raffle.max_purchases_per_wallet,
+        )?;
+
+        record_raffle_purchase(
+            &mut ctx.accounts.raffle_stats,
+            sol_raffle.key(),
+            ctx.bumps.raffle_stats,
+            round.purchases_count == 0,
+            new_total - round.total_tickets,
+            cost,
+        )?;
+
+        record_purchase(
+            &ctx.accounts.records,
+            &round,
+            ctx.accounts.player.key(),
+            cost,
+            ctx.accounts.player_stats.current_streak,
+            current_time,
+        )?;
+
+        record_daily_purchase(
+            &mut ctx.accounts.daily_stats,
+            sol_raffle.key(),
+            ctx.bumps.daily_stats,
+            Some(&mut ctx.accounts.player_stats),
+            new_total - round.total_tickets,
+            cost,
+            current_time,
+        )?;
+
+        // Bonus tickets included, they share the purchase's range
+        let ticket_start = round.total_tickets;
+        let effective_ticket_price = cost / (new_total - ticket_start) as u64;
+
         round.total_tickets = new_total;
         round.purchases_count += 1;
+        close_at_prize_target(sol_raffle, &mut round, current_time);
 
         {
             let mut final_round_data = ctx.accounts.round.try_borrow_mut_data()?;
@@ -531,10 +876,19 @@ pub mod raffle {
             round_id: round.round_id,
             buyer: ctx.accounts.player.key(),
             count,
+            ticket_price,
+            oracle_price,
+            price_slot,
             total_amount: cost,
-            prize_amount,
-            commission_amount,
+            prize_amount: split.prize_amount,
+            commission_amount: split.commission_amount,
+            fee_bps,
             timestamp: current_time,
+            purchase_index,
+            ticket_start,
+            ticket_end: new_total,
+            effective_ticket_price,
+            total_tickets: round.total_tickets,
         });
 
         Ok(())
@@ -552,217 +906,756 @@ pub mod raffle {
         );
 
         let sol_raffle = &mut ctx.accounts.sol_raffle;
+        sol_raffle.check_version()?;
 
-        let round_id = determine_round_to_process(sol_raffle)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        // The drawn round stays queued until the callback, retries find it at the front
+        let mut pending_queue = ctx.accounts.pending_queue.load_mut()?;
+        enqueue_expired_round(sol_raffle, &mut pending_queue, now)?;
+        let round_id = determine_round_to_process(sol_raffle, &pending_queue)?;
+        let pending_queue_bump = pending_queue.bump;
+        drop(pending_queue);
         msg!("round_id for winner pick: {}", round_id);
 
-        //Checking for available request_accounts to use
         let raffle_state = &mut ctx.accounts.raffle_state;
-        let idx = raffle_state.vrf_request_counter as usize;
-        msg!("idx: {}", idx);
-        msg!("seeds.len(): {}", seeds.len());
-        require!(idx < seeds.len(), RaffleError::AllRequestsCompleted);
 
-        let cpi_program = ctx.accounts.vrf.to_account_info();
+        require_keeper(
+            raffle_state,
+            ctx.accounts.keeper_registry.as_ref(),
+            &ctx.accounts.keeper.key(),
+        )?;
 
-        let vrf_fee_vault_bump = ctx.bumps.vrf_fee_vault;
-        
-        // Signature for both PDA: client_state and vrf_fee_vault
-        let signers_seeds: &[&[&[u8]]] = &[
-            &[CLIENT_STATE_SEED, &[ctx.accounts.client_state.bump]],
-            &[b"vrf_fee_vault", &[vrf_fee_vault_bump]],
-        ];
+        let (round_pubkey, _) = Pubkey::find_program_address(
+            &[b"round", sol_raffle.key().as_ref(), &round_id.to_le_bytes()],
+            &crate::id(),
+        );
+        require_keys_eq!(ctx.accounts.round.key(), round_pubkey, RaffleError::RoundNotAvailable);
+
+        let draw = prepare_round_request(
+            raffle_state,
+            sol_raffle,
+            &ctx.accounts.round,
+            RequestFundingAccounts {
+                vault: &ctx.accounts.vault,
+                vault_ledger: &ctx.accounts.vault_ledger,
+                keeper: &ctx.accounts.keeper.to_account_info(),
+                insurance_vault: ctx.accounts.insurance_vault.as_ref().map(|v| v.as_ref()),
+                mega_jackpot: ctx.accounts.mega_jackpot.as_mut(),
+                system_program: &ctx.accounts.system_program.to_account_info(),
+            },
+            now,
+        )?;
+        if !draw {
+            ctx.accounts.pending_queue.load_mut()?.remove(round_id);
+            return Ok(());
+        }
 
-        let request_account = ctx
-            .remaining_accounts
-            .get(idx)
-            .ok_or(RaffleError::NotEnoughRemainingAccounts)?;
+        submit_vrf_request(
+            raffle_state,
+            ctx.accounts.cluster_profile.as_deref(),
+            sol_raffle,
+            round_id,
+            VrfRequestAccounts {
+                pending_queue: ctx.accounts.pending_queue.to_account_info(),
+                pending_queue_bump,
+                vrf: ctx.accounts.vrf.to_account_info(),
+                vrf_fee_vault: ctx.accounts.vrf_fee_vault.to_account_info(),
+                vrf_fee_vault_bump: ctx.bumps.vrf_fee_vault,
+                client_state: ctx.accounts.client_state.to_account_info(),
+                client_state_bump: ctx.accounts.client_state.bump,
+                client: ctx.accounts.client.to_account_info(),
+                network_state: ctx.accounts.network_state.to_account_info(),
+                treasury: ctx.accounts.treasury.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            ctx.remaining_accounts,
+            &seeds,
+        )?;
 
-        let seed = seeds
-            .get(idx)
-            .ok_or(RaffleError::NotEnoughSeeds)?;
+        msg!("request_randomness done");
+        Ok(())
+    }
 
-        msg!("Preparing VRF request seed={:?} request_account={}", seed, request_account.key);
+    pub fn consume_randomness(
+        ctx: Context<ConsumeRandomness>,
+        round_id: u32
+    ) -> Result<()> {
+        vrf::consume_randomness(ctx, round_id)
+    }
 
-        let sol_raffle_key = sol_raffle.key();
-        
-        let round_seeds: &[&[u8]] = &[
-            b"round",
-            sol_raffle_key.as_ref(),
-            &round_id.to_le_bytes(),
-        ];
+    // Permissionless, resolves the winning purchase of rounds drawn over several pages
+    pub fn finalize_draw(ctx: Context<FinalizeDraw>, round_id: u32, page_hint: u32) -> Result<()> {
+        vrf::finalize_draw(ctx, round_id, page_hint)
+    }
 
-        let (round_pubkey, round_bump) =
-            Pubkey::find_program_address(round_seeds, &crate::id());
+    // Permissionless, the caller pays for the DrawProof account
+    pub fn publish_draw_proof(ctx: Context<PublishDrawProof>, round_id: u32) -> Result<()> {
+        draw_proof::publish_draw_proof(ctx, round_id)
+    }
 
-        msg!("Computed round PDA = {} (round_id={})", round_pubkey, round_id);
+    // Permissionless, closes the expired round and opens the next one
+    pub fn rollover(ctx: Context<Rollover>, round_id_prev: u32, round_id_next: u32) -> Result<()> {
+        keeper::rollover(ctx, round_id_prev, round_id_next)
+    }
 
-        // Prepare callback instruction data
-        let callback_ix = crate::instruction::ConsumeRandomness { round_id };
+    // Permissionless crank for schedulers, does whatever work is due
+    pub fn tick<'info>(
+        ctx: Context<'_, '_, '_, 'info, Tick<'info>>,
+        seeds: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        keeper::tick(ctx, seeds)
+    }
 
-        let mut callback = Callback::from_instruction_data(&callback_ix);
-        
-        callback = callback.with_remaining_account(RemainingAccount::writable(
-            sol_raffle_key,
-            vec![
-                b"sol_raffle".to_vec(),
-                vec![sol_raffle.bump],
-            ],
-        ));
-        
-        callback = callback.with_remaining_account(RemainingAccount::writable(
-            round_pubkey,
-            vec![
-                b"round".to_vec(),
-                sol_raffle_key.as_ref().to_vec(),
-                round_id.to_le_bytes().to_vec(), 
-                vec![round_bump],
-            ],
-        ));
+    // Permissionless unless the keeper allowlist is on
+    pub fn redraw<'info>(
+        ctx: Context<'_, '_, '_, 'info, Redraw<'info>>,
+        round_id: u32,
+        seeds: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        redraw::redraw(ctx, round_id, seeds)
+    }
 
-        let round_tickets_seeds: &[&[u8]] = &[
-            b"round_tickets",
-            round_pubkey.as_ref(),
-        ];
+    // Permissionless unless the keeper allowlist is on
+    pub fn lock_round(ctx: Context<LockRound>, round_id: u32) -> Result<()> {
+        keeper::lock_round(ctx, round_id)
+    }
 
-        let (round_tickets_pubkey, round_tickets_bump) =
-            Pubkey::find_program_address(round_tickets_seeds, &crate::id());
+    // Permissionless, pays the keeper tip
+    pub fn settle_round(ctx: Context<SettleRound>, round_id: u32, memo: Option<String>) -> Result<()> {
+        keeper::settle_round(ctx, round_id, memo)
+    }
 
-        msg!("Computed round_tickets PDA = {}", round_tickets_pubkey);
+    // Only authority
+    pub fn set_keeper_tip(ctx: Context<UpdateRaffleState>, keeper_tip: u64) -> Result<()> {
+        keeper::set_keeper_tip(ctx, keeper_tip)
+    }
 
-        callback = callback.with_remaining_account(RemainingAccount::writable(
-            round_tickets_pubkey,
-            vec![
-                b"round_tickets".to_vec(),
-                round_pubkey.as_ref().to_vec(),
-                vec![round_tickets_bump],
-            ],
-        ));
-
-        let mut cpi_accounts = cpi::accounts::Request {
-            payer: ctx.accounts.vrf_fee_vault.to_account_info(),
-            state: ctx.accounts.client_state.to_account_info(),
-            client: ctx.accounts.client.to_account_info(),
-            network_state: ctx.accounts.network_state.to_account_info(),
-            treasury: ctx.accounts.treasury.to_account_info(),
-            request: request_account.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-        };
+    // Only authority
+    pub fn set_randomness_rate_limit(
+        ctx: Context<UpdateRaffleState>,
+        cooldown_seconds: i64,
+        max_requests: u8,
+    ) -> Result<()> {
+        vrf::set_randomness_rate_limit(ctx, cooldown_seconds, max_requests)
+    }
 
-        // Both accounts must be signer
-        cpi_accounts.state.is_signer = true;
-        cpi_accounts.payer.is_signer = true;
+    // Only authority
+    pub fn reset_randomness_requests(ctx: Context<ResetRandomnessRequests>, round_id: u32) -> Result<()> {
+        vrf::reset_randomness_requests(ctx, round_id)
+    }
 
-        let cpi_ctx = CpiContext::new(cpi_program.clone(), cpi_accounts).with_signer(signers_seeds);
+    // Only authority
+    pub fn initialize_audit_log(ctx: Context<InitializeAuditLog>) -> Result<()> {
+        audit::initialize_audit_log(ctx)
+    }
 
-        cpi::request(
-            cpi_ctx,
-            RequestParams::new(*seed).with_callback(Some(callback))
-        )?;
+    pub fn get_audit_log(ctx: Context<GetAuditLog>, from_seq: u64, limit: u8) -> Result<Vec<AuditEntryView>> {
+        audit::get_audit_log(ctx, from_seq, limit)
+    }
 
-        msg!("Submitted VRF request -> request_account={} round={}", request_account.key, round_pubkey);
+    // Only authority
+    pub fn initialize_winner_history(ctx: Context<InitializeWinnerHistory>) -> Result<()> {
+        winner_history::initialize_winner_history(ctx)
+    }
 
-        raffle_state.vrf_request_counter += 1;
-        if seeds.len() == raffle_state.vrf_request_counter as usize {
-            raffle_state.vrf_request_counter = 0;
+    pub fn get_winner_history(
+        ctx: Context<GetWinnerHistory>,
+        from_seq: u64,
+        limit: u8,
+    ) -> Result<Vec<WinnerEntryView>> {
+        winner_history::get_winner_history(ctx, from_seq, limit)
+    }
 
-            msg!("AllRequestsCompleted");
-            emit!(AllRequestsCompleted {});
-        }
+    // Only authority
+    pub fn initialize_records(ctx: Context<InitializeRecords>) -> Result<()> {
+        records::initialize_records(ctx)
+    }
 
-        msg!("request_randomness done");
-        Ok(())
+    pub fn get_records(ctx: Context<GetRecords>) -> Result<Records> {
+        records::get_records(ctx)
     }
 
-    pub fn consume_randomness(
-        ctx: Context<ConsumeRandomness>,
-        round_id: u32
-    ) -> Result<()> {
-        vrf::consume_randomness(ctx, round_id)
+    // Only authority
+    pub fn initialize_vault_ledger(ctx: Context<InitializeVaultLedger>, outstanding: u64) -> Result<()> {
+        solvency::initialize_vault_ledger(ctx, outstanding)
     }
 
-    pub fn claim_prize_sol(ctx: Context<ClaimPrizeSol>, round_id: u32) -> Result<()> {
+    // Permissionless, emits VaultInsolvent when sol_vault holds less than it owes
+    pub fn check_solvency(ctx: Context<CheckSolvency>) -> Result<()> {
+        solvency::check_solvency(ctx)
+    }
 
-        let sol_raffle = &mut ctx.accounts.sol_raffle;
-        let round = &mut ctx.accounts.round;
-        let vault = &mut ctx.accounts.vault;
-        
-        require!(
-            round.status == RoundStatus::Completed,
-            RaffleError::RoundNotCompleted
-        );
+    // Only authority
+    pub fn initialize_keeper_registry(ctx: Context<InitializeKeeperRegistry>) -> Result<()> {
+        keeper::initialize_keeper_registry(ctx)
+    }
+
+    // Only authority
+    pub fn set_keeper_allowlist_enabled(ctx: Context<UpdateRaffleState>, enabled: bool) -> Result<()> {
+        keeper::set_keeper_allowlist_enabled(ctx, enabled)
+    }
+
+    // Only authority
+    pub fn add_keeper(ctx: Context<UpdateKeeperRegistry>, keeper: Pubkey) -> Result<()> {
+        keeper::add_keeper(ctx, keeper)
+    }
+
+    // Only authority
+    pub fn remove_keeper(ctx: Context<UpdateKeeperRegistry>, keeper: Pubkey) -> Result<()> {
+        keeper::remove_keeper(ctx, keeper)
+    }
+
+    // Only authority
+    pub fn initialize_blocklist(ctx: Context<InitializeBlocklist>) -> Result<()> {
+        blocklist::initialize_blocklist(ctx)
+    }
+
+    // Only authority
+    pub fn set_blocklist_enabled(ctx: Context<UpdateRaffleState>, enabled: bool) -> Result<()> {
+        blocklist::set_blocklist_enabled(ctx, enabled)
+    }
+
+    // Only authority
+    pub fn block_address(ctx: Context<UpdateBlocklist>, address: Pubkey) -> Result<()> {
+        blocklist::block_address(ctx, address)
+    }
+
+    // Only authority
+    pub fn unblock_address(ctx: Context<UpdateBlocklist>, address: Pubkey) -> Result<()> {
+        blocklist::unblock_address(ctx, address)
+    }
+
+    pub fn fund_tuktuk_vault(ctx: Context<FundTukTukVault>, amount: u64) -> Result<()> {
+        tuktuk::fund_tuktuk_vault(ctx, amount)
+    }
+
+    // Only authority
+    pub fn withdraw_tuktuk_vault(ctx: Context<WithdrawTukTukVault>, amount: u64) -> Result<()> {
+        tuktuk::withdraw_tuktuk_vault(ctx, amount)
+    }
+
+    // Only authority
+    pub fn register_tuktuk_task(ctx: Context<RegisterTukTukTask>, args: TukTukTaskArgs) -> Result<()> {
+        tuktuk::register_tuktuk_task(ctx, args)
+    }
+
+    // Only authority
+    pub fn refresh_tuktuk_task(ctx: Context<RefreshTukTukTask>, args: TukTukTaskArgs) -> Result<()> {
+        tuktuk::refresh_tuktuk_task(ctx, args)
+    }
+
+    pub fn claim_prize_sol(ctx: Context<ClaimPrizeSol>, _round_id: u32, memo: Option<String>) -> Result<()> {
         require!(
-            round.winner_address == Some(ctx.accounts.winner.key()),
+            ctx.accounts.round.winner_address == Some(ctx.accounts.winner.key()),
             RaffleError::NotTheWinner
         );
-        require!(!round.prize_claimed, RaffleError::PrizeAlreadyClaimed);
+        require_not_blocked(&ctx.accounts.raffle_state, ctx.accounts.blocklist.as_ref(), &ctx.accounts.winner.key())?;
 
-        let prize_amount = round.prize_amount;
-        let commission_amount = round.commission_balance;
-        let total_required = prize_amount
-            .checked_add(commission_amount)
-            .ok_or(ProgramError::ArithmeticOverflow)?;
-        msg!("prize_amount: {}", prize_amount);
-        msg!("commission_amount: {}", commission_amount);
-        msg!("total_required: {}", total_required);
+        // Cold wallets and multisigs route the payout to a recipient, the signer stays the winner
+        let recipient = match ctx.accounts.recipient.as_ref() {
+            Some(recipient) => recipient.to_account_info(),
+            None => ctx.accounts.winner.to_account_info(),
+        };
+        require_not_blocked(&ctx.accounts.raffle_state, ctx.accounts.blocklist.as_ref(), &recipient.key())?;
+
+        pay_out_prize(
+            &ctx.accounts.raffle_state,
+            &mut ctx.accounts.round,
+            ctx.accounts.winner.key(),
+            &recipient,
+            SettlementAccounts {
+                vault: &ctx.accounts.vault,
+                vault_ledger: &ctx.accounts.vault_ledger,
+                beneficiary: &ctx.accounts.beneficiary,
+                insurance_vault: &ctx.accounts.insurance_vault,
+            },
+        )?;
+        record_payout(
+            PayoutStats {
+                raffle_stats: &mut ctx.accounts.raffle_stats,
+                raffle_stats_bump: ctx.bumps.raffle_stats,
+                daily_stats: &mut ctx.accounts.daily_stats,
+                daily_stats_bump: ctx.bumps.daily_stats,
+                winner_history: &ctx.accounts.winner_history,
+            },
+            &ctx.accounts.round,
+            ctx.accounts.round.prize_amount,
+            true,
+        )?;
 
-        let vault_balance = **vault.lamports.borrow();
-        require!(
-            vault_balance >= total_required,
-            RaffleError::InsufficientVaultBalance
-        );
-        
-        let winner_balance_before = **ctx.accounts.winner.lamports.borrow();
-        let beneficiary_balance_before = **ctx.accounts.beneficiary.lamports.borrow();
-        msg!(
-            "Winner balance before: {}",
-            winner_balance_before
-        );
-        msg!(
-            "Beneficiary balance before: {}",
-            beneficiary_balance_before
-        );
+        attach_memo(ctx.accounts.memo_program.as_ref(), memo)
+    }
 
-        // Transfer prize to winner
-        **ctx.accounts.vault.try_borrow_mut_lamports()? -= prize_amount;
-        **ctx.accounts.winner.try_borrow_mut_lamports()? += prize_amount;
-        
-        msg!("✓ Transferred {} lamports to winner", prize_amount);
-        
-        // Transfer commission to beneficiary
-        **ctx.accounts.vault.try_borrow_mut_lamports()? -= commission_amount;
-        **ctx.accounts.beneficiary.try_borrow_mut_lamports()? += commission_amount;
-        
-        msg!("✓ Transferred {} lamports to beneficiary", commission_amount);
-        
-        let winner_balance_after = **ctx.accounts.winner.lamports.borrow();
-        let beneficiary_balance_after = **ctx.accounts.beneficiary.lamports.borrow();
-        
-        msg!("=== Transfer Completed ===");
-        msg!(
-            "Winner balance after: {} lamports (delta: +{})",
-            winner_balance_after,
-            prize_amount
-        );
-        msg!(
-            "Beneficiary balance after: {} lamports (delta: +{})",
-            beneficiary_balance_after,
-            commission_amount
-        );
+    pub fn set_claim_delegate(ctx: Context<SetClaimDelegate>, delegate: Pubkey) -> Result<()> {
+        delegate::set_claim_delegate(ctx, delegate)
+    }
 
-        round.prize_claimed = true;
-        emit!(PrizeClaimed {
-            round_id: round.round_id,
-            winner: ctx.accounts.winner.key(),
-        });
+    pub fn claim_prize_as_delegate(
+        ctx: Context<ClaimPrizeAsDelegate>,
+        round_id: u32,
+        memo: Option<String>,
+    ) -> Result<()> {
+        delegate::claim_prize_as_delegate(ctx, round_id, memo)
+    }
 
-        Ok(())
+    // remaining_accounts: the fee discount pair, if any, then the basket and fallback feeds
+    pub fn roll_prize_into_tickets<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RollPrizeIntoTickets<'info>>,
+        round_id: u32,
+        target_round_id: u32,
+        purchase_index: u32,
+        count: u32,
+        access_proof: Vec<[u8; 32]>, // empty unless the target round is gated
+    ) -> Result<()> {
+        compound::roll_prize_into_tickets(ctx, round_id, target_round_id, purchase_index, count, access_proof)
+    }
+
+    pub fn boost_prize(ctx: Context<BoostPrize>, round_id: u32, amount: u64) -> Result<()> {
+        sponsor::boost_prize(ctx, round_id, amount)
+    }
+
+    // Only authority or operator, with the sponsor
+    pub fn create_sponsor_match(
+        ctx: Context<CreateSponsorMatch>,
+        round_id: u32,
+        ratio_bps: u16,
+        cap: u64,
+    ) -> Result<()> {
+        sponsor::create_sponsor_match(ctx, round_id, ratio_bps, cap)
+    }
+
+    pub fn close_sponsor_match(ctx: Context<CloseSponsorMatch>, round_id: u32) -> Result<()> {
+        sponsor::close_sponsor_match(ctx, round_id)
+    }
+
+    pub fn set_operator(ctx: Context<UpdateRaffleState>, operator: Pubkey) -> Result<()> {
+        promo::set_operator(ctx, operator)
+    }
+
+    pub fn fund_promo_vault(ctx: Context<FundPromoVault>, amount: u64) -> Result<()> {
+        promo::fund_promo_vault(ctx, amount)
+    }
+
+    // Only authority
+    pub fn withdraw_promo_vault(ctx: Context<WithdrawPromoVault>, amount: u64) -> Result<()> {
+        promo::withdraw_promo_vault(ctx, amount)
+    }
+
+    pub fn grant_promo_tickets(
+        ctx: Context<GrantPromoTickets>,
+        round_id: u32,
+        purchase_index: u32,
+        player: Pubkey,
+        count: u32,
+        access_proof: Vec<[u8; 32]>, // of `player`, empty unless the round is gated
+    ) -> Result<()> {
+        promo::grant_promo_tickets(ctx, round_id, purchase_index, player, count, access_proof)
+    }
+
+    pub fn open_free_entry_round(
+        ctx: Context<OpenFreeEntryRound>,
+        round_id: u32,
+        prize_amount: u64,
+        max_tickets_per_wallet: u32,
+    ) -> Result<()> {
+        promo::open_free_entry_round(ctx, round_id, prize_amount, max_tickets_per_wallet)
+    }
+
+    pub fn enter_free_round(
+        ctx: Context<EnterFreeRound>,
+        round_id: u32,
+        purchase_index: u32,
+        count: u32,
+        access_proof: Vec<[u8; 32]>, // empty unless the round is gated
+    ) -> Result<()> {
+        promo::enter_free_round(ctx, round_id, purchase_index, count, access_proof)
+    }
+
+    // Only authority
+    pub fn set_entrance_fee(ctx: Context<UpdateRaffleState>, entrance_fee_percentage: u8) -> Result<()> {
+        governance::set_entrance_fee(ctx, entrance_fee_percentage)
+    }
+
+    // Only authority
+    pub fn set_beneficiary(ctx: Context<UpdateRaffleState>, beneficiary: Pubkey) -> Result<()> {
+        governance::set_beneficiary(ctx, beneficiary)
+    }
+
+    // Only authority
+    pub fn propose_authority(ctx: Context<UpdateRaffleState>, new_authority: Pubkey) -> Result<()> {
+        governance::propose_authority(ctx, new_authority)
+    }
+
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        governance::accept_authority(ctx)
+    }
+
+    // Only authority. Applies starting from the next round
+    pub fn set_round_duration(ctx: Context<UpdateTokenRaffle>, round_duration: i64) -> Result<()> {
+        admin::set_round_duration(ctx, round_duration)
+    }
+
+    // Only authority
+    pub fn schedule_maintenance(ctx: Context<UpdateRaffleState>, start: i64, end: i64) -> Result<()> {
+        schedule::schedule_maintenance(ctx, start, end)
+    }
+
+    // Only authority
+    pub fn set_watchtower(ctx: Context<UpdateRaffleState>, watchtower: Pubkey) -> Result<()> {
+        watchtower::set_watchtower(ctx, watchtower)
+    }
+
+    // Watchtower or authority
+    pub fn freeze_round(ctx: Context<FreezeRound>, round_id: u32) -> Result<()> {
+        watchtower::freeze_round(ctx, round_id)
+    }
+
+    // Only authority
+    pub fn unfreeze_round(ctx: Context<FreezeRound>, round_id: u32) -> Result<()> {
+        watchtower::unfreeze_round(ctx, round_id)
+    }
+
+    // Only authority
+    pub fn set_price_circuit_breaker(ctx: Context<UpdateRaffleState>, max_deviation_bps: u16) -> Result<()> {
+        price_feeds::set_price_circuit_breaker(ctx, max_deviation_bps)
+    }
+
+    // Only authority
+    pub fn set_price_confidence(ctx: Context<UpdateRaffleState>, max_confidence_bps: u16) -> Result<()> {
+        price_feeds::set_price_confidence(ctx, max_confidence_bps)
+    }
+
+    // Only authority. remaining_accounts: the feed of every peg component, in order
+    pub fn set_ticket_peg(ctx: Context<SetTicketPeg>, components: Vec<PegComponent>) -> Result<()> {
+        price_feeds::set_ticket_peg(ctx, components)
+    }
+
+    // Only authority
+    pub fn set_ticket_price_satoshis(ctx: Context<UpdateRaffleState>, ticket_satoshis: u64) -> Result<()> {
+        price_feeds::set_ticket_price_satoshis(ctx, ticket_satoshis)
+    }
+
+    // Only authority. Stops purchases in this raffle only
+    pub fn set_sales_paused(ctx: Context<UpdateTokenRaffle>, paused: bool) -> Result<()> {
+        admin::set_sales_paused(ctx, paused)
+    }
+
+    // Only authority
+    pub fn set_mega_round_config(
+        ctx: Context<UpdateTokenRaffle>,
+        weekday: Option<u8>,
+        fee_percentage: u8,
+        duration: i64,
+        extra_jackpot_bps: u16,
+    ) -> Result<()> {
+        schedule::set_mega_round_config(ctx, weekday, fee_percentage, duration, extra_jackpot_bps)
+    }
+
+    // Only authority
+    pub fn set_streak_rewards(
+        ctx: Context<UpdateTokenRaffle>,
+        every: u32,
+        bonus_tickets: u32,
+    ) -> Result<()> {
+        player_stats::set_streak_rewards(ctx, every, bonus_tickets)
+    }
+
+    // Only authority
+    pub fn set_first_ticket_bonus(
+        ctx: Context<UpdateTokenRaffle>,
+        enabled: bool,
+        bonus_tickets: u32,
+        every: u32,
+    ) -> Result<()> {
+        admin::set_first_ticket_bonus(ctx, enabled, bonus_tickets, every)
+    }
+
+    // Only authority
+    pub fn set_volume_bonus(
+        ctx: Context<UpdateTokenRaffle>,
+        per_tickets: u32,
+        bonus_tickets: u32,
+    ) -> Result<()> {
+        admin::set_volume_bonus(ctx, per_tickets, bonus_tickets)
+    }
+
+    // Only authority. Takes effect from the next round
+    pub fn set_pricing_mode(
+        ctx: Context<SetPricingMode>,
+        mode: PricingMode,
+        fixed_ticket_price_usd: u64,
+    ) -> Result<()> {
+        price_feeds::set_pricing_mode(ctx, mode, fixed_ticket_price_usd)
+    }
+
+    // Only authority. Takes effect from the next round
+    pub fn set_commit_round_price(ctx: Context<UpdateTokenRaffle>, enabled: bool) -> Result<()> {
+        price_feeds::set_commit_round_price(ctx, enabled)
+    }
+
+    // Only authority. Takes effect from the next round
+    pub fn set_price_sampling(ctx: Context<UpdateTokenRaffle>, mode: PriceSampleMode, count: u8) -> Result<()> {
+        price_feeds::set_price_sampling(ctx, mode, count)
+    }
+
+    // Only authority. remaining_accounts: the feed of every peg component, pegged raffles on
+    // mainnet only
+    pub fn set_price_decay(
+        ctx: Context<SetPriceDecay>,
+        mode: PriceDecayMode,
+        start_bps: u16,
+        end_bps: u16,
+        steps: u8,
+    ) -> Result<()> {
+        price_feeds::set_price_decay(ctx, mode, start_bps, end_bps, steps)
+    }
+
+    // Only authority
+    pub fn set_cancel_penalty(ctx: Context<UpdateTokenRaffle>, penalty_bps: Option<u16>) -> Result<()> {
+        cancellation::set_cancel_penalty(ctx, penalty_bps)
+    }
+
+    pub fn cancel_purchase(ctx: Context<CancelPurchase>, round_id: u32, purchase_index: u32) -> Result<()> {
+        cancellation::cancel_purchase(ctx, round_id, purchase_index)
+    }
+
+    pub fn cancel_round(ctx: Context<CancelRound>, round_id: u32) -> Result<()> {
+        cancellation::cancel_round(ctx, round_id)
+    }
+
+    pub fn claim_refunds_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimRefundsBatch<'info>>,
+    ) -> Result<()> {
+        cancellation::claim_refunds_batch(ctx)
+    }
+
+    pub fn transfer_purchase(
+        ctx: Context<TransferPurchase>,
+        round_id: u32,
+        purchase_index: u32,
+        new_owner: Pubkey,
+        access_proof: Vec<[u8; 32]>, // of `new_owner`, empty unless the round is gated
+    ) -> Result<()> {
+        ownership::transfer_purchase(ctx, round_id, purchase_index, new_owner, access_proof)
+    }
+
+    // Only authority
+    pub fn set_loyalty_config(
+        ctx: Context<UpdateTokenRaffle>,
+        points_per_sol: u64,
+        points_per_ticket: u64,
+    ) -> Result<()> {
+        player_stats::set_loyalty_config(ctx, points_per_sol, points_per_ticket)
+    }
+
+    pub fn fund_rewards_vault(ctx: Context<FundRewardsVault>, amount: u64) -> Result<()> {
+        player_stats::fund_rewards_vault(ctx, amount)
+    }
+
+    pub fn redeem_points_for_tickets(
+        ctx: Context<RedeemPointsForTickets>,
+        round_id: u32,
+        purchase_index: u32,
+        count: u32,
+        access_proof: Vec<[u8; 32]>, // empty unless the round is gated
+    ) -> Result<()> {
+        player_stats::redeem_points_for_tickets(ctx, round_id, purchase_index, count, access_proof)
+    }
+
+    pub fn initialize_staking(ctx: Context<InitializeStaking>) -> Result<()> {
+        staking::initialize_staking(ctx)
+    }
+
+    pub fn set_staking_mode(
+        ctx: Context<UpdateTokenRaffle>,
+        enabled: bool,
+        yield_to_insurance: bool,
+    ) -> Result<()> {
+        staking::set_staking_mode(ctx, enabled, yield_to_insurance)
+    }
+
+    pub fn set_yield_sweep_interval(ctx: Context<SetYieldSweepInterval>, interval_seconds: i64) -> Result<()> {
+        staking::set_yield_sweep_interval(ctx, interval_seconds)
+    }
+
+    pub fn sweep_staking_yield(ctx: Context<SweepStakingYield>) -> Result<()> {
+        staking::sweep_staking_yield(ctx)
+    }
+
+    pub fn stake_prize_pool(ctx: Context<StakePrizePool>, round_id: u32) -> Result<()> {
+        staking::stake_prize_pool(ctx, round_id)
+    }
+
+    pub fn unstake_prize_pool(ctx: Context<UnstakePrizePool>, round_id: u32) -> Result<()> {
+        staking::unstake_prize_pool(ctx, round_id)
+    }
+
+    pub fn initialize_treasury(ctx: Context<InitializeTreasury>, treasurer: Pubkey) -> Result<()> {
+        treasury::initialize_treasury(ctx, treasurer)
+    }
+
+    pub fn set_treasurer(ctx: Context<SetTreasurer>, treasurer: Pubkey) -> Result<()> {
+        treasury::set_treasurer(ctx, treasurer)
+    }
+
+    pub fn deposit_treasury(ctx: Context<DepositTreasury>, amount: u64) -> Result<()> {
+        treasury::deposit_treasury(ctx, amount)
+    }
+
+    pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, pool_tokens: u64) -> Result<()> {
+        treasury::withdraw_treasury(ctx, pool_tokens)
+    }
+
+    pub fn initialize_commission_vault(ctx: Context<InitializeCommissionVault>) -> Result<()> {
+        commission::initialize_commission_vault(ctx)
+    }
+
+    // Only authority
+    pub fn set_raffle_access_list(ctx: Context<UpdateTokenRaffle>, access_root: Option<[u8; 32]>) -> Result<()> {
+        access_list::set_raffle_access_list(ctx, access_root)
+    }
+
+    // Only authority
+    pub fn set_round_access_list(
+        ctx: Context<SetRoundAccessList>,
+        round_id: u32,
+        access_root: Option<[u8; 32]>,
+    ) -> Result<()> {
+        access_list::set_round_access_list(ctx, round_id, access_root)
+    }
+
+    // Only authority
+    pub fn set_max_purchases_per_wallet(ctx: Context<UpdateTokenRaffle>, max_purchases: u32) -> Result<()> {
+        purchase_limits::set_max_purchases_per_wallet(ctx, max_purchases)
+    }
+
+    pub fn close_wallet_purchases(ctx: Context<CloseWalletPurchases>, round_id: u32) -> Result<()> {
+        purchase_limits::close_wallet_purchases(ctx, round_id)
+    }
+
+    // Only authority
+    pub fn set_min_purchase(ctx: Context<UpdateTokenRaffle>, min_tickets: u32, min_lamports: u64) -> Result<()> {
+        purchase_limits::set_min_purchase(ctx, min_tickets, min_lamports)
+    }
+
+    // Only authority
+    pub fn set_prize_target(ctx: Context<UpdateTokenRaffle>, prize_target_lamports: Option<u64>) -> Result<()> {
+        schedule::set_prize_target(ctx, prize_target_lamports)
+    }
+
+    // Only authority
+    pub fn set_prize_cap(ctx: Context<UpdateTokenRaffle>, prize_cap_lamports: Option<u64>) -> Result<()> {
+        prize_limits::set_prize_cap(ctx, prize_cap_lamports)
+    }
+
+    // Only authority
+    pub fn set_min_prize(ctx: Context<UpdateTokenRaffle>, min_prize_lamports: u64) -> Result<()> {
+        prize_limits::set_min_prize(ctx, min_prize_lamports)
+    }
+
+    // Only authority
+    pub fn set_house_ticket(ctx: Context<UpdateTokenRaffle>, threshold: u32, house_tickets: u32) -> Result<()> {
+        house_ticket::set_house_ticket(ctx, threshold, house_tickets)
+    }
+
+    // Only authority
+    pub fn set_redraw_after(ctx: Context<UpdateTokenRaffle>, redraw_after_seconds: i64) -> Result<()> {
+        redraw::set_redraw_after(ctx, redraw_after_seconds)
+    }
+
+    // Only authority
+    pub fn set_attestation_requirement(
+        ctx: Context<UpdateTokenRaffle>,
+        required: bool,
+        credential: Pubkey,
+        schema: Pubkey,
+    ) -> Result<()> {
+        attestation::set_attestation_requirement(ctx, required, credential, schema)
+    }
+
+    // Only authority
+    pub fn initialize_fee_discount(
+        ctx: Context<InitializeFeeDiscount>,
+        min_stake: u64,
+        discount_bps: u16,
+    ) -> Result<()> {
+        fee_discount::initialize_fee_discount(ctx, min_stake, discount_bps)
+    }
+
+    // Only authority
+    pub fn set_fee_discount(ctx: Context<SetFeeDiscount>, min_stake: u64, discount_bps: u16) -> Result<()> {
+        fee_discount::set_fee_discount(ctx, min_stake, discount_bps)
+    }
+
+    pub fn stake_governance_tokens(ctx: Context<StakeGovernanceTokens>, amount: u64) -> Result<()> {
+        fee_discount::stake_governance_tokens(ctx, amount)
+    }
+
+    pub fn unstake_governance_tokens(ctx: Context<UnstakeGovernanceTokens>, amount: u64) -> Result<()> {
+        fee_discount::unstake_governance_tokens(ctx, amount)
+    }
+
+    // Only authority
+    pub fn set_fee_tiers(ctx: Context<UpdateTokenRaffle>, tiers: Vec<FeeTier>) -> Result<()> {
+        commission::set_fee_tiers(ctx, tiers)
+    }
+
+    pub fn set_commission_mode(ctx: Context<UpdateTokenRaffle>, mode: CommissionMode) -> Result<()> {
+        commission::set_commission_mode(ctx, mode)
+    }
+
+    pub fn set_commission_withdrawal_cap(ctx: Context<SetCommissionWithdrawalCap>, daily_cap: u64) -> Result<()> {
+        commission::set_commission_withdrawal_cap(ctx, daily_cap)
+    }
+
+    pub fn withdraw_commission(ctx: Context<WithdrawCommission>, amount: u64) -> Result<()> {
+        commission::withdraw_commission(ctx, amount)
+    }
+
+    pub fn set_insurance_bps(ctx: Context<UpdateRaffleState>, insurance_bps: u16) -> Result<()> {
+        insurance::set_insurance_bps(ctx, insurance_bps)
+    }
+
+    pub fn fund_insurance_vault(ctx: Context<FundInsuranceVault>, amount: u64) -> Result<()> {
+        insurance::fund_insurance_vault(ctx, amount)
+    }
+
+    // Only authority
+    pub fn insurance_payout(ctx: Context<InsurancePayout>, amount: u64, reason: u8) -> Result<()> {
+        insurance::insurance_payout(ctx, amount, reason)
+    }
+
+    pub fn initialize_mega_jackpot(ctx: Context<InitializeMegaJackpot>) -> Result<()> {
+        jackpot::initialize_mega_jackpot(ctx)
+    }
+
+    // Only authority
+    pub fn set_jackpot_config(ctx: Context<SetJackpotConfig>, jackpot_bps: u16, odds: u32) -> Result<()> {
+        jackpot::set_jackpot_config(ctx, jackpot_bps, odds)
+    }
+
+    pub fn set_vesting_config(
+        ctx: Context<UpdateRaffleState>,
+        threshold: u64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        vesting::set_vesting_config(ctx, threshold, cliff_seconds, duration_seconds)
+    }
+
+    pub fn start_vesting(ctx: Context<StartVesting>, round_id: u32) -> Result<()> {
+        vesting::start_vesting(ctx, round_id)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>, round_id: u32) -> Result<()> {
+        vesting::claim_vested(ctx, round_id)
     }
 
     pub fn set_winner_address(
         ctx: Context<SetWinnerAddress>,
         round_id: u32,
-        purchase_index: u32,
+        _purchase_index: u32,
     ) -> Result<()> {
         let round = &mut ctx.accounts.round;
         let round_tickets_purchase = &ctx.accounts.round_tickets_purchase;
@@ -778,6 +1671,7 @@ pub mod raffle {
         );
 
         round.winner_address = Some(round_tickets_purchase.player);
+        record_prize(&ctx.accounts.records, round)?;
 
         msg!(
             "✅ Winner address set for round {}: {}",
@@ -785,14 +1679,390 @@ pub mod raffle {
             round_tickets_purchase.player
         );
 
+        record_audit(
+            &ctx.accounts.audit_log,
+            ctx.accounts.authority.key(),
+            AuditAction::WinnerAddressSet,
+            round_id as u64,
+        )?;
+
         Ok(())
     }
 }
 
-fn determine_round_to_process(sol_raffle: &Account<TokenRaffle>) -> Result<u32> {
-    if !sol_raffle.pending_rounds.is_empty() {
-        let round_id = sol_raffle.pending_rounds[0];
-        msg!("✅ Taking round {} from pending_rounds", round_id);
+// Vault the prize leaves from and where its commission goes
+pub struct SettlementAccounts<'a, 'info> {
+    pub vault: &'a AccountInfo<'info>,
+    pub vault_ledger: &'a AccountInfo<'info>,
+    pub beneficiary: &'a AccountInfo<'info>,
+    pub insurance_vault: &'a AccountInfo<'info>,
+}
+
+// Pays a Completed round's prize to `recipient` and settles its commission. The caller
+// checks who may claim for `winner`
+fn pay_out_prize<'info>(
+    raffle_state: &RaffleState,
+    round: &mut Round,
+    winner: Pubkey,
+    recipient: &AccountInfo<'info>,
+    accounts: SettlementAccounts<'_, 'info>,
+) -> Result<()> {
+    let SettlementAccounts {
+        vault,
+        vault_ledger,
+        beneficiary,
+        insurance_vault,
+    } = accounts;
+
+    require!(
+        round.status == RoundStatus::Completed,
+        RaffleError::RoundNotCompleted
+    );
+    require!(!round.prize_claimed, RaffleError::PrizeAlreadyClaimed);
+    require!(!round.frozen, RaffleError::RoundFrozen);
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+    round.check_version()?;
+    require!(
+        !raffle_state.is_vested_prize(round.prize_amount),
+        RaffleError::PrizeSubjectToVesting
+    );
+
+    let prize_amount = round.prize_amount;
+    let commission_amount = round.commission_balance;
+    let total_required = prize_amount
+        .checked_add(commission_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    msg!("prize_amount: {}", prize_amount);
+    msg!("commission_amount: {}", commission_amount);
+    msg!("total_required: {}", total_required);
+
+    let vault_balance = vault.lamports();
+    require!(
+        vault_balance >= total_required,
+        RaffleError::InsufficientVaultBalance
+    );
+    require_solvent(vault_ledger, vault, round.round_id)?;
+
+    let recipient_balance_before = recipient.lamports();
+    let beneficiary_balance_before = beneficiary.lamports();
+    msg!(
+        "Recipient balance before: {}",
+        recipient_balance_before
+    );
+    msg!(
+        "Beneficiary balance before: {}",
+        beneficiary_balance_before
+    );
+
+    // Transfer prize to recipient
+    **vault.try_borrow_mut_lamports()? -= prize_amount;
+    **recipient.try_borrow_mut_lamports()? += prize_amount;
+    release_in_ledger_account(vault_ledger, prize_amount)?;
+
+    msg!("✓ Transferred {} lamports to {}", prize_amount, recipient.key());
+
+    // Transfer commission to beneficiary and insurance
+    let beneficiary_amount = settle_sol_commission(
+        raffle_state,
+        vault,
+        vault_ledger,
+        beneficiary,
+        insurance_vault,
+        round.round_id,
+        commission_amount,
+    )?;
+
+    let recipient_balance_after = recipient.lamports();
+    let beneficiary_balance_after = beneficiary.lamports();
+
+    msg!("=== Transfer Completed ===");
+    msg!(
+        "Recipient balance after: {} lamports (delta: +{})",
+        recipient_balance_after,
+        prize_amount
+    );
+    msg!(
+        "Beneficiary balance after: {} lamports (delta: +{})",
+        beneficiary_balance_after,
+        beneficiary_amount
+    );
+
+    round.prize_claimed = true;
+    round.commission_settled = true;
+    emit!(PrizeClaimed {
+        round_id: round.round_id,
+        winner,
+        recipient: recipient.key(),
+        prize_amount,
+        commission_amount,
+        beneficiary: beneficiary.key(),
+        pushed: false,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Labels the payout transaction for exchanges and accounting tools, e.g. "PotJack round #123 prize"
+fn attach_memo<'info>(memo_program: Option<&Program<'info, Memo>>, memo: Option<String>) -> Result<()> {
+    let Some(memo) = memo else {
+        return Ok(());
+    };
+    require!(memo.len() <= MAX_MEMO_LEN, RaffleError::MemoTooLong);
+    let memo_program = memo_program.ok_or(RaffleError::MissingMemoProgram)?;
+
+    memo::build_memo(
+        CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+        memo.as_bytes(),
+    )
+}
+
+// Pays the keeper tip and the min prize top-up of a round being requested
+pub struct RequestFundingAccounts<'a, 'info> {
+    pub vault: &'a AccountInfo<'info>,
+    pub vault_ledger: &'a AccountInfo<'info>,
+    pub keeper: &'a AccountInfo<'info>,
+    pub insurance_vault: Option<&'a AccountInfo<'info>>,
+    pub mega_jackpot: Option<&'a mut Account<'info, MegaJackpot>>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+// Rate limit, keeper tip once per round and the move to Drawing. A round without tickets expires
+// instead, returns false when there is nothing to draw. The round is passed unchecked as it is picked from the queue
+fn prepare_round_request<'info>(
+    raffle_state: &RaffleState,
+    sol_raffle: &mut TokenRaffle,
+    round_info: &AccountInfo<'info>,
+    accounts: RequestFundingAccounts<'_, 'info>,
+    now: i64,
+) -> Result<bool> {
+    let RequestFundingAccounts {
+        vault,
+        vault_ledger,
+        keeper,
+        insurance_vault,
+        mega_jackpot,
+        system_program,
+    } = accounts;
+
+    let mut round_data = round_info.try_borrow_mut_data()?;
+    let mut round = Round::try_deserialize(&mut &round_data[..])?;
+
+    let draw = round.total_tickets > 0;
+    if draw {
+        record_randomness_request(raffle_state, &mut round, now)?;
+
+        if !round.request_tip_paid {
+            pay_keeper_tip(raffle_state, &mut round, vault, vault_ledger, keeper)?;
+            round.request_tip_paid = true;
+        }
+
+        if round.status == RoundStatus::Open {
+            lock_round_totals(sol_raffle, &mut round, now)?;
+        }
+        if round.status == RoundStatus::Locked {
+            transition_round(sol_raffle, &mut round, RoundStatus::Drawing)?;
+            add_house_ticket(sol_raffle, &mut round, raffle_state.beneficiary);
+            top_up_min_prize(&mut round, vault, vault_ledger, insurance_vault, mega_jackpot, system_program)?;
+        }
+    } else {
+        msg!("Round {} has no tickets, expiring", round.round_id);
+        transition_round(sol_raffle, &mut round, RoundStatus::Expired)?;
+
+        pass_on_carryover(sol_raffle, round.carryover_in)?;
+        round.prize_amount -= round.carryover_in;
+        round.carryover_in = 0;
+    }
+
+    let mut writer = &mut round_data[..];
+    round.try_serialize(&mut writer)?;
+
+    Ok(draw)
+}
+
+pub struct VrfRequestAccounts<'info> {
+    pub pending_queue: AccountInfo<'info>,
+    pub pending_queue_bump: u8,
+    pub vrf: AccountInfo<'info>,
+    pub vrf_fee_vault: AccountInfo<'info>,
+    pub vrf_fee_vault_bump: u8,
+    pub client_state: AccountInfo<'info>,
+    pub client_state_bump: u8,
+    pub client: AccountInfo<'info>,
+    pub network_state: AccountInfo<'info>,
+    pub treasury: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+// Sends the ORAO request with consume_randomness as callback, shared by request_randomness and tick
+fn submit_vrf_request<'info>(
+    raffle_state: &mut RaffleState,
+    cluster_profile: Option<&ClusterProfile>,
+    sol_raffle: &Account<'info, TokenRaffle>,
+    round_id: u32,
+    accounts: VrfRequestAccounts<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    seeds: &[[u8; 32]],
+) -> Result<()> {
+    if let Some(cluster_profile) = require_cluster_profile(raffle_state, cluster_profile)? {
+        cluster_profile.check_vrf(&accounts.vrf, &accounts.treasury)?;
+    }
+
+    //Checking for available request_accounts to use
+    let idx = raffle_state.vrf_request_counter as usize;
+    msg!("idx: {}", idx);
+    msg!("seeds.len(): {}", seeds.len());
+    require!(idx < seeds.len(), RaffleError::AllRequestsCompleted);
+
+    // Signature for both PDA: client_state and vrf_fee_vault
+    let signers_seeds: &[&[&[u8]]] = &[
+        &[CLIENT_STATE_SEED, &[accounts.client_state_bump]],
+        &[b"vrf_fee_vault", &[accounts.vrf_fee_vault_bump]],
+    ];
+
+    let request_account = remaining_accounts
+        .get(idx)
+        .ok_or(RaffleError::NotEnoughRemainingAccounts)?;
+
+    let seed = seeds
+        .get(idx)
+        .ok_or(RaffleError::NotEnoughSeeds)?;
+
+    msg!("Preparing VRF request seed={:?} request_account={}", seed, request_account.key);
+
+    let sol_raffle_key = sol_raffle.key();
+
+    let round_seeds: &[&[u8]] = &[
+        b"round",
+        sol_raffle_key.as_ref(),
+        &round_id.to_le_bytes(),
+    ];
+
+    let (round_pubkey, round_bump) =
+        Pubkey::find_program_address(round_seeds, &crate::id());
+
+    msg!("Computed round PDA = {} (round_id={})", round_pubkey, round_id);
+
+    // Prepare callback instruction data
+    let callback_ix = crate::instruction::ConsumeRandomness { round_id };
+
+    let mut callback = Callback::from_instruction_data(&callback_ix);
+    
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        sol_raffle_key,
+        vec![
+            b"sol_raffle".to_vec(),
+            vec![sol_raffle.cadence_id],
+            vec![sol_raffle.bump],
+        ],
+    ));
+    
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        round_pubkey,
+        vec![
+            b"round".to_vec(),
+            sol_raffle_key.as_ref().to_vec(),
+            round_id.to_le_bytes().to_vec(), 
+            vec![round_bump],
+        ],
+    ));
+
+    let round_tickets_seeds: &[&[u8]] = &[
+        b"round_tickets",
+        round_pubkey.as_ref(),
+    ];
+
+    let (round_tickets_pubkey, round_tickets_bump) =
+        Pubkey::find_program_address(round_tickets_seeds, &crate::id());
+
+    msg!("Computed round_tickets PDA = {}", round_tickets_pubkey);
+
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        round_tickets_pubkey,
+        vec![
+            b"round_tickets".to_vec(),
+            round_pubkey.as_ref().to_vec(),
+            vec![round_tickets_bump],
+        ],
+    ));
+
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        accounts.pending_queue.key(),
+        vec![
+            b"pending_queue".to_vec(),
+            sol_raffle_key.as_ref().to_vec(),
+            vec![accounts.pending_queue_bump],
+        ],
+    ));
+
+    // Accounts for the mega jackpot secondary draw
+    let (mega_jackpot_pubkey, mega_jackpot_bump) =
+        Pubkey::find_program_address(&[b"mega_jackpot"], &crate::id());
+    let (sol_vault_pubkey, sol_vault_bump) =
+        Pubkey::find_program_address(&[b"sol_vault"], &crate::id());
+
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        mega_jackpot_pubkey,
+        vec![b"mega_jackpot".to_vec(), vec![mega_jackpot_bump]],
+    ));
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        sol_vault_pubkey,
+        vec![b"sol_vault".to_vec(), vec![sol_vault_bump]],
+    ));
+
+    // A won jackpot becomes an obligation of sol_vault
+    let (vault_ledger_pubkey, vault_ledger_bump) =
+        Pubkey::find_program_address(&[b"vault_ledger"], &crate::id());
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        vault_ledger_pubkey,
+        vec![b"vault_ledger".to_vec(), vec![vault_ledger_bump]],
+    ));
+
+    // Receives the commission of CommissionMode::OnCompletion rounds
+    let (commission_vault_pubkey, commission_vault_bump) =
+        Pubkey::find_program_address(&[b"commission_vault"], &crate::id());
+    callback = callback.with_remaining_account(RemainingAccount::writable(
+        commission_vault_pubkey,
+        vec![b"commission_vault".to_vec(), vec![commission_vault_bump]],
+    ));
+
+    let mut cpi_accounts = orao_cpi::accounts::Request {
+        payer: accounts.vrf_fee_vault,
+        state: accounts.client_state,
+        client: accounts.client,
+        network_state: accounts.network_state,
+        treasury: accounts.treasury,
+        request: request_account.to_account_info(),
+        system_program: accounts.system_program,
+    };
+
+    // Both accounts must be signer
+    cpi_accounts.state.is_signer = true;
+    cpi_accounts.payer.is_signer = true;
+
+    let cpi_ctx = CpiContext::new(accounts.vrf, cpi_accounts).with_signer(signers_seeds);
+
+    orao_cpi::request(
+        cpi_ctx,
+        RequestParams::new(*seed).with_callback(Some(callback))
+    )?;
+
+    msg!("Submitted VRF request -> request_account={} round={}", request_account.key, round_pubkey);
+
+    raffle_state.vrf_request_counter += 1;
+    if seeds.len() == raffle_state.vrf_request_counter as usize {
+        raffle_state.vrf_request_counter = 0;
+
+        msg!("AllRequestsCompleted");
+        emit!(AllRequestsCompleted {});
+    }
+
+    Ok(())
+}
+
+fn determine_round_to_process(sol_raffle: &Account<TokenRaffle>, pending_queue: &PendingQueue) -> Result<u32> {
+    if let Some(round_id) = pending_queue.front() {
+        msg!("✅ Taking round {} from pending queue", round_id);
         return Ok(round_id);
     }
     
@@ -813,7 +2083,10 @@ fn determine_round_to_process(sol_raffle: &Account<TokenRaffle>) -> Result<u32>
     msg!("Current time: {}", clock.unix_timestamp);
     msg!("Current round status: {:?}", status);
     
-    require!(*status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require!(
+        matches!(status, RoundStatus::Open | RoundStatus::Locked),
+        RaffleError::RoundNotOpen
+    );
     require!(clock.unix_timestamp >= end_time, RaffleError::RoundNotEndedYet);
     
     Ok(current_id)
@@ -832,52 +2105,401 @@ fn initialize_round_tickets_purchase(
     round_tickets_purchase.purchase_index = purchases_count;
     round_tickets_purchase.tickets_count = tickets_count;
     round_tickets_purchase.bump = round_tickets_purchase_bump;
+    round_tickets_purchase.bonus_tickets = 0;
+    round_tickets_purchase.ticket_price = 0;
+    round_tickets_purchase.oracle_price = 0;
+    round_tickets_purchase.price_slot = 0;
+    round_tickets_purchase.fee_bps = 0;
+    round_tickets_purchase.cancelled = false;
+    round_tickets_purchase.jackpot_bps = Some(0);
+    round_tickets_purchase.loyalty_points = 0;
+    round_tickets_purchase.sponsor_matched = 0;
+    round_tickets_purchase.entry_streak = 0;
+    round_tickets_purchase.best_streak_raised = false;
     msg!(" Round tickets purchase initialized");
     Ok(())
 }
 
-fn get_next_ny_anchor_time(current_timestamp: i64) -> i64 {
-    let ny_time = current_timestamp - NY_OFFSET;
-    let ny_seconds_in_day = ny_time % SECONDS_IN_DAY;
-    let mut ny_today_anchor = ny_time - ny_seconds_in_day;
+pub struct RoundBumps {
+    pub round: u8,
+    pub round_tickets: u8,
+    pub rent_vault: u8,
+}
+
+pub struct RoundAccounts<'a, 'info> {
+    pub round: &'a AccountInfo<'info>,
+    pub round_tickets: &'a AccountInfo<'info>,
+    pub rent_vault: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+}
+
+// Creates and initializes Round + RoundTickets paid from rent_vault and makes it the current round
+pub fn create_round_accounts<'info>(
+    program_id: &Pubkey,
+    sol_raffle: &mut Account<'info, TokenRaffle>,
+    pending_queue: &mut PendingQueue,
+    accounts: RoundAccounts<'_, 'info>,
+    round_id: u32,
+    bumps: RoundBumps,
+    current_time: i64,
+) -> Result<()> {
+    let RoundAccounts {
+        round,
+        round_tickets,
+        rent_vault,
+        system_program,
+    } = accounts;
+
+    require_eq!(
+        round_id,
+        sol_raffle.next_round_id(),
+        RaffleError::RoundIdNotSequential
+    );
+
+    let rent = Rent::get()?;
+    let sol_raffle_key = sol_raffle.key();
+
+    let vault_bump = bumps.rent_vault;
+    let round_bump = bumps.round;
+    let round_tickets_bump = bumps.round_tickets;
+
+    let vault_seeds = &[b"rent_vault".as_ref(), &[vault_bump]];
+
+    // ========== Create Round account ==========
+    let round_seeds = &[
+        b"round".as_ref(),
+        sol_raffle_key.as_ref(),
+        &round_id.to_le_bytes(),
+        &[round_bump],
+    ];
+    let round_space = 8 + Round::INIT_SPACE;
+    let round_lamports = rent.minimum_balance(round_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &rent_vault.key(),
+            &round.key(),
+            round_lamports,
+            round_space as u64,
+            program_id,
+        ),
+        &[
+            rent_vault.to_account_info(),
+            round.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[vault_seeds, round_seeds],
+    )?;
+
+    let (round_end_time, mega_round) = next_round_schedule(sol_raffle, current_time);
+    let carryover = sol_raffle.carryover;
+
+    // Initialize Round
+    {
+        let mut round_account_data = round.try_borrow_mut_data()?;
+
+        let round_data = Round {
+            initialized: true,
+            token_raffle: sol_raffle_key,
+            round_id,
+            status: RoundStatus::Open,
+            start_time: current_time,
+            end_time: round_end_time,
+            prize_amount: carryover,
+            commission_balance: 0,
+            purchases_count: 0,
+            total_tickets: 0,
+            winner_ticket_index: None,
+            winner_purchase_index: None,
+            winner_address: None,
+            prize_claimed: false,
+            sponsored_amount: 0,
+            free_entry: false,
+            free_entry_max_tickets: 0,
+            commission_settled: false,
+            jackpot_amount: 0,
+            mega_round,
+            open_ticket_price: 0,
+            open_sol_price_usd: 0,
+            open_reference_price_usd: 0,
+            open_price_slot: 0,
+            commit_price: sol_raffle.commit_round_price,
+            ticket_price_lamports: 0,
+            price_sample_mode: sol_raffle.price_sample_mode.clone(),
+            price_sample_count: sol_raffle.price_sample_count,
+            frozen: false,
+            request_tip_paid: false,
+            last_request_time: 0,
+            request_count: 0,
+            jackpot_contributed: 0,
+            keeper_tips_paid: 0,
+            refunded_amount: 0,
+            commission_mode: sol_raffle.commission_mode.clone(),
+            commission_accrued: 0,
+            staked_lamports: 0,
+            stake_pool_tokens: 0,
+            staking_yield: 0,
+            staking_loss: 0,
+            pricing_mode: sol_raffle.pricing_mode.clone(),
+            fixed_ticket_price_usd: sol_raffle.fixed_ticket_price_usd,
+            access_root: sol_raffle.access_root,
+            prize_target_lamports: sol_raffle.prize_target_lamports,
+            prize_cap_lamports: sol_raffle.prize_cap_lamports,
+            carryover_in: carryover,
+            carryover_out: 0,
+            min_prize_lamports: sol_raffle.min_prize_lamports,
+            prize_top_up: 0,
+            house_tickets: 0,
+            house_owner: Pubkey::default(),
+            completed_at: 0,
+            redraw_pending: false,
+            first_winner_purchase_index: None,
+            first_winner_ticket_index: None,
+            first_winner_address: None,
+            randomness: [0; 64],
+            vrf_request: Pubkey::default(),
+            locked_at: 0,
+            locked_total_tickets: 0,
+            locked_purchases_count: 0,
+            locked_prize_amount: 0,
+            sponsor_match: Pubkey::default(),
+            house_rolled: 0,
+            version: Round::VERSION,
+            bump: round_bump,
+        };
+
+        let mut writer = &mut round_account_data[..];
+        round_data.try_serialize(&mut writer)?;
+    }
+
+    // ========== Create RoundTickets account ==========
+    let round_key = round.key();
+    let round_tickets_seeds = &[
+        b"round_tickets".as_ref(),
+        round_key.as_ref(),
+        &[round_tickets_bump],
+    ];
+    let tickets_space = 8 + std::mem::size_of::<RoundTickets>();
+    let tickets_lamports = rent.minimum_balance(tickets_space);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            &rent_vault.key(),
+            &round_tickets.key(),
+            tickets_lamports,
+            tickets_space as u64,
+            program_id,
+        ),
+        &[
+            rent_vault.to_account_info(),
+            round_tickets.to_account_info(),
+            system_program.to_account_info(),
+        ],
+        &[vault_seeds, round_tickets_seeds],
+    )?;
+
+    // Initialize RoundTickets
+    {
+        let mut tickets_data = round_tickets.try_borrow_mut_data()?;
+
+        let discriminator = RoundTickets::DISCRIMINATOR;
+        tickets_data[0..8].copy_from_slice(discriminator);
+
+        for byte in &mut tickets_data[8..] {
+            *byte = 0;
+        }
 
-    ny_today_anchor += ROUND_DURATION;
+        tickets_data[8..40].copy_from_slice(round_key.as_ref());
 
-    if ny_time >= ny_today_anchor {
-        ny_today_anchor += ROUND_DURATION;
+        let bump_offset = 8 + 32 + (1024 * 4) + (1024 * 4) + 4;
+        tickets_data[bump_offset] = round_tickets_bump;
+        tickets_data[ROUND_TICKETS_VERSION_OFFSET] = RoundTickets::VERSION;
     }
 
-    ny_today_anchor + NY_OFFSET
+    // Update sol_raffle state
+    enqueue_expired_round(sol_raffle, pending_queue, current_time)?;
+
+    open_round(sol_raffle, round_id, round_end_time);
+    sol_raffle.carryover = 0;
+    sol_raffle.total_rounds = sol_raffle
+        .total_rounds
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("✅ Round {} created", round_id);
+
+    emit!(RoundCreated {
+        token: sol_raffle.token_mint,
+        cadence_id: sol_raffle.cadence_id,
+        round_id,
+        start_time: current_time,
+        end_time: round_end_time,
+        mega_round,
+        carryover,
+    });
+
+    Ok(())
+}
+
+pub struct PurchaseSplit {
+    pub commission_amount: u64,
+    pub prize_amount: u64, // what the round's prize grew by, jackpot part excluded
+    pub jackpot_bps: u16,
+    pub jackpot_amount: u64,
+    pub immediate_commission: u64, // owed to commission_vault now, CommissionMode::Immediate rounds
 }
 
-pub fn change_round_status(
-    sol_raffle: &mut Account<TokenRaffle>,
-    round: &mut Account<Round>,
-    new_status: RoundStatus,
-) -> Result<()> {
+// Splits a purchase cost between the commission, the mega jackpot and the round's prize and books
+// the round's side of it by the round's commission mode. The caller moves the lamports: the jackpot
+// part to mega_jackpot, the immediate commission to commission_vault, the rest stays in sol_vault
+pub fn split_purchase(
+    raffle_state: &RaffleState,
+    sol_raffle: &TokenRaffle,
+    round: &mut Round,
+    cost: u64,
+    fee_bps: u16,
+    mega_jackpot_passed: bool,
+) -> Result<PurchaseSplit> {
+    let (commission_amount, prize_amount) = split_commission(cost, fee_bps)?;
+
+    let jackpot_bps = round_jackpot_bps(raffle_state, sol_raffle, round);
+    let jackpot_amount = jackpot_contribution(jackpot_bps, cost)?;
+    require!(
+        jackpot_amount == 0 || mega_jackpot_passed,
+        RaffleError::MissingJackpotAccount
+    );
+    let prize_amount = prize_amount
+        .checked_sub(jackpot_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.jackpot_contributed = round
+        .jackpot_contributed
+        .checked_add(jackpot_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let immediate_commission = if round.commission_mode == CommissionMode::Immediate {
+        commission_amount
+    } else {
+        round.commission_balance = round
+            .commission_balance
+            .checked_add(commission_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        0
+    };
+
+    add_capped_prize(round, prize_amount)?;
+
+    Ok(PurchaseSplit {
+        commission_amount,
+        prize_amount,
+        jackpot_bps,
+        jackpot_amount,
+        immediate_commission,
+    })
+}
+
+// Splits a purchase cost into (commission, prize) parts
+pub fn split_commission(cost: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    let commission_amount = cost
+        .checked_mul(fee_bps as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_div(10_000)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    let old_status = round.status.clone();
+    let prize_amount = cost
+        .checked_sub(commission_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    require!(old_status != RoundStatus::Completed, RaffleError::CannotChangeCompletedRound);
+    Ok((commission_amount, prize_amount))
+}
 
-    round.status = new_status.clone();
+// Bonus tickets the purchase at `purchase_index` earns under the raffle's first ticket bonus policy
+pub fn first_ticket_bonus(sol_raffle: &TokenRaffle, purchase_index: u32) -> u32 {
+    if !sol_raffle.first_ticket_bonus_enabled {
+        return 0;
+    }
 
-    if sol_raffle.current_round_id == Some(round.round_id) {
-        sol_raffle.current_round_status = new_status.clone();
-        msg!("✅ Updated current_round_status to {:?}", new_status);
+    let every = sol_raffle.first_ticket_bonus_every;
+    let qualifies = if every == 0 {
+        purchase_index == 0
     } else {
-        msg!("⚠️ Not updating current_round_status (processing old round {} while current is {:?})", 
-            round.round_id, 
-            sol_raffle.current_round_id
-        );
+        (purchase_index as u64 + 1).is_multiple_of(every as u64)
+    };
+
+    if qualifies { sol_raffle.first_ticket_bonus_tickets } else { 0 }
+}
+
+// Bonus tickets for buying `count` tickets in one transaction
+pub fn volume_bonus_tickets(sol_raffle: &TokenRaffle, count: u32) -> Result<u32> {
+    let per = sol_raffle.volume_bonus_per;
+    if per == 0 {
+        return Ok(0);
     }
 
-    emit!(StatusChanged {
-        round_id: round.round_id,
-        old_status,
-        new_status,
-        timestamp: Clock::get()?.unix_timestamp,
-    });
+    let bonus = (count / per)
+        .checked_mul(sol_raffle.volume_bonus_tickets)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(bonus)
+}
+
+// Adds free tickets to a purchase, kept apart in bonus_tickets so refunds and
+// prize accounting only use the paid ones. Returns the new round total
+pub fn add_bonus_tickets(
+    round_tickets_purchase: &mut RoundTicketsPurchase,
+    total_tickets: u32,
+    bonus: u32,
+) -> Result<u32> {
+    if bonus == 0 {
+        return Ok(total_tickets);
+    }
+
+    round_tickets_purchase.tickets_count = round_tickets_purchase
+        .tickets_count
+        .checked_add(bonus)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round_tickets_purchase.bonus_tickets = round_tickets_purchase
+        .bonus_tickets
+        .checked_add(bonus)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let new_total = total_tickets
+        .checked_add(bonus)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    Ok(new_total)
+}
+
+// Appends a cumulative ticket value to RoundTickets without deserializing the whole account
+pub fn append_cumulative_tickets(round_tickets: &AccountInfo, cumulative: u32) -> Result<()> {
+    let mut tickets_data = round_tickets.try_borrow_mut_data()?;
+
+    // Read current len (offset: 8 + 32 + 4096 + 4096 = 8232)
+    let len_offset = 8 + 32 + (1024 * 4) + (1024 * 4);
+    let len = u32::from_le_bytes([
+        tickets_data[len_offset],
+        tickets_data[len_offset + 1],
+        tickets_data[len_offset + 2],
+        tickets_data[len_offset + 3],
+    ]) as usize;
+
+    require!(
+        len < MAX_TICKETS,
+        RaffleError::RoundTicketsFull
+    );
+
+    // Write new cumulative value
+    let cumulative_bytes = cumulative.to_le_bytes();
+    if len < 1024 {
+        // Write to cumulative_tickets_1 (offset: 8 + 32)
+        let ticket_offset = 8 + 32 + (len * 4);
+        tickets_data[ticket_offset..ticket_offset + 4].copy_from_slice(&cumulative_bytes);
+    } else {
+        // Write to cumulative_tickets_2 (offset: 8 + 32 + 4096)
+        let ticket_offset = 8 + 32 + (1024 * 4) + ((len - 1024) * 4);
+        tickets_data[ticket_offset..ticket_offset + 4].copy_from_slice(&cumulative_bytes);
+    }
+
+    // Update len
+    let new_len = (len + 1) as u32;
+    tickets_data[len_offset..len_offset + 4].copy_from_slice(&new_len.to_le_bytes());
 
     Ok(())
 }
@@ -951,6 +2573,13 @@ pub struct WithdrawRentVault<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -982,6 +2611,7 @@ pub struct InitializeRaffle<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(cadence_id: u8)]
 pub struct InitializeSolRaffle<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -990,11 +2620,67 @@ pub struct InitializeSolRaffle<'info> {
         init,
         payer = authority,
         space = 8 + TokenRaffle::INIT_SPACE,
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle".as_ref(), &cadence_id.to_le_bytes()],
+        bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    // Rounds waiting to be processed by TukTuk and VRF
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PendingQueue>(),
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(cadence_id: u8)]
+pub struct InitializeTokenRaffle<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TokenRaffle::INIT_SPACE,
+        seeds = [b"sol_raffle".as_ref(), &cadence_id.to_le_bytes()],
         bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
 
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PendingQueue>(),
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    // None - native SOL raffle
+    pub token_mint: Option<Account<'info, Mint>>,
+
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Account<'info, FeedRegistry>,
+
+    /// CHECK: checked against feed_registry and parsed as a Switchboard pull feed
+    pub price_feed: AccountInfo<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1034,6 +2720,13 @@ pub struct WithdrawVrfVault<'info> {
     )]
     pub vrf_fee_vault: SystemAccount<'info>,
     
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1044,16 +2737,16 @@ pub struct BuyTicketsSol<'info> {
         seeds = [b"raffle_state"],
         bump = raffle_state.bump
     )]
-    pub raffle_state: Account<'info, RaffleState>,
+    pub raffle_state: Box<Account<'info, RaffleState>>,
 
     #[account(
         mut,
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
-    pub sol_raffle: Account<'info, TokenRaffle>,
+    pub sol_raffle: Box<Account<'info, TokenRaffle>>,
 
-    /// CHECK: May not exist - will be created via CPI if needed
+    /// CHECK: created by rollover, existence checked in the handler
     #[account(
         mut,
         seeds = [
@@ -1065,7 +2758,7 @@ pub struct BuyTicketsSol<'info> {
     )]
     pub round: UncheckedAccount<'info>,
 
-    /// CHECK: May not exist - will be created via CPI if needed
+    /// CHECK: created by rollover, connection to round checked in the handler
     #[account(
         mut,
         seeds = [
@@ -1076,13 +2769,6 @@ pub struct BuyTicketsSol<'info> {
     )]
     pub round_tickets: UncheckedAccount<'info>,
 
-    #[account(
-        mut,
-        seeds = [b"rent_vault"],
-        bump
-    )]
-    pub rent_vault: SystemAccount<'info>,
-
     #[account(
         init,
         payer = player,
@@ -1094,7 +2780,43 @@ pub struct BuyTicketsSol<'info> {
         ],
         bump
     )]
-    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+    pub round_tickets_purchase: Box<Account<'info, RoundTicketsPurchase>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [
+            b"player_stats",
+            sol_raffle.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub player_stats: Box<Account<'info, PlayerStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Box<Account<'info, RaffleStats>>,
+
+    // Required while sol_raffle.max_purchases_per_wallet is set
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + WalletPurchases::INIT_SPACE,
+        seeds = [
+            b"wallet_purchases",
+            round.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
 
     #[account(mut)]
     pub player: Signer<'info>,
@@ -1104,14 +2826,113 @@ pub struct BuyTicketsSol<'info> {
         seeds = [b"sol_vault"],
         bump
     )]
-    /// CHECK: vault for storing SOL
-    pub vault: AccountInfo<'info>,
-
-    /// CHECK: BTC price feed account
-    pub btc_price_feed: AccountInfo<'info>,
-
-    /// CHECK: SOL price feed account
-    pub sol_price_feed: AccountInfo<'info>,
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: price feed of the first ticket peg component, basket feeds go in remaining_accounts
+    pub reference_price_feed: AccountInfo<'info>,
+
+    /// CHECK: SOL price feed account
+    pub sol_price_feed: AccountInfo<'info>,
+
+    // Fallbacks for stale feeds, the fallback feeds go in remaining_accounts after the basket feeds
+    #[account(
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Option<Account<'info, FeedFailover>>,
+
+    // Crank-refreshed feed prices, read instead of the feeds while fresh
+    #[account(
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Option<Account<'info, PriceCache>>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+
+    // Required once the cluster profile is set up, pins the SOL feed and the oracle program
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    /// CHECK: must own sol_price_feed, required with feed_update
+    pub switchboard_program: Option<AccountInfo<'info>>,
+
+    // Required while the round has a sponsor match
+    #[account(
+        mut,
+        seeds = [
+            b"sponsor_match",
+            round.key().as_ref(),
+            sponsor_match.sponsor.as_ref()
+        ],
+        bump = sponsor_match.bump
+    )]
+    pub sponsor_match: Option<Account<'info, SponsorMatch>>,
+
+    // Required while jackpot_bps > 0
+    #[account(
+        mut,
+        seeds = [b"mega_jackpot"],
+        bump = mega_jackpot.bump
+    )]
+    pub mega_jackpot: Option<Account<'info, MegaJackpot>>,
+
+    // Required in rounds with CommissionMode::Immediate
+    #[account(
+        mut,
+        seeds = [b"commission_vault"],
+        bump = commission_vault.bump
+    )]
+    pub commission_vault: Option<Account<'info, CommissionVault>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    /// CHECK: SAS attestation of the player, required if sol_raffle.attestation_required. Validated in the handler
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Records PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Box<Account<'info, DailyStats>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -1128,7 +2949,7 @@ pub struct ClaimPrizeSol<'info> {
 
     #[account(
         mut,
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
@@ -1166,6 +2987,14 @@ pub struct ClaimPrizeSol<'info> {
     /// CHECK: vault for storing SOL
     pub vault: AccountInfo<'info>,
 
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [b"rent_vault"],
@@ -1179,6 +3008,61 @@ pub struct ClaimPrizeSol<'info> {
     )]
     /// CHECK: key validated via constraint
     pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    // Receives the prize instead of the winner
+    #[account(mut)]
+    pub recipient: Option<SystemAccount<'info>>,
+
+    // Lifetime prize totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: WinnerHistory PDA for the recent winners widget, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    // Required with a memo
+    pub memo_program: Option<Program<'info, Memo>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -1193,11 +3077,49 @@ pub struct RequestRandomness<'info> {
 
     #[account(
         mut,
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
 
+    #[account(
+        mut,
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump = pending_queue.load()?.bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    /// CHECK: the round picked from the queue, key checked in the handler
+    #[account(mut, owner = crate::id())]
+    pub round: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    // Receives the keeper tip
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    // Required while the keeper allowlist is enabled
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
     // PDA for VRF payment
     #[account(
         mut,
@@ -1237,6 +3159,28 @@ pub struct RequestRandomness<'info> {
     #[account(mut)]
     pub treasury: AccountInfo<'info>,
 
+    // Required once the cluster profile is set up
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Cover the prize floor of rounds with min_prize_lamports, insurance first
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: Option<SystemAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"mega_jackpot"],
+        bump = mega_jackpot.bump
+    )]
+    pub mega_jackpot: Option<Account<'info, MegaJackpot>>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -1253,7 +3197,7 @@ pub struct SetWinnerAddress<'info> {
 
     #[account(
         mut,
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
@@ -1281,6 +3225,21 @@ pub struct SetWinnerAddress<'info> {
     pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
 
     pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    /// CHECK: Records PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: UncheckedAccount<'info>,
 }
 
 // Account data structs
@@ -1293,6 +3252,49 @@ pub struct RaffleState {
     pub created_at: i64,
     pub vrf_request_counter: u8,
     pub bump: u8,
+    pub version: u8,
+    pub vesting_threshold: u64, // 0 - vesting disabled
+    pub vesting_cliff_seconds: i64,
+    pub vesting_duration_seconds: i64,
+    pub operator: Pubkey, // may grant promo tickets alongside the authority
+    pub insurance_bps: u16, // share of commission routed to insurance_vault
+    pub jackpot_bps: u16, // share of every purchase fed into the mega jackpot
+    pub maintenance_start: i64,
+    pub maintenance_end: i64, // 0 - no maintenance window
+    pub max_price_deviation_bps: u16, // from the round open price, 0 - circuit breaker disabled
+    pub watchtower: Pubkey, // can freeze rounds, Pubkey::default() - none
+    pub pending_authority: Pubkey, // set by propose_authority until accepted
+    pub keeper_tip: u64, // lamports from commission per settlement crank
+    pub keeper_allowlist_enabled: bool, // cranks restricted to KeeperRegistry
+    pub randomness_cooldown_seconds: i64, // between requests for the same round
+    pub max_randomness_requests: u8, // per round, 0 - unlimited
+    pub ticket_price_satoshis: u64, // base ticket price, BTC-denominated
+    #[max_len(MAX_PEG_COMPONENTS)]
+    pub ticket_peg: Vec<PegComponent>, // empty - pegged to ticket_price_satoshis of BTC
+    pub blocklist_enabled: bool, // purchases and claims must pass the Blocklist
+    pub max_price_confidence_bps: u16, // feed std dev as a share of the value, 0 - unchecked
+    pub cluster_profile_enabled: bool, // purchases and randomness requests must pass the ClusterProfile
+    pub price_cache_enabled: bool, // purchases and price views must pass the PriceCache
+    pub feed_registry_enabled: bool, // every feed read must pass the FeedRegistry
+}
+
+impl RaffleState {
+    pub fn is_operator(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.operator != Pubkey::default() && *key == self.operator)
+    }
+
+    pub fn is_vested_prize(&self, prize_amount: u64) -> bool {
+        self.vesting_threshold > 0 && prize_amount >= self.vesting_threshold
+    }
+
+    pub fn is_watchtower(&self, key: &Pubkey) -> bool {
+        *key == self.authority || (self.watchtower != Pubkey::default() && *key == self.watchtower)
+    }
+
+    // Blocks round creation and purchases only, settlement keeps working
+    pub fn is_in_maintenance(&self, now: i64) -> bool {
+        now >= self.maintenance_start && now < self.maintenance_end
+    }
 }
 
 #[account]
@@ -1302,6 +3304,13 @@ pub struct ClientState {
     pub authority: Pubkey,
 }
 
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TokenRaffleArgs {
+    pub decimals: u8, // must match the mint, 9 for SOL
+    pub fee_percentage: Option<u8>, // None - raffle_state.entrance_fee_percentage
+    pub round_duration: i64, // 0 - default 12 hours cadence
+}
+
 #[account]
 #[derive(InitSpace, Debug)]
 pub struct TokenRaffle {
@@ -1312,8 +3321,64 @@ pub struct TokenRaffle {
     pub current_round_end_time: Option<i64>,
     pub total_rounds: u32,
     #[max_len(20)]
-    pub pending_rounds: Vec<u32>,  // Rounds waiting to be processed by TukTuk and VRF
+    pub pending_rounds: Vec<u32>, // replaced by PendingQueue, emptied by initialize_pending_queue
     pub bump: u8,
+    pub version: u8,
+    pub streak_bonus_every: u32, // 0 - streak rewards disabled
+    pub streak_bonus_tickets: u32,
+    pub first_ticket_bonus_enabled: bool,
+    pub first_ticket_bonus_tickets: u32,
+    pub first_ticket_bonus_every: u32, // 0 - first buyer only, N - every Nth buyer
+    pub volume_bonus_per: u32, // tickets bought in one transaction per bonus, 0 - disabled
+    pub volume_bonus_tickets: u32,
+    pub price_decay_mode: PriceDecayMode,
+    pub price_decay_start_bps: u16, // of the oracle price, at round start
+    pub price_decay_end_bps: u16, // at round end
+    pub price_decay_steps: u8, // Stepwise mode only
+    pub price_sample_mode: PriceSampleMode, // snapshotted by each round at creation
+    pub price_sample_count: u8, // oracle submissions taken by Min and Median
+    pub cancel_penalty_bps: Option<u16>, // None - purchases can't be cancelled
+    pub commission_mode: CommissionMode, // snapshotted by each round at creation
+    pub staking_enabled: bool, // idle prize pools of daily+ rounds go to the stake pool
+    pub staking_yield_to_insurance: bool, // false - yield is added to the prize
+    pub price_feed: Pubkey, // registered feed ticket prices are read from, default for raffles created by initialize_sol_raffle
+    pub token_decimals: u8, // ticket prices are in base units of the token
+    pub fee_percentage: Option<u8>, // overrides raffle_state.entrance_fee_percentage outside mega rounds
+    #[max_len(MAX_FEE_TIERS)]
+    pub fee_tiers: Vec<FeeTier>, // by round pot, overrides fee_percentage. Empty - flat fee
+    pub max_purchases_per_wallet: u32, // buy_tickets_sol purchases per round, 0 - unlimited
+    pub min_tickets_per_purchase: u32, // buy_tickets_sol, 0 - no minimum
+    pub min_purchase_lamports: u64, // buy_tickets_sol cost, 0 - no minimum
+    pub prize_target_lamports: Option<u64>, // snapshotted by each round at creation, None - timed only
+    pub prize_cap_lamports: Option<u64>, // snapshotted by each round at creation, None - uncapped
+    pub carryover: u64, // prize excess of drawn rounds, added to the next round's pot at creation
+    pub min_prize_lamports: u64, // snapshotted by each round at creation, 0 - no floor
+    pub house_ticket_threshold: u32, // rounds with fewer tickets get a house purchase, 0 - disabled
+    pub house_tickets: u32, // size of the house purchase
+    pub redraw_after_seconds: i64, // claim deadline after the draw before redraw is allowed, 0 - disabled
+    pub access_root: Option<[u8; 32]>, // snapshotted by each round at creation, None - public
+    pub attestation_required: bool, // buy_tickets_sol needs a SAS attestation of the player
+    pub attestation_credential: Pubkey, // issuer credential the attestation must be signed under
+    pub attestation_schema: Pubkey, // e.g. age or KYC
+    pub pricing_mode: PricingMode, // snapshotted by each round at creation
+    pub fixed_ticket_price_usd: u64, // USD with 6 decimals, FixedUsd mode only
+    pub commit_round_price: bool, // snapshotted by each round at creation
+    pub loyalty_points_per_sol: u64, // accrual rate, 0 - disabled
+    pub loyalty_points_per_ticket: u64, // redemption cost, 0 - disabled
+    pub cadence_id: u8, // part of PDA seeds, several cadences run for the same asset
+    pub round_duration: i64, // seconds, divides a day evenly
+    pub mega_round_weekday: Option<u8>, // 0 - Sunday, NY time. None - no mega rounds
+    pub mega_round_fee_percentage: u8,
+    pub mega_round_duration: i64,
+    pub mega_round_jackpot_bps: u16, // on top of RaffleState.jackpot_bps
+    pub sales_paused: bool,
+}
+
+impl TokenRaffle {
+    // Round ids are strictly sequential, the first round is 0
+    pub fn next_round_id(&self) -> u32 {
+        self.current_round_id.map_or(0, |id| id + 1)
+    }
 }
 
 #[account]
@@ -1334,6 +3399,58 @@ pub struct Round {
     pub winner_address: Option<Pubkey>,
     pub prize_claimed: bool,
     pub bump: u8,
+    pub version: u8, // 0 - created before the field existed, read as v1
+    pub sponsored_amount: u64, // part of prize_amount from boosts, matching and promo budget, no commission taken
+    pub free_entry: bool, // tickets are free, the prize is funded from promo_vault
+    pub free_entry_max_tickets: u32, // per wallet
+    pub commission_settled: bool,
+    pub jackpot_amount: u64, // mega jackpot won on top of the prize, included in prize_amount
+    pub mega_round: bool, // scheduled special round with its own fee and duration
+    pub open_ticket_price: u64, // lamports, recorded by the first priced purchase
+    pub open_sol_price_usd: u64, // USD with 6 decimals, snapshotted with open_ticket_price
+    pub open_reference_price_usd: u64, // first peg component (BTC by default), 0 in FixedUsd mode
+    pub open_price_slot: u64,
+    pub commit_price: bool, // the first priced purchase fixes ticket_price_lamports
+    pub ticket_price_lamports: u64, // committed oracle price every purchase uses, 0 - live oracle price
+    pub price_sample_mode: PriceSampleMode,
+    pub price_sample_count: u8,
+    pub frozen: bool, // no draws and no claims pending investigation
+    pub request_tip_paid: bool,
+    pub last_request_time: i64, // last request_randomness for this round
+    pub request_count: u8, // reset by authority once max_randomness_requests is hit
+    pub jackpot_contributed: u64, // part of ticket sales sent to the mega jackpot, not in prize_amount
+    pub keeper_tips_paid: u64, // taken out of commission_balance
+    pub refunded_amount: u64, // returned to buyers of cancelled purchases
+    pub commission_mode: CommissionMode,
+    pub commission_accrued: u64, // moved to commission_vault, not part of commission_balance
+    pub staked_lamports: u64, // part of prize_amount deposited in the stake pool
+    pub stake_pool_tokens: u64,
+    pub staking_yield: u64, // added to prize_amount by unstaking
+    pub staking_loss: u64, // taken from prize_amount when the pool returned less than staked
+    pub pricing_mode: PricingMode,
+    pub fixed_ticket_price_usd: u64,
+    pub access_root: Option<[u8; 32]>, // merkle root of allowed wallets, None - public
+    pub prize_target_lamports: Option<u64>, // the round closes once prize_amount reaches it
+    pub prize_cap_lamports: Option<u64>,
+    pub carryover_in: u64, // from earlier rounds, part of prize_amount
+    pub carryover_out: u64, // prize above the cap, passed to the next round once drawn
+    pub min_prize_lamports: u64,
+    pub prize_top_up: u64, // from insurance and the mega jackpot to reach the floor, part of prize_amount
+    pub house_tickets: u32, // drawn after total_tickets, a house win rolls the pot over
+    pub house_owner: Pubkey, // beneficiary at draw time
+    pub completed_at: i64, // time of the latest draw
+    pub redraw_pending: bool, // waiting for the randomness of a redraw
+    pub first_winner_purchase_index: Option<u32>, // set by redraw, the winner fields then hold the second draw
+    pub first_winner_ticket_index: Option<u32>,
+    pub first_winner_address: Option<Pubkey>,
+    pub randomness: [u8; 64], // VRF output of the latest draw
+    pub vrf_request: Pubkey, // ORAO request account of the latest draw
+    pub locked_at: i64,
+    pub locked_total_tickets: u32, // totals when sales closed, the draw must still match them
+    pub locked_purchases_count: u32,
+    pub locked_prize_amount: u64, // before the house ticket and the prize floor top-up
+    pub sponsor_match: Pubkey, // the open SponsorMatch purchases must pass, Pubkey::default() - none
+    pub house_rolled: u64, // pot moved on by a house win, no longer in prize_amount
 }
 
 #[account(zero_copy)]
@@ -1344,10 +3461,13 @@ pub struct RoundTickets {
     pub cumulative_tickets_2: [u32; 1024],
     pub len: u32,
     pub bump: u8,
-    pub padding: [u8; 3],
+    pub version: u8, // taken from padding, the layout size is unchanged. 0 - read as v1
+    pub padding: [u8; 2],
 }
 
 impl RoundTickets {
+    pub const PAGE_SIZE: usize = 1024; // purchases per page, one binary search fits a callback
+
     pub fn get_tickets(&self) -> Vec<u32> {
         let len = self.len as usize;
         
@@ -1386,14 +3506,33 @@ impl RoundTickets {
         Ok(())
     }
     
+    // Purchases [start, end) stored on a page, None past the last one
+    pub fn page_range(&self, page: usize) -> Option<(usize, usize)> {
+        let start = page.checked_mul(Self::PAGE_SIZE)?;
+        let len = self.len as usize;
+
+        if start >= len {
+            return None;
+        }
+
+        Some((start, len.min(start + Self::PAGE_SIZE)))
+    }
+
     // Binary search
-    pub fn partition_point<F>(&self, mut pred: F) -> usize 
+    pub fn partition_point<F>(&self, pred: F) -> usize 
     where
         F: FnMut(u32) -> bool
     {
-        let len = self.len as usize;
-        let mut left = 0;
-        let mut right = len;
+        self.partition_point_in(0, self.len as usize, pred)
+    }
+
+    // Binary search within purchases [start, end)
+    pub fn partition_point_in<F>(&self, start: usize, end: usize, mut pred: F) -> usize
+    where
+        F: FnMut(u32) -> bool
+    {
+        let mut left = start;
+        let mut right = end;
         
         while left < right {
             let mid = left + (right - left) / 2;
@@ -1416,8 +3555,19 @@ pub struct RoundTicketsPurchase {
     pub round: Pubkey, //connection with Round struct
     pub player: Pubkey,
     pub purchase_index: u32,
-    pub tickets_count: u32,
+    pub tickets_count: u32, // includes bonus_tickets
     pub bump: u8,
+    pub bonus_tickets: u32, // free tickets on top of the paid ones
+    pub ticket_price: u64, // lamports per paid ticket, 0 for free entries
+    pub oracle_price: u64, // lamports per ticket from the feeds, before price decay
+    pub price_slot: u64, // slot of the SOL feed result used
+    pub fee_bps: u16, // entrance fee charged on this purchase
+    pub cancelled: bool, // tombstone, the ticket range was collapsed
+    pub jackpot_bps: Option<u16>, // jackpot share charged on this purchase, None - bought before it was kept
+    pub loyalty_points: u64, // points this purchase earned
+    pub sponsor_matched: u64, // lamports the round's sponsor match added for this purchase
+    pub entry_streak: u32, // streak reached by counting the round here, 0 - it was already counted
+    pub best_streak_raised: bool, // the entry raised the buyer's best_streak
 }
 
 #[account]
@@ -1442,6 +3592,37 @@ pub struct VRFContext {
 pub enum RoundStatus {
     Open, // 0 - Round is open for tickets buying
     Completed, // 1 - Round closed, winner picked
+    Drawing, // 2 - Randomness requested, waiting for the VRF callback
+    Cancelled, // 3 - Round called off, no winner
+    Expired, // 4 - Round ended without tickets
+    Locked, // 5 - Sales closed and totals captured, randomness not requested yet
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum PriceSampleMode {
+    Latest, // 0 - The feed's latest aggregated result
+    Min, // 1 - Lowest of the last price_sample_count oracle submissions
+    Median, // 2 - Median of the last price_sample_count oracle submissions
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum PriceDecayMode {
+    None, // 0 - Tickets cost the oracle price
+    Linear, // 1 - Price falls continuously from start_bps to end_bps
+    Stepwise, // 2 - Price falls in price_decay_steps even drops
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum CommissionMode {
+    OnClaim, // 0 - Paid out of sol_vault together with the prize
+    OnCompletion, // 1 - Moved to commission_vault when the winner is drawn
+    Immediate, // 2 - Sent to commission_vault by every purchase
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace, Debug)]
+pub enum PricingMode {
+    Pegged, // 0 - RaffleState.ticket_peg, BTC by default
+    FixedUsd, // 1 - TokenRaffle.fixed_ticket_price_usd
 }
 
 // Events
@@ -1451,10 +3632,30 @@ pub struct TicketPurchased {
     pub round_id: u32,
     pub buyer: Pubkey,
     pub count: u32,
+    pub ticket_price: u64,
+    pub oracle_price: u64, // before price decay
+    pub price_slot: u64, // slot of the SOL feed result used
     pub total_amount: u64,
     pub prize_amount: u64,
     pub commission_amount: u64,
+    pub fee_bps: u16,
     pub timestamp: i64,
+    pub purchase_index: u32,
+    pub ticket_start: u32, // cumulative ticket range of the purchase [start, end)
+    pub ticket_end: u32,
+    pub effective_ticket_price: u64, // total_amount per ticket, bonus tickets included
+    pub total_tickets: u32, // round total after the purchase
+}
+
+#[event]
+pub struct SlippageExceeded {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub buyer: Pubkey,
+    pub count: u32,
+    pub ticket_price: u64, // the unit price the purchase would have paid
+    pub max_price_per_ticket: u64,
+    pub cost: u64, // ticket_price * count
 }
 
 #[event]
@@ -1462,17 +3663,20 @@ pub struct FirstTicketBonusAwarded {
     pub token: Pubkey,
     pub round_id: u32,
     pub buyer: Pubkey,
+    pub purchase_index: u32,
+    pub bonus_tickets: u32,
     pub timestamp: i64,
     pub round_start_time: i64,
     pub round_end_time: i64,
 }
 
 #[event]
-pub struct StatusChanged {
+pub struct VolumeBonusAwarded {
+    pub token: Pubkey,
     pub round_id: u32,
-    pub old_status: RoundStatus,
-    pub new_status: RoundStatus,
-    pub timestamp: i64,
+    pub buyer: Pubkey,
+    pub purchased: u32,
+    pub bonus_tickets: u32,
 }
 
 #[event]
@@ -1482,14 +3686,20 @@ pub struct AllRequestsCompleted {}
 pub struct PrizeClaimed {
     pub round_id: u32,
     pub winner: Pubkey,
+    pub recipient: Pubkey,
+    pub prize_amount: u64,
+    pub commission_amount: u64, // before the insurance share
+    pub beneficiary: Pubkey,
+    pub pushed: bool, // paid out by a keeper, otherwise claimed
+    pub timestamp: i64,
 }
 
-// Error codes
+// Error codes. Clients map errors by code, new variants are only appended
 #[error_code]
 pub enum RaffleError {
     #[msg("Prize already claimed")]
     AlreadyClaimed,
-    
+
     #[msg("All VRF request accounts is used")]
     AllRequestsCompleted,
 
@@ -1579,4 +3789,340 @@ pub enum RaffleError {
 
     #[msg("Winner address already set")]
     WinnerAlreadySet,
+
+    #[msg("Invalid vesting config")]
+    InvalidVestingConfig,
+
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+
+    #[msg("Prize is below the vesting threshold")]
+    PrizeNotSubjectToVesting,
+
+    #[msg("Prize is above the vesting threshold, use start_vesting")]
+    PrizeSubjectToVesting,
+
+    #[msg("Invalid sponsor match ratio")]
+    InvalidMatchRatio,
+
+    #[msg("Promo budget exhausted")]
+    PromoBudgetExhausted,
+
+    #[msg("Free-entry ticket limit per wallet reached")]
+    FreeEntryLimitReached,
+
+    #[msg("Round is free entry, use enter_free_round")]
+    FreeEntryRound,
+
+    #[msg("Round is not a free-entry round")]
+    NotFreeEntryRound,
+
+    #[msg("Round already exists")]
+    RoundAlreadyExists,
+
+    #[msg("Invalid streak rewards config")]
+    InvalidStreakConfig,
+
+    #[msg("Insufficient loyalty points")]
+    InsufficientLoyaltyPoints,
+
+    #[msg("Loyalty points redemption is disabled")]
+    LoyaltyRedemptionDisabled,
+
+    #[msg("Rewards budget exhausted")]
+    RewardsBudgetExhausted,
+
+    #[msg("Invalid basis points value")]
+    InvalidBps,
+
+    #[msg("Raffle is not denominated in an SPL token")]
+    NotTokenRaffle,
+
+    #[msg("Mega jackpot account required")]
+    MissingJackpotAccount,
+
+    #[msg("Round duration must evenly divide a day")]
+    InvalidRoundDuration,
+
+    #[msg("Invalid mega round config")]
+    InvalidMegaRoundConfig,
+
+    #[msg("Invalid maintenance window")]
+    InvalidMaintenanceWindow,
+
+    #[msg("Program is in maintenance mode")]
+    MaintenanceMode,
+
+    #[msg("Sales are paused for this raffle")]
+    SalesPaused,
+
+    #[msg("Oracle price moved too far since round open")]
+    PriceCircuitBreaker,
+
+    #[msg("Round is frozen")]
+    RoundFrozen,
+
+    #[msg("Round is not frozen")]
+    RoundNotFrozen,
+
+    #[msg("Fee percentage must not exceed 100")]
+    InvalidFeePercentage,
+
+    #[msg("Account schema version mismatch")]
+    SchemaVersionMismatch,
+
+    #[msg("Pending queue is full")]
+    PendingQueueFull,
+
+    #[msg("Pending queue not initialized")]
+    PendingQueueNotInitialized,
+
+    #[msg("Keeper already registered")]
+    KeeperAlreadyRegistered,
+
+    #[msg("Keeper is not registered")]
+    KeeperNotRegistered,
+
+    #[msg("Keeper registry is full")]
+    KeeperRegistryFull,
+
+    #[msg("Randomness request cooldown has not elapsed")]
+    RandomnessCooldown,
+
+    #[msg("Randomness request limit reached for this round, authority reset required")]
+    RandomnessRetriesExhausted,
+
+    #[msg("TukTuk task may only call this program")]
+    InvalidTukTukTask,
+
+    #[msg("Purchase index must equal the round's purchases count")]
+    PurchaseIndexMismatch,
+
+    #[msg("Round id must follow the current round")]
+    RoundIdNotSequential,
+
+    #[msg("Round status transition not allowed")]
+    InvalidStatusTransition,
+
+    #[msg("sol_vault does not cover outstanding obligations")]
+    InsolventVault,
+
+    #[msg("Metadata field too long")]
+    MetadataTooLong,
+
+    #[msg("Invalid first ticket bonus config")]
+    InvalidFirstTicketBonusConfig,
+
+    #[msg("Invalid volume bonus config")]
+    InvalidVolumeBonusConfig,
+
+    #[msg("Invalid price decay config")]
+    InvalidPriceDecayConfig,
+
+    #[msg("Purchase cancellation is disabled")]
+    CancellationDisabled,
+
+    #[msg("Purchase was cancelled")]
+    PurchaseCancelled,
+
+    #[msg("New owner must be a different, non-default address")]
+    InvalidNewOwner,
+
+    #[msg("Memo too long")]
+    MemoTooLong,
+
+    #[msg("Memo program account required")]
+    MissingMemoProgram,
+
+    #[msg("Round is not cancelled")]
+    RoundNotCancelled,
+
+    #[msg("Purchase already refunded")]
+    AlreadyRefunded,
+
+    #[msg("Commission vault account required")]
+    MissingCommissionVault,
+
+    #[msg("Daily commission withdrawal cap exceeded")]
+    CommissionWithdrawalCapExceeded,
+
+    #[msg("Prize pool is staked, unstake it first")]
+    PrizePoolStaked,
+
+    #[msg("Staking is not enabled for this raffle or the round is shorter than a day")]
+    StakingNotAvailable,
+
+    #[msg("Staking yield sweep interval has not elapsed")]
+    YieldSweepTooEarly,
+
+    #[msg("Price feed is not registered for this mint")]
+    FeedNotRegistered,
+
+    #[msg("Feed registry is full")]
+    FeedRegistryFull,
+
+    #[msg("Token decimals don't match the mint")]
+    InvalidTokenDecimals,
+
+    #[msg("Invalid ticket peg config")]
+    InvalidTicketPeg,
+
+    #[msg("Fee tiers must grow by pot with non-increasing fees")]
+    InvalidFeeTiers,
+
+    #[msg("Purchase limit per wallet reached for this round")]
+    WalletPurchaseLimitReached,
+
+    #[msg("Wallet is not on the round's access list")]
+    NotOnAccessList,
+
+    #[msg("Round already has purchases")]
+    RoundHasPurchases,
+
+    #[msg("Address is already blocked")]
+    AddressAlreadyBlocked,
+
+    #[msg("Address is blocked")]
+    AddressBlocked,
+
+    #[msg("Address is not blocked")]
+    AddressNotBlocked,
+
+    #[msg("Blocklist is full")]
+    BlocklistFull,
+
+    #[msg("Blocklist account is required while the blocklist is enabled")]
+    BlocklistRequired,
+
+    #[msg("Attestation has expired")]
+    AttestationExpired,
+
+    #[msg("Attestation account is required for this raffle")]
+    AttestationRequired,
+
+    #[msg("Attestation does not match the raffle's credential, schema or player")]
+    InvalidAttestation,
+
+    #[msg("Attestation credential and schema must be set")]
+    InvalidAttestationConfig,
+
+    #[msg("Purchase is below the raffle's minimum ticket count or spend")]
+    PurchaseTooSmall,
+
+    #[msg("Insurance vault or mega jackpot is required to cover the prize floor")]
+    MissingPrizeFloorAccount,
+
+    #[msg("House ticket threshold and size must both be set or both be zero")]
+    InvalidHouseTicketConfig,
+
+    #[msg("Round was already redrawn")]
+    AlreadyRedrawn,
+
+    #[msg("No other tickets to redraw from")]
+    NothingToRedraw,
+
+    #[msg("Redraw is disabled for this raffle")]
+    RedrawDisabled,
+
+    #[msg("Prize claim deadline has not passed yet")]
+    RedrawTooEarly,
+
+    #[msg("Winning ticket is not on this page")]
+    InvalidPageHint,
+
+    #[msg("Winning purchase not resolved yet, run finalize_draw")]
+    WinnerNotFinalized,
+
+    #[msg("Round tickets changed after the round was locked")]
+    RoundChangedAfterLock,
+
+    #[msg("Oracle price confidence interval is too wide")]
+    LowConfidencePrice,
+
+    #[msg("Invalid price sampling config")]
+    InvalidPriceSamplingConfig,
+
+    #[msg("Not enough fresh oracle samples")]
+    NotEnoughPriceSamples,
+
+    #[msg("Feed failover is full")]
+    FeedFailoverFull,
+
+    #[msg("Invalid feed failover")]
+    InvalidFeedFailover,
+
+    #[msg("Invalid emergency price")]
+    InvalidEmergencyPrice,
+
+    #[msg("No emergency price set")]
+    NoEmergencyPrice,
+
+    #[msg("Oracle feed value is not positive")]
+    OracleNonPositivePrice,
+
+    #[msg("Oracle feed account could not be parsed")]
+    OracleParseFailed,
+
+    #[msg("Oracle feed has no fresh samples")]
+    OracleStale,
+
+    #[msg("Feed is not in the price cache")]
+    FeedNotCached,
+
+    #[msg("Invalid price cache config")]
+    InvalidPriceCacheConfig,
+
+    #[msg("Feed update must go to the program owning the SOL feed")]
+    InvalidFeedUpdate,
+
+    #[msg("Switchboard program account is required for a feed update")]
+    MissingSwitchboardProgram,
+
+    #[msg("Legacy Switchboard aggregator feeds are not supported, use the On-Demand pull feed")]
+    OracleLegacyFeed,
+
+    #[msg("Invalid feed read limits")]
+    InvalidFeedReadLimits,
+
+    #[msg("Account doesn't match the cluster profile")]
+    ClusterMismatch,
+
+    #[msg("Cluster profile account is required once the profile is set up")]
+    ClusterProfileRequired,
+
+    #[msg("Invalid cluster profile")]
+    InvalidClusterProfile,
+
+    #[msg("Ticket price below the mainnet floor")]
+    MainnetPriceFloor,
+
+    #[msg("Daily stats are not for the current UTC day")]
+    DailyStatsDayMismatch,
+
+    #[msg("Daily stats can only be opened for today or tomorrow")]
+    InvalidDailyStatsDay,
+
+    #[msg("Account is already in the current layout")]
+    AccountAlreadyMigrated,
+
+    #[msg("The round has no tickets to draw from")]
+    NoTicketsToDraw,
+
+    #[msg("Price cache account is required once the cache is set up")]
+    PriceCacheRequired,
+
+    #[msg("Feed registry account is required once the registry is set up")]
+    FeedRegistryRequired,
+
+    #[msg("The ticket peg must name its feeds under a cluster profile")]
+    TicketPegFeedRequired,
+
+    #[msg("The round's sponsor match must be passed")]
+    SponsorMatchRequired,
+
+    #[msg("The round already has a sponsor match")]
+    SponsorMatchActive,
+
+    #[msg("The wallet's purchase count must be passed while the per-wallet cap is set")]
+    WalletPurchasesRequired,
 }
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RaffleError,
+    TokenRaffle,
+};
+
+// Every allowed round status change. Completed, Cancelled and Expired are final
+//
+//   Open ──► Locked ──► Drawing ──► Completed
+//    │         │           │
+//    │         │           └──────► Cancelled
+//    │         ├──────────────────► Cancelled
+//    │         └──────────────────► Expired (locked without tickets)
+//    ├────────────────────────────► Cancelled
+//    └────────────────────────────► Expired (ended without tickets)
+const TRANSITIONS: &[(RoundStatus, RoundStatus)] = &[
+    (RoundStatus::Open, RoundStatus::Locked),
+    (RoundStatus::Open, RoundStatus::Cancelled),
+    (RoundStatus::Open, RoundStatus::Expired),
+    (RoundStatus::Locked, RoundStatus::Drawing),
+    (RoundStatus::Locked, RoundStatus::Cancelled),
+    (RoundStatus::Locked, RoundStatus::Expired),
+    (RoundStatus::Drawing, RoundStatus::Completed),
+    (RoundStatus::Drawing, RoundStatus::Cancelled),
+];
+
+impl RoundStatus {
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            RoundStatus::Completed | RoundStatus::Cancelled | RoundStatus::Expired
+        )
+    }
+}
+
+pub fn can_transition(from: &RoundStatus, to: &RoundStatus) -> bool {
+    TRANSITIONS.iter().any(|(f, t)| f == from && t == to)
+}
+
+// Marks a freshly created round as the current one
+pub fn open_round(sol_raffle: &mut TokenRaffle, round_id: u32, end_time: i64) {
+    sol_raffle.current_round_id = Some(round_id);
+    sol_raffle.current_round_status = RoundStatus::Open;
+    sol_raffle.current_round_end_time = Some(end_time);
+}
+
+// Sales are closed from here on, the draw and pick_winner work on the captured totals
+pub fn lock_round_totals(sol_raffle: &mut TokenRaffle, round: &mut Round, now: i64) -> Result<()> {
+    transition_round(sol_raffle, round, RoundStatus::Locked)?;
+
+    round.locked_at = now;
+    round.locked_total_tickets = round.total_tickets;
+    round.locked_purchases_count = round.purchases_count;
+    round.locked_prize_amount = round.prize_amount;
+
+    emit!(RoundLocked {
+        round_id: round.round_id,
+        total_tickets: round.total_tickets,
+        purchases_count: round.purchases_count,
+        prize_amount: round.prize_amount,
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+// The only place a round status changes, TokenRaffle.current_round_status follows the current round
+pub fn transition_round(
+    sol_raffle: &mut TokenRaffle,
+    round: &mut Round,
+    new_status: RoundStatus,
+) -> Result<()> {
+    let old_status = round.status.clone();
+
+    require!(!old_status.is_final(), RaffleError::CannotChangeCompletedRound);
+    require!(
+        can_transition(&old_status, &new_status),
+        RaffleError::InvalidStatusTransition
+    );
+
+    round.status = new_status.clone();
+
+    if sol_raffle.current_round_id == Some(round.round_id) {
+        sol_raffle.current_round_status = new_status.clone();
+        msg!("✅ Updated current_round_status to {:?}", new_status);
+    } else {
+        msg!("⚠️ Not updating current_round_status (processing old round {} while current is {:?})",
+            round.round_id,
+            sol_raffle.current_round_id
+        );
+    }
+
+    emit!(StatusChanged {
+        round_id: round.round_id,
+        old_status,
+        new_status,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Events
+#[event]
+pub struct RoundLocked {
+    pub round_id: u32,
+    pub total_tickets: u32,
+    pub purchases_count: u32,
+    pub prize_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StatusChanged {
+    pub round_id: u32,
+    pub old_status: RoundStatus,
+    pub new_status: RoundStatus,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [RoundStatus; 6] = [
+        RoundStatus::Open,
+        RoundStatus::Completed,
+        RoundStatus::Drawing,
+        RoundStatus::Cancelled,
+        RoundStatus::Expired,
+        RoundStatus::Locked,
+    ];
+
+    #[test]
+    fn locked_round_can_expire() {
+        assert!(can_transition(&RoundStatus::Locked, &RoundStatus::Expired));
+        assert!(can_transition(&RoundStatus::Open, &RoundStatus::Expired));
+        assert!(!can_transition(&RoundStatus::Drawing, &RoundStatus::Expired));
+    }
+
+    #[test]
+    fn final_status_has_no_transition() {
+        for from in ALL.iter().filter(|status| status.is_final()) {
+            for to in ALL.iter() {
+                assert!(!can_transition(from, to), "{:?} -> {:?}", from, to);
+            }
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+};
+
+pub const MAX_METADATA_NAME_LEN: usize = 32;
+pub const MAX_METADATA_VERSION_LEN: usize = 16;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+pub const MAX_METADATA_CONTACT_LEN: usize = 64;
+pub const MAX_METADATA_DESCRIPTION_LEN: usize = 200;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ProgramMetadataArgs {
+    pub name: String,
+    pub version: String, // semantic version, e.g. "1.4.0"
+    pub config_uri: String,
+    pub support_contact: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RaffleMetadataArgs {
+    pub name: String,
+    pub description: String,
+    pub image_uri: String,
+}
+
+// Only authority. Run once at deploy
+pub fn initialize_program_metadata(
+    ctx: Context<InitializeProgramMetadata>,
+    args: ProgramMetadataArgs,
+) -> Result<()> {
+    let program_metadata = &mut ctx.accounts.program_metadata;
+    program_metadata.bump = ctx.bumps.program_metadata;
+    apply_program_metadata(program_metadata, args)
+}
+
+// Only authority
+pub fn set_program_metadata(ctx: Context<UpdateProgramMetadata>, args: ProgramMetadataArgs) -> Result<()> {
+    apply_program_metadata(&mut ctx.accounts.program_metadata, args)
+}
+
+fn apply_program_metadata(program_metadata: &mut ProgramMetadata, args: ProgramMetadataArgs) -> Result<()> {
+    require!(
+        args.name.len() <= MAX_METADATA_NAME_LEN
+            && args.version.len() <= MAX_METADATA_VERSION_LEN
+            && args.config_uri.len() <= MAX_METADATA_URI_LEN
+            && args.support_contact.len() <= MAX_METADATA_CONTACT_LEN,
+        RaffleError::MetadataTooLong
+    );
+
+    program_metadata.name = args.name;
+    program_metadata.version = args.version;
+    program_metadata.config_uri = args.config_uri;
+    program_metadata.support_contact = args.support_contact;
+    program_metadata.updated_at = Clock::get()?.unix_timestamp;
+
+    msg!("✅ Program metadata set, version {}", program_metadata.version);
+
+    emit!(ProgramMetadataUpdated {
+        name: program_metadata.name.clone(),
+        version: program_metadata.version.clone(),
+        config_uri: program_metadata.config_uri.clone(),
+        support_contact: program_metadata.support_contact.clone(),
+    });
+
+    Ok(())
+}
+
+// Only authority. Creates the raffle's metadata on first use
+pub fn set_raffle_metadata(ctx: Context<SetRaffleMetadata>, args: RaffleMetadataArgs) -> Result<()> {
+    require!(
+        args.name.len() <= MAX_METADATA_NAME_LEN
+            && args.description.len() <= MAX_METADATA_DESCRIPTION_LEN
+            && args.image_uri.len() <= MAX_METADATA_URI_LEN,
+        RaffleError::MetadataTooLong
+    );
+
+    let raffle_metadata = &mut ctx.accounts.raffle_metadata;
+    raffle_metadata.token_raffle = ctx.accounts.sol_raffle.key();
+    raffle_metadata.name = args.name;
+    raffle_metadata.description = args.description;
+    raffle_metadata.image_uri = args.image_uri;
+    raffle_metadata.bump = ctx.bumps.raffle_metadata;
+
+    msg!("✅ Metadata set for raffle {}", ctx.accounts.sol_raffle.cadence_id);
+
+    emit!(RaffleMetadataUpdated {
+        token_raffle: raffle_metadata.token_raffle,
+        name: raffle_metadata.name.clone(),
+        description: raffle_metadata.description.clone(),
+        image_uri: raffle_metadata.image_uri.clone(),
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeProgramMetadata<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramMetadata::INIT_SPACE,
+        seeds = [b"program_metadata"],
+        bump
+    )]
+    pub program_metadata: Account<'info, ProgramMetadata>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateProgramMetadata<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"program_metadata"],
+        bump = program_metadata.bump
+    )]
+    pub program_metadata: Account<'info, ProgramMetadata>,
+
+    pub authority: Signer<'info>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetRaffleMetadata<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RaffleMetadata::INIT_SPACE,
+        seeds = [b"raffle_metadata", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_metadata: Account<'info, RaffleMetadata>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+// Deployment info for frontends and explorers
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ProgramMetadata {
+    #[max_len(MAX_METADATA_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_METADATA_VERSION_LEN)]
+    pub version: String,
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub config_uri: String,
+    #[max_len(MAX_METADATA_CONTACT_LEN)]
+    pub support_contact: String,
+    pub updated_at: i64,
+    pub bump: u8,
+}
+
+// Display info of one TokenRaffle
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct RaffleMetadata {
+    pub token_raffle: Pubkey, //connection with TokenRaffle struct
+    #[max_len(MAX_METADATA_NAME_LEN)]
+    pub name: String,
+    #[max_len(MAX_METADATA_DESCRIPTION_LEN)]
+    pub description: String,
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub image_uri: String,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct ProgramMetadataUpdated {
+    pub name: String,
+    pub version: String,
+    pub config_uri: String,
+    pub support_contact: String,
+}
+
+#[event]
+pub struct RaffleMetadataUpdated {
+    pub token_raffle: Pubkey,
+    pub name: String,
+    pub description: String,
+    pub image_uri: String,
+}
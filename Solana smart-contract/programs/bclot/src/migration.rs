@@ -0,0 +1,725 @@
+use anchor_lang::prelude::*;
+use crate::{
+    CommissionMode,
+    PriceSampleMode,
+    PricingMode,
+    Round,
+    RoundStatus,
+    RoundTickets,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    ROUND_DURATION,
+    SOL_DECIMALS,
+    transfer,
+};
+use crate::commission::CommissionVault;
+use crate::oracle_failover::{FeedFailover, FeedRoute};
+use crate::player_stats::PlayerStats;
+use crate::price_feeds::{FeedRegistry, PegComponent, RegisteredFeed, DEFAULT_TICKET_SATOSHIS, MAX_PEG_COMPONENTS};
+use crate::staking::StakingConfig;
+use crate::state::AccountKind;
+use crate::RoundTicketsPurchase;
+
+// v1 - original layouts
+// v2 - RaffleState vesting, promo, insurance, jackpot, maintenance and governance fields
+// v3 - fields only appended after bump. RaffleState is rebuilt by migrate_raffle_state, every other
+// account is grown in place by migrate_account. Any later field is appended and bumps this again
+// v4 - RaffleState price_cache_enabled
+// v5 - RaffleState feed_registry_enabled
+// v6 - Round sponsor_match
+// v7 - RoundTicketsPurchase jackpot split and earned rewards, kept for cancel_purchase
+// v8 - Round house_rolled
+pub const CURRENT_SCHEMA_VERSION: u16 = 8;
+
+// Authority is the first field in every RaffleState layout
+const AUTHORITY_OFFSET: usize = 8;
+
+// RoundTickets is accessed by raw offsets: discriminator + round + tickets + len + bump
+pub const ROUND_TICKETS_VERSION_OFFSET: usize = 8 + 32 + (1024 * 4) + (1024 * 4) + 4 + 1;
+
+// Every state struct carries its layout version, checked when the account is loaded
+pub trait Versioned {
+    const VERSION: u8;
+
+    fn version(&self) -> u8;
+
+    fn check_version(&self) -> Result<()> {
+        require_eq!(effective_version(self.version()), Self::VERSION, RaffleError::SchemaVersionMismatch);
+        Ok(())
+    }
+}
+
+// Accounts created before the version field existed read 0, they are in the v1 layout
+pub fn effective_version(version: u8) -> u8 {
+    if version == 0 { 1 } else { version }
+}
+
+impl Versioned for RaffleState {
+    const VERSION: u8 = 5;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for TokenRaffle {
+    const VERSION: u8 = 2;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for Round {
+    const VERSION: u8 = 4;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+impl Versioned for RoundTickets {
+    const VERSION: u8 = 1;
+
+    fn version(&self) -> u8 {
+        self.version
+    }
+}
+
+// For callers holding raw RoundTickets data instead of a bytemuck view
+pub fn check_round_tickets_version(tickets_data: &[u8]) -> Result<()> {
+    require!(
+        tickets_data.len() > ROUND_TICKETS_VERSION_OFFSET,
+        RaffleError::InvalidAccountSize
+    );
+    require_eq!(
+        effective_version(tickets_data[ROUND_TICKETS_VERSION_OFFSET]),
+        RoundTickets::VERSION,
+        RaffleError::SchemaVersionMismatch
+    );
+    Ok(())
+}
+
+// Only authority. Detects the schema of the existing RaffleState by its size
+pub fn initialize_program_version(ctx: Context<InitializeProgramVersion>) -> Result<()> {
+    let raffle_state_info = ctx.accounts.raffle_state.to_account_info();
+    require_raffle_state_authority(&raffle_state_info, &ctx.accounts.authority.key())?;
+
+    let schema_version = match raffle_state_info.data_len() - 8 {
+        RaffleStateV1::SIZE => 1,
+        RaffleStateV2::SIZE => 2,
+        RaffleStateV3::INIT_SPACE => 3,
+        RaffleStateV4::INIT_SPACE => 4,
+        _ => CURRENT_SCHEMA_VERSION,
+    };
+
+    let program_version = &mut ctx.accounts.program_version;
+    program_version.schema_version = schema_version;
+    program_version.bump = ctx.bumps.program_version;
+
+    msg!("✅ Program version initialized, schema v{}", schema_version);
+
+    Ok(())
+}
+
+// Only authority. Reallocates RaffleState from an older layout to the current one keeping the
+// values it had. v1 and v2 are rebuilt, from v3 on fields were only appended and it is grown
+pub fn migrate_raffle_state(ctx: Context<MigrateRaffleState>) -> Result<()> {
+    let from_version = ctx.accounts.program_version.schema_version;
+    require!(
+        from_version < CURRENT_SCHEMA_VERSION,
+        RaffleError::AccountAlreadyMigrated
+    );
+
+    let raffle_state_info = ctx.accounts.raffle_state.to_account_info();
+    require_raffle_state_authority(&raffle_state_info, &ctx.accounts.authority.key())?;
+    {
+        let data = raffle_state_info.try_borrow_data()?;
+        require!(
+            data[..8] == *RaffleState::DISCRIMINATOR,
+            RaffleError::SchemaVersionMismatch
+        );
+    }
+
+    let payer = ctx.accounts.authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    match from_version {
+        1 | 2 => rebuild_raffle_state(&raffle_state_info, &payer, &system_program, from_version)?,
+        3 => {
+            grow_account::<RaffleState, RaffleStateV3>(&raffle_state_info, &payer, &system_program, |raffle_state| {
+                raffle_state.version = RaffleState::VERSION;
+            })?
+        }
+        4 => {
+            grow_account::<RaffleState, RaffleStateV4>(&raffle_state_info, &payer, &system_program, |raffle_state| {
+                raffle_state.version = RaffleState::VERSION;
+            })?
+        }
+        // Unchanged since
+        5..=7 => {}
+        _ => return err!(RaffleError::SchemaVersionMismatch),
+    }
+
+    ctx.accounts.program_version.schema_version = CURRENT_SCHEMA_VERSION;
+
+    msg!("✅ RaffleState migrated to schema v{}", CURRENT_SCHEMA_VERSION);
+
+    emit!(SchemaMigrated {
+        account: raffle_state_info.key(),
+        from_version,
+        to_version: CURRENT_SCHEMA_VERSION,
+    });
+
+    Ok(())
+}
+
+// The v1 and v2 layouts had fields before bump, every field is rewritten
+fn rebuild_raffle_state<'info>(
+    raffle_state_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    from_version: u16,
+) -> Result<()> {
+    let old = {
+        let data = raffle_state_info.try_borrow_data()?;
+        match from_version {
+            1 => RaffleStateV2::from(RaffleStateV1::deserialize(&mut &data[8..])?),
+            _ => RaffleStateV2::deserialize(&mut &data[8..])?,
+        }
+    };
+
+    let new_space = 8 + RaffleState::INIT_SPACE;
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(raffle_state_info.lamports());
+
+    if rent_needed > 0 {
+        transfer(
+            system_program.clone(),
+            payer.clone(),
+            raffle_state_info.clone(),
+            rent_needed,
+            None,
+        )?;
+    }
+
+    raffle_state_info.resize(new_space)?;
+
+    let migrated = RaffleState {
+        authority: old.authority,
+        entrance_fee_percentage: old.entrance_fee_percentage,
+        beneficiary: old.beneficiary,
+        created_at: old.created_at,
+        vrf_request_counter: old.vrf_request_counter,
+        bump: old.bump,
+        version: RaffleState::VERSION,
+        vesting_threshold: old.vesting_threshold,
+        vesting_cliff_seconds: old.vesting_cliff_seconds,
+        vesting_duration_seconds: old.vesting_duration_seconds,
+        operator: old.operator,
+        insurance_bps: old.insurance_bps,
+        jackpot_bps: old.jackpot_bps,
+        maintenance_start: old.maintenance_start,
+        maintenance_end: old.maintenance_end,
+        max_price_deviation_bps: old.max_price_deviation_bps,
+        watchtower: old.watchtower,
+        pending_authority: old.pending_authority,
+        keeper_tip: 0,
+        keeper_allowlist_enabled: false,
+        randomness_cooldown_seconds: 0,
+        max_randomness_requests: 0,
+        ticket_price_satoshis: DEFAULT_TICKET_SATOSHIS,
+        ticket_peg: Vec::new(),
+        blocklist_enabled: false,
+        max_price_confidence_bps: 0,
+        cluster_profile_enabled: false,
+        price_cache_enabled: false,
+        feed_registry_enabled: false,
+    };
+
+    let mut data = raffle_state_info.try_borrow_mut_data()?;
+    let mut writer = &mut data[..];
+    migrated.try_serialize(&mut writer)?;
+
+    Ok(())
+}
+
+// Permissionless. Accounts other than RaffleState kept their original fields in place, the
+// newer ones follow bump. Growing the account and zeroing the new tail reads those as
+// zero, None and empty, a few get their creation defaults
+pub fn migrate_account(ctx: Context<MigrateAccount>) -> Result<()> {
+    let account_info = ctx.accounts.account.to_account_info();
+    let payer = ctx.accounts.payer.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let kind = {
+        let data = account_info.try_borrow_data()?;
+        AccountKind::from_account_data(&data).ok_or(RaffleError::SchemaVersionMismatch)?
+    };
+
+    match kind {
+        AccountKind::TokenRaffle => {
+            grow_account::<TokenRaffle, TokenRaffleV1>(&account_info, &payer, &system_program, |sol_raffle| {
+                sol_raffle.version = TokenRaffle::VERSION;
+                sol_raffle.round_duration = ROUND_DURATION;
+                sol_raffle.token_decimals = SOL_DECIMALS;
+            })?
+        }
+        // Sized at creation, a v2 round already has the version field
+        AccountKind::Round if account_info.data_len() == 8 + RoundV3::INIT_SPACE => {
+            grow_account::<Round, RoundV3>(&account_info, &payer, &system_program, |round| {
+                round.version = Round::VERSION;
+            })?
+        }
+        AccountKind::Round if account_info.data_len() == 8 + RoundV2::INIT_SPACE => {
+            grow_account::<Round, RoundV2>(&account_info, &payer, &system_program, |round| {
+                round.version = Round::VERSION;
+            })?
+        }
+        AccountKind::Round => {
+            grow_account::<Round, RoundV1>(&account_info, &payer, &system_program, |round| {
+                round.version = Round::VERSION;
+            })?
+        }
+        // Zeroed rewards are never clawed back, a None jackpot_bps refunds at the current share
+        AccountKind::RoundTicketsPurchase if account_info.data_len() == 8 + RoundTicketsPurchaseV2::INIT_SPACE => {
+            grow_account::<RoundTicketsPurchase, RoundTicketsPurchaseV2>(&account_info, &payer, &system_program, |_| {})?
+        }
+        AccountKind::RoundTicketsPurchase => {
+            grow_account::<RoundTicketsPurchase, RoundTicketsPurchaseV1>(&account_info, &payer, &system_program, |_| {})?
+        }
+        AccountKind::PlayerStats => {
+            grow_account::<PlayerStats, PlayerStatsV1>(&account_info, &payer, &system_program, |_| {})?
+        }
+        AccountKind::CommissionVault => {
+            grow_account::<CommissionVault, CommissionVaultV1>(&account_info, &payer, &system_program, |_| {})?
+        }
+        AccountKind::StakingConfig => {
+            grow_account::<StakingConfig, StakingConfigV1>(&account_info, &payer, &system_program, |_| {})?
+        }
+        AccountKind::FeedFailover => {
+            grow_account::<FeedFailover, FeedFailoverV1>(&account_info, &payer, &system_program, |_| {})?
+        }
+        AccountKind::FeedRegistry => {
+            grow_account::<FeedRegistry, FeedRegistryV1>(&account_info, &payer, &system_program, |_| {})?
+        }
+        _ => return err!(RaffleError::AccountAlreadyMigrated),
+    }
+
+    msg!("✅ {:?} {} migrated to schema v{}", kind, account_info.key(), CURRENT_SCHEMA_VERSION);
+
+    emit!(SchemaMigrated {
+        account: account_info.key(),
+        from_version: 1,
+        to_version: CURRENT_SCHEMA_VERSION,
+    });
+
+    Ok(())
+}
+
+// `L` is the original layout of `T`, read only to find where its data ends. Borsh options and
+// vectors vary in length, bytes past the end may be left over from a longer earlier value
+fn grow_account<'info, T, L>(
+    account_info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    defaults: impl FnOnce(&mut T),
+) -> Result<()>
+where
+    T: AccountSerialize + AccountDeserialize + Space,
+    L: AnchorDeserialize,
+{
+    let new_space = 8 + T::INIT_SPACE;
+    require!(
+        account_info.data_len() < new_space,
+        RaffleError::AccountAlreadyMigrated
+    );
+
+    let end = {
+        let data = account_info.try_borrow_data()?;
+        let mut rest = &data[8..];
+        L::deserialize(&mut rest)?;
+        data.len() - rest.len()
+    };
+
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_space)
+        .saturating_sub(account_info.lamports());
+
+    if rent_needed > 0 {
+        transfer(
+            system_program.clone(),
+            payer.clone(),
+            account_info.clone(),
+            rent_needed,
+            None,
+        )?;
+    }
+
+    account_info.resize(new_space)?;
+
+    let mut data = account_info.try_borrow_mut_data()?;
+    data[end..].fill(0);
+
+    let mut account = T::try_deserialize(&mut &data[..])?;
+    defaults(&mut account);
+
+    let mut writer = &mut data[..];
+    account.try_serialize(&mut writer)?;
+
+    Ok(())
+}
+
+fn require_raffle_state_authority(raffle_state_info: &AccountInfo, signer: &Pubkey) -> Result<()> {
+    let data = raffle_state_info.try_borrow_data()?;
+    require!(
+        data.len() >= AUTHORITY_OFFSET + 32,
+        RaffleError::InvalidAccountSize
+    );
+
+    let authority = Pubkey::try_from(&data[AUTHORITY_OFFSET..AUTHORITY_OFFSET + 32])
+        .map_err(|_| RaffleError::InvalidAccountSize)?;
+    require_keys_eq!(authority, *signer, RaffleError::Unauthorized);
+
+    Ok(())
+}
+
+// Account validation structs
+// raffle_state is unchecked: an outdated layout can't be deserialized as RaffleState
+#[derive(Accounts)]
+pub struct InitializeProgramVersion<'info> {
+    /// CHECK: PDA verified by seeds, authority read from raw data
+    #[account(
+        seeds = [b"raffle_state"],
+        bump,
+        owner = crate::id()
+    )]
+    pub raffle_state: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ProgramVersion::INIT_SPACE,
+        seeds = [b"program_version"],
+        bump
+    )]
+    pub program_version: Account<'info, ProgramVersion>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateAccount<'info> {
+    /// CHECK: any program account, its type is read from the discriminator
+    #[account(
+        mut,
+        owner = crate::id()
+    )]
+    pub account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRaffleState<'info> {
+    /// CHECK: PDA verified by seeds, layout checked by program_version
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump,
+        owner = crate::id()
+    )]
+    pub raffle_state: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"program_version"],
+        bump = program_version.bump
+    )]
+    pub program_version: Account<'info, ProgramVersion>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct ProgramVersion {
+    pub schema_version: u16,
+    pub bump: u8,
+}
+
+// Original RaffleState layout, used only to read accounts before migration
+#[derive(AnchorDeserialize)]
+pub struct RaffleStateV1 {
+    pub authority: Pubkey,
+    pub entrance_fee_percentage: u8,
+    pub beneficiary: Pubkey,
+    pub created_at: i64,
+    pub vrf_request_counter: u8,
+    pub bump: u8,
+}
+
+impl RaffleStateV1 {
+    pub const SIZE: usize = 32 + 1 + 32 + 8 + 1 + 1;
+}
+
+// Schema v2 RaffleState layout, the new fields were placed before bump
+#[derive(AnchorDeserialize)]
+pub struct RaffleStateV2 {
+    pub authority: Pubkey,
+    pub entrance_fee_percentage: u8,
+    pub beneficiary: Pubkey,
+    pub created_at: i64,
+    pub vrf_request_counter: u8,
+    pub vesting_threshold: u64,
+    pub vesting_cliff_seconds: i64,
+    pub vesting_duration_seconds: i64,
+    pub operator: Pubkey,
+    pub insurance_bps: u16,
+    pub jackpot_bps: u16,
+    pub maintenance_start: i64,
+    pub maintenance_end: i64,
+    pub max_price_deviation_bps: u16,
+    pub watchtower: Pubkey,
+    pub pending_authority: Pubkey,
+    pub bump: u8,
+}
+
+impl RaffleStateV2 {
+    pub const SIZE: usize = RaffleStateV1::SIZE + 8 + 8 + 8 + 32 + 2 + 2 + 8 + 8 + 2 + 32 + 32;
+}
+
+impl From<RaffleStateV1> for RaffleStateV2 {
+    fn from(old: RaffleStateV1) -> Self {
+        RaffleStateV2 {
+            authority: old.authority,
+            entrance_fee_percentage: old.entrance_fee_percentage,
+            beneficiary: old.beneficiary,
+            created_at: old.created_at,
+            vrf_request_counter: old.vrf_request_counter,
+            vesting_threshold: 0,
+            vesting_cliff_seconds: 0,
+            vesting_duration_seconds: 0,
+            operator: Pubkey::default(),
+            insurance_bps: 0,
+            jackpot_bps: 0,
+            maintenance_start: 0,
+            maintenance_end: 0,
+            max_price_deviation_bps: 0,
+            watchtower: Pubkey::default(),
+            pending_authority: Pubkey::default(),
+            bump: old.bump,
+        }
+    }
+}
+
+// Schema v3 RaffleState layout, the current one is grown from it
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RaffleStateV3 {
+    pub authority: Pubkey,
+    pub entrance_fee_percentage: u8,
+    pub beneficiary: Pubkey,
+    pub created_at: i64,
+    pub vrf_request_counter: u8,
+    pub bump: u8,
+    pub version: u8,
+    pub vesting_threshold: u64,
+    pub vesting_cliff_seconds: i64,
+    pub vesting_duration_seconds: i64,
+    pub operator: Pubkey,
+    pub insurance_bps: u16,
+    pub jackpot_bps: u16,
+    pub maintenance_start: i64,
+    pub maintenance_end: i64,
+    pub max_price_deviation_bps: u16,
+    pub watchtower: Pubkey,
+    pub pending_authority: Pubkey,
+    pub keeper_tip: u64,
+    pub keeper_allowlist_enabled: bool,
+    pub randomness_cooldown_seconds: i64,
+    pub max_randomness_requests: u8,
+    pub ticket_price_satoshis: u64,
+    #[max_len(MAX_PEG_COMPONENTS)]
+    pub ticket_peg: Vec<PegComponent>,
+    pub blocklist_enabled: bool,
+    pub max_price_confidence_bps: u16,
+    pub cluster_profile_enabled: bool,
+}
+
+// Schema v4 RaffleState layout
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RaffleStateV4 {
+    pub v3: RaffleStateV3,
+    pub price_cache_enabled: bool,
+}
+
+// Original layouts of the accounts grown by migrate_account, each a prefix of the current one
+#[derive(AnchorDeserialize)]
+pub struct TokenRaffleV1 {
+    pub authority: Pubkey,
+    pub token_mint: Pubkey,
+    pub current_round_id: Option<u32>,
+    pub current_round_status: RoundStatus,
+    pub current_round_end_time: Option<i64>,
+    pub total_rounds: u32,
+    pub pending_rounds: Vec<u32>,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RoundV1 {
+    pub initialized: bool,
+    pub token_raffle: Pubkey,
+    pub round_id: u32,
+    pub status: RoundStatus,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub prize_amount: u64,
+    pub commission_balance: u64,
+    pub purchases_count: u32,
+    pub total_tickets: u32,
+    pub winner_ticket_index: Option<u32>,
+    pub winner_purchase_index: Option<u32>,
+    pub winner_address: Option<Pubkey>,
+    pub prize_claimed: bool,
+    pub bump: u8,
+}
+
+// Schema v2 Round layout
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RoundV2 {
+    pub v1: RoundV1,
+    pub version: u8,
+    pub sponsored_amount: u64,
+    pub free_entry: bool,
+    pub free_entry_max_tickets: u32,
+    pub commission_settled: bool,
+    pub jackpot_amount: u64,
+    pub mega_round: bool,
+    pub open_ticket_price: u64,
+    pub open_sol_price_usd: u64,
+    pub open_reference_price_usd: u64,
+    pub open_price_slot: u64,
+    pub commit_price: bool,
+    pub ticket_price_lamports: u64,
+    pub price_sample_mode: PriceSampleMode,
+    pub price_sample_count: u8,
+    pub frozen: bool,
+    pub request_tip_paid: bool,
+    pub last_request_time: i64,
+    pub request_count: u8,
+    pub jackpot_contributed: u64,
+    pub keeper_tips_paid: u64,
+    pub refunded_amount: u64,
+    pub commission_mode: CommissionMode,
+    pub commission_accrued: u64,
+    pub staked_lamports: u64,
+    pub stake_pool_tokens: u64,
+    pub staking_yield: u64,
+    pub staking_loss: u64,
+    pub pricing_mode: PricingMode,
+    pub fixed_ticket_price_usd: u64,
+    pub access_root: Option<[u8; 32]>,
+    pub prize_target_lamports: Option<u64>,
+    pub prize_cap_lamports: Option<u64>,
+    pub carryover_in: u64,
+    pub carryover_out: u64,
+    pub min_prize_lamports: u64,
+    pub prize_top_up: u64,
+    pub house_tickets: u32,
+    pub house_owner: Pubkey,
+    pub completed_at: i64,
+    pub redraw_pending: bool,
+    pub first_winner_purchase_index: Option<u32>,
+    pub first_winner_ticket_index: Option<u32>,
+    pub first_winner_address: Option<Pubkey>,
+    pub randomness: [u8; 64],
+    pub vrf_request: Pubkey,
+    pub locked_at: i64,
+    pub locked_total_tickets: u32,
+    pub locked_purchases_count: u32,
+    pub locked_prize_amount: u64,
+}
+
+// Schema v6 Round layout
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RoundV3 {
+    pub v2: RoundV2,
+    pub sponsor_match: Pubkey,
+}
+
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RoundTicketsPurchaseV1 {
+    pub round: Pubkey,
+    pub player: Pubkey,
+    pub purchase_index: u32,
+    pub tickets_count: u32,
+    pub bump: u8,
+}
+
+// Schema v6 RoundTicketsPurchase layout
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct RoundTicketsPurchaseV2 {
+    pub v1: RoundTicketsPurchaseV1,
+    pub bonus_tickets: u32,
+    pub ticket_price: u64,
+    pub oracle_price: u64,
+    pub price_slot: u64,
+    pub fee_bps: u16,
+    pub cancelled: bool,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct PlayerStatsV1 {
+    pub player: Pubkey,
+    pub last_round_id: Option<u32>,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub rounds_entered: u32,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct CommissionVaultV1 {
+    pub balance: u64,
+    pub total_accrued: u64,
+    pub total_withdrawn: u64,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct StakingConfigV1 {
+    pub stake_pool: Pubkey,
+    pub pool_mint: Pubkey,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct FeedFailoverV1 {
+    pub routes: Vec<FeedRoute>,
+    pub bump: u8,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct FeedRegistryV1 {
+    pub feeds: Vec<RegisteredFeed>,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct SchemaMigrated {
+    pub account: Pubkey,
+    pub from_version: u16,
+    pub to_version: u16,
+}
@@ -0,0 +1,534 @@
+use anchor_lang::prelude::*;
+use rust_decimal::Decimal;
+use crate::{
+    RaffleError,
+    RaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::cluster_profile::ClusterProfile;
+use crate::{
+    PriceSampleMode,
+};
+use crate::price_cache::{CachedPrice, PriceCache};
+use crate::price_feeds::{check_feed_account, sol_price_slot, FeedRegistry, PriceSampling, USD_DECIMALS};
+
+pub const MAX_FAILOVER_ROUTES: usize = 8;
+pub const MAX_FALLBACK_FEEDS: usize = 3;
+pub const MAX_EMERGENCY_PRICE_SLOTS: u64 = 216_000; // ~1 day
+
+// Only authority
+pub fn initialize_feed_failover(ctx: Context<InitializeFeedFailover>) -> Result<()> {
+    let feed_failover = &mut ctx.accounts.feed_failover;
+    feed_failover.routes = Vec::new();
+    feed_failover.emergency_price = None;
+    feed_failover.bump = ctx.bumps.feed_failover;
+
+    msg!("✅ Feed failover initialized");
+
+    Ok(())
+}
+
+// Only authority. Feeds tried in order when `primary` is stale or fails its checks, each a
+// Switchboard pull feed of the same asset, typically aggregating other providers.
+// Empty `fallbacks` removes the route. remaining_accounts: every fallback feed, in order
+pub fn set_feed_failover(ctx: Context<UpdateFeedFailover>, primary: Pubkey, fallbacks: Vec<Pubkey>) -> Result<()> {
+    require!(
+        fallbacks.len() <= MAX_FALLBACK_FEEDS && ctx.remaining_accounts.len() == fallbacks.len(),
+        RaffleError::InvalidFeedFailover
+    );
+
+    for (index, (fallback, feed)) in fallbacks.iter().zip(ctx.remaining_accounts.iter()).enumerate() {
+        require!(
+            *fallback != primary
+                && *fallback == feed.key()
+                && !fallbacks[..index].contains(fallback),
+            RaffleError::InvalidFeedFailover
+        );
+        check_feed_account(feed)?;
+    }
+
+    let feed_failover = &mut ctx.accounts.feed_failover;
+    feed_failover.routes.retain(|route| route.primary != primary);
+    if !fallbacks.is_empty() {
+        require!(
+            feed_failover.routes.len() < MAX_FAILOVER_ROUTES,
+            RaffleError::FeedFailoverFull
+        );
+        feed_failover.routes.push(FeedRoute {
+            primary,
+            fallbacks: fallbacks.clone(),
+        });
+    }
+
+    emit!(FeedFailoverUpdated {
+        primary,
+        fallbacks: fallbacks.clone(),
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::FeedFailoverChanged,
+        fallbacks.len() as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority, meant to be the SPL Governance PDA so the override passes its vote and hold-up
+// time. Prices in USD_DECIMALS, used for the passed feeds only once they and all their fallbacks
+// fail, until expiry_slot
+pub fn set_emergency_price(
+    ctx: Context<SetEmergencyPrice>,
+    reference_usd: u64,
+    sol_usd: u64,
+    expiry_slot: u64,
+) -> Result<()> {
+    let slot = Clock::get()?.slot;
+    require!(
+        reference_usd > 0
+            && reference_usd <= i64::MAX as u64
+            && sol_usd > 0
+            && sol_usd <= i64::MAX as u64
+            && expiry_slot > slot
+            && expiry_slot - slot <= MAX_EMERGENCY_PRICE_SLOTS,
+        RaffleError::InvalidEmergencyPrice
+    );
+    check_feed_account(&ctx.accounts.reference_price_feed)?;
+    check_feed_account(&ctx.accounts.sol_price_feed)?;
+
+    let emergency_price = EmergencyPrice {
+        reference_feed: ctx.accounts.reference_price_feed.key(),
+        reference_usd,
+        sol_feed: ctx.accounts.sol_price_feed.key(),
+        sol_usd,
+        expiry_slot,
+    };
+
+    msg!("🚨 Emergency price set until slot {}", expiry_slot);
+
+    emit!(EmergencyPriceSet {
+        reference_feed: emergency_price.reference_feed,
+        reference_usd,
+        sol_feed: emergency_price.sol_feed,
+        sol_usd,
+        expiry_slot,
+    });
+
+    ctx.accounts.feed_failover.emergency_price = Some(emergency_price);
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::EmergencyPriceChanged,
+        expiry_slot,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Ends the override before its expiry
+pub fn clear_emergency_price(ctx: Context<UpdateFeedFailover>) -> Result<()> {
+    let feed_failover = &mut ctx.accounts.feed_failover;
+    require!(feed_failover.emergency_price.is_some(), RaffleError::NoEmergencyPrice);
+    feed_failover.emergency_price = None;
+
+    emit!(EmergencyPriceCleared {
+        slot: Clock::get()?.slot,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::EmergencyPriceChanged,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Reads a feed, from the price cache while fresh in Latest sampling, moving on to the fallbacks
+// of its route when it fails. Fallback feeds are looked up by key among `feeds`, those not passed
+// are skipped. An unexpired emergency price for the feed is the last resort, used only once every
+// fallback was passed and failed, so leaving one out can't force it. With a cluster profile every
+// feed read must be owned by its oracle program
+#[derive(Clone, Copy)]
+pub struct FeedReader<'a, 'info> {
+    feed_registry: Option<&'a FeedRegistry>,
+    price_cache: Option<&'a PriceCache>,
+    cluster_profile: Option<&'a ClusterProfile>,
+    routes: &'a [FeedRoute],
+    emergency_price: Option<&'a EmergencyPrice>,
+    feeds: &'a [AccountInfo<'info>],
+}
+
+impl<'a, 'info> FeedReader<'a, 'info> {
+    pub fn new(
+        feed_registry: Option<&'a FeedRegistry>,
+        feed_failover: Option<&'a FeedFailover>,
+        price_cache: Option<&'a PriceCache>,
+        cluster_profile: Option<&'a ClusterProfile>,
+        feeds: &'a [AccountInfo<'info>],
+    ) -> Self {
+        Self {
+            feed_registry,
+            price_cache,
+            cluster_profile,
+            routes: feed_failover.map(|feed_failover| &feed_failover.routes[..]).unwrap_or(&[]),
+            emergency_price: feed_failover.and_then(|feed_failover| feed_failover.emergency_price.as_ref()),
+            feeds,
+        }
+    }
+
+    // Per-feed read limits, the defaults without a registry
+    pub fn feed_registry(&self) -> Option<&'a FeedRegistry> {
+        self.feed_registry
+    }
+
+    // The first price `read` accepts, the primary feed's error if none does
+    pub fn read(
+        &self,
+        primary: &AccountInfo,
+        sampling: &PriceSampling,
+        read: impl Fn(&AccountInfo) -> Result<Decimal>,
+    ) -> Result<Decimal> {
+        self.check_cluster(primary)?;
+        if let Some(price) = self.cached_price(&primary.key(), sampling)? {
+            msg!("Cached price of {}: {}", primary.key(), price);
+            return Ok(price);
+        }
+
+        let primary_error = match read(primary) {
+            Ok(price) => return Ok(price),
+            Err(error) => error,
+        };
+
+        let fallbacks = self
+            .routes
+            .iter()
+            .find(|route| route.primary == primary.key())
+            .map(|route| &route.fallbacks[..])
+            .unwrap_or(&[]);
+
+        let mut fallback_missing = false;
+        for fallback in fallbacks {
+            let Some(feed) = self.feeds.iter().find(|feed| feed.key == fallback) else {
+                msg!("Fallback feed {} not passed", fallback);
+                fallback_missing = true;
+                continue;
+            };
+            self.check_cluster(feed)?;
+
+            match read(feed) {
+                Ok(price) => {
+                    msg!("⚠️ Feed {} failed, priced from fallback {}", primary.key(), fallback);
+                    return Ok(price);
+                }
+                Err(error) => msg!("Fallback feed {} failed: {:?}", fallback, error),
+            }
+        }
+
+        let slot = Clock::get()?.slot;
+        let Some((price_usd, expiry_slot)) = self.emergency_usd(&primary.key(), slot) else {
+            return Err(primary_error);
+        };
+        if fallback_missing {
+            msg!("Emergency price of {} needs every fallback passed", primary.key());
+            return Err(primary_error);
+        }
+
+        msg!("🚨 Feed {} failed, priced at emergency {} micro-USD", primary.key(), price_usd);
+
+        emit!(EmergencyPriceUsed {
+            feed: primary.key(),
+            price_usd,
+            expiry_slot,
+            slot,
+        });
+
+        Ok(Decimal::new(price_usd as i64, USD_DECIMALS as u32))
+    }
+
+    fn check_cluster(&self, feed: &AccountInfo) -> Result<()> {
+        match self.cluster_profile {
+            Some(cluster_profile) => cluster_profile.check_feed(feed),
+            None => Ok(()),
+        }
+    }
+
+    // Slot of the SOL price a purchase was priced with, kept for audits. The cache refresh when
+    // the cache supplied it, the feed isn't parsed then
+    pub fn price_slot(&self, sol_price_feed: &AccountInfo, sampling: &PriceSampling) -> Result<u64> {
+        match self.cached_entry(&sol_price_feed.key(), sampling)? {
+            Some(entry) => Ok(entry.refreshed_slot),
+            None => sol_price_slot(sol_price_feed),
+        }
+    }
+
+    fn cached_price(&self, feed: &Pubkey, sampling: &PriceSampling) -> Result<Option<Decimal>> {
+        Ok(self
+            .cached_entry(feed, sampling)?
+            .map(|entry| Decimal::deserialize(entry.price)))
+    }
+
+    fn cached_entry(&self, feed: &Pubkey, sampling: &PriceSampling) -> Result<Option<&'a CachedPrice>> {
+        match self.price_cache {
+            Some(price_cache) if sampling.mode == PriceSampleMode::Latest => {
+                Ok(price_cache.fresh_entry(feed, Clock::get()?.slot))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn emergency_usd(&self, feed: &Pubkey, slot: u64) -> Option<(u64, u64)> {
+        let emergency_price = self.emergency_price.filter(|emergency_price| slot < emergency_price.expiry_slot)?;
+
+        if *feed == emergency_price.reference_feed {
+            Some((emergency_price.reference_usd, emergency_price.expiry_slot))
+        } else if *feed == emergency_price.sol_feed {
+            Some((emergency_price.sol_usd, emergency_price.expiry_slot))
+        } else {
+            None
+        }
+    }
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeFeedFailover<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeedFailover::INIT_SPACE,
+        seeds = [b"feed_failover"],
+        bump
+    )]
+    pub feed_failover: Account<'info, FeedFailover>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateFeedFailover<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Account<'info, FeedFailover>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetEmergencyPrice<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Account<'info, FeedFailover>,
+
+    /// CHECK: parsed as a Switchboard pull feed, priced at reference_usd during the override
+    pub reference_price_feed: AccountInfo<'info>,
+
+    /// CHECK: parsed as a Switchboard pull feed, priced at sol_usd during the override
+    pub sol_price_feed: AccountInfo<'info>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Account data structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct FeedRoute {
+    pub primary: Pubkey,
+    #[max_len(MAX_FALLBACK_FEEDS)]
+    pub fallbacks: Vec<Pubkey>, // in priority order
+}
+
+// Manual USD prices in USD_DECIMALS of the reference and SOL feeds
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct EmergencyPrice {
+    pub reference_feed: Pubkey,
+    pub reference_usd: u64,
+    pub sol_feed: Pubkey,
+    pub sol_usd: u64,
+    pub expiry_slot: u64, // ignored from this slot on
+}
+
+// Ordered fallbacks per feed, shared by every raffle
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct FeedFailover {
+    #[max_len(MAX_FAILOVER_ROUTES)]
+    pub routes: Vec<FeedRoute>,
+    pub bump: u8,
+    pub emergency_price: Option<EmergencyPrice>, // None - no override
+}
+
+// Events
+#[event]
+pub struct EmergencyPriceCleared {
+    pub slot: u64,
+}
+
+#[event]
+pub struct EmergencyPriceSet {
+    pub reference_feed: Pubkey,
+    pub reference_usd: u64,
+    pub sol_feed: Pubkey,
+    pub sol_usd: u64,
+    pub expiry_slot: u64,
+}
+
+// Emitted by every purchase priced with the override
+#[event]
+pub struct EmergencyPriceUsed {
+    pub feed: Pubkey,
+    pub price_usd: u64,
+    pub expiry_slot: u64,
+    pub slot: u64,
+}
+
+#[event]
+pub struct FeedFailoverUpdated {
+    pub primary: Pubkey,
+    pub fallbacks: Vec<Pubkey>, // empty - route removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_stubs::{set_syscall_stubs, SyscallStubs};
+
+    const SLOT: u64 = 1_000;
+    const EMERGENCY_USD: u64 = 150_000_000;
+
+    struct ClockStub;
+
+    impl SyscallStubs for ClockStub {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe {
+                *(var_addr as *mut Clock) = Clock {
+                    slot: SLOT,
+                    ..Clock::default()
+                };
+            }
+            0
+        }
+    }
+
+    fn feed_failover(primary: Pubkey, fallbacks: Vec<Pubkey>) -> FeedFailover {
+        FeedFailover {
+            routes: vec![FeedRoute { primary, fallbacks }],
+            bump: 255,
+            emergency_price: Some(EmergencyPrice {
+                reference_feed: Pubkey::new_unique(),
+                reference_usd: 1_000_000,
+                sol_feed: primary,
+                sol_usd: EMERGENCY_USD,
+                expiry_slot: SLOT + 10,
+            }),
+        }
+    }
+
+    // Read failing on every feed
+    fn fail(_feed: &AccountInfo) -> Result<Decimal> {
+        err!(RaffleError::InvalidFeedAccount)
+    }
+
+    #[test]
+    fn emergency_price_needs_every_fallback_passed() {
+        set_syscall_stubs(Box::new(ClockStub));
+
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let owner = Pubkey::new_unique();
+        let (mut lamports_0, mut lamports_1, mut lamports_2) = (0, 0, 0);
+        let (mut data_0, mut data_1, mut data_2) = ([0u8; 0], [0u8; 0], [0u8; 0]);
+        let primary = AccountInfo::new(&keys[0], false, false, &mut lamports_0, &mut data_0, &owner, false, 0);
+        let fallback_1 = AccountInfo::new(&keys[1], false, false, &mut lamports_1, &mut data_1, &owner, false, 0);
+        let fallback_2 = AccountInfo::new(&keys[2], false, false, &mut lamports_2, &mut data_2, &owner, false, 0);
+        let feed_failover = feed_failover(keys[0], vec![keys[1], keys[2]]);
+
+        let feeds = [fallback_1.clone()];
+        let reader = FeedReader::new(None, Some(&feed_failover), None, None, &feeds);
+        assert_eq!(
+            reader.read(&primary, &PriceSampling::LATEST, fail).unwrap_err(),
+            RaffleError::InvalidFeedAccount.into()
+        );
+
+        let feeds = [fallback_1, fallback_2];
+        let reader = FeedReader::new(None, Some(&feed_failover), None, None, &feeds);
+        assert_eq!(
+            reader.read(&primary, &PriceSampling::LATEST, fail).unwrap(),
+            Decimal::new(EMERGENCY_USD as i64, USD_DECIMALS as u32)
+        );
+    }
+
+    #[test]
+    fn passed_fallback_prices_failed_primary() {
+        set_syscall_stubs(Box::new(ClockStub));
+
+        let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let owner = Pubkey::new_unique();
+        let (mut lamports_0, mut lamports_2) = (0, 0);
+        let (mut data_0, mut data_2) = ([0u8; 0], [0u8; 0]);
+        let primary = AccountInfo::new(&keys[0], false, false, &mut lamports_0, &mut data_0, &owner, false, 0);
+        let fallback_2 = AccountInfo::new(&keys[2], false, false, &mut lamports_2, &mut data_2, &owner, false, 0);
+        let feed_failover = feed_failover(keys[0], vec![keys[1], keys[2]]);
+
+        let feeds = [fallback_2];
+        let reader = FeedReader::new(None, Some(&feed_failover), None, None, &feeds);
+        let price = reader
+            .read(&primary, &PriceSampling::LATEST, |feed| {
+                if *feed.key == keys[2] {
+                    Ok(Decimal::ONE)
+                } else {
+                    fail(feed)
+                }
+            })
+            .unwrap();
+
+        assert_eq!(price, Decimal::ONE);
+    }
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RoundTicketsPurchase,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+};
+use crate::access_list::require_entry_allowed;
+use crate::blocklist::Blocklist;
+
+// Gifts or sells an entry before the draw. Transfers stop once the round leaves Open,
+// so set_winner_address pays whoever owned the purchase when randomness was requested.
+// The new owner passes the same entry gates as a buyer
+pub fn transfer_purchase(
+    ctx: Context<TransferPurchase>,
+    round_id: u32,
+    purchase_index: u32,
+    new_owner: Pubkey,
+    access_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let round = &ctx.accounts.round;
+    let purchase = &mut ctx.accounts.round_tickets_purchase;
+    let old_owner = purchase.player;
+
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    // Free-entry tickets are capped per wallet, transfers would bypass the cap
+    require!(!round.free_entry, RaffleError::FreeEntryRound);
+    require!(!purchase.cancelled, RaffleError::PurchaseCancelled);
+    require!(
+        new_owner != Pubkey::default() && new_owner != old_owner,
+        RaffleError::InvalidNewOwner
+    );
+    require_entry_allowed(
+        &ctx.accounts.raffle_state,
+        &ctx.accounts.sol_raffle,
+        round,
+        ctx.accounts.blocklist.as_ref(),
+        ctx.accounts.attestation.as_ref(),
+        &new_owner,
+        &access_proof,
+    )?;
+
+    purchase.player = new_owner;
+
+    msg!("✅ Purchase {} of round {} transferred to {}", purchase_index, round_id, new_owner);
+
+    emit!(PurchaseTransferred {
+        token: ctx.accounts.sol_raffle.token_mint,
+        round_id,
+        purchase_index,
+        from: old_owner,
+        to: new_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32, purchase_index: u32)]
+pub struct TransferPurchase<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets_purchase",
+            round.key().as_ref(),
+            &purchase_index.to_le_bytes()
+        ],
+        bump = round_tickets_purchase.bump,
+        constraint = round_tickets_purchase.player == player.key() @ RaffleError::Unauthorized
+    )]
+    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+
+    pub player: Signer<'info>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    /// CHECK: SAS attestation of the new owner, required if sol_raffle.attestation_required. Validated in the handler
+    pub attestation: Option<UncheckedAccount<'info>>,
+}
+
+// Events
+#[event]
+pub struct PurchaseTransferred {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub purchase_index: u32,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub timestamp: i64,
+}
@@ -0,0 +1,647 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use crate::{
+    Round,
+    RoundStatus,
+    RoundTicketsPurchase,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateTokenRaffle,
+    append_cumulative_tickets,
+    initialize_round_tickets_purchase,
+    transfer,
+};
+use crate::access_list::require_entry_allowed;
+use crate::audit::{record_audit, AuditAction};
+use crate::blocklist::Blocklist;
+use crate::cluster_profile::{check_cluster_sol_feed, ClusterProfile};
+use crate::daily_stats::{record_daily_purchase, utc_day, DailyStats};
+use crate::oracle_failover::{FeedReader, FeedFailover};
+use crate::price_cache::{require_price_cache, PriceCache};
+use crate::price_feeds::{require_feed_registry, FeedRegistry};
+use crate::price_feeds::{calculate_round_ticket_price, check_price_circuit_breaker, snapshot_open_prices};
+use crate::prize_limits::add_capped_prize;
+use crate::purchase_limits::{check_min_purchase, record_wallet_purchase, WalletPurchases};
+use crate::raffle_stats::{record_raffle_purchase, RaffleStats};
+use crate::records::record_purchase;
+use crate::schedule::close_at_prize_target;
+use crate::solvency::record_in_ledger_account;
+
+// Only authority. Every `every` consecutive rounds a player gets `bonus_tickets`, 0 disables
+pub fn set_streak_rewards(
+    ctx: Context<UpdateTokenRaffle>,
+    every: u32,
+    bonus_tickets: u32,
+) -> Result<()> {
+    require!(
+        (every == 0) == (bonus_tickets == 0),
+        RaffleError::InvalidStreakConfig
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.streak_bonus_every = every;
+    sol_raffle.streak_bonus_tickets = bonus_tickets;
+
+    emit!(StreakRewardsUpdated {
+        token: sol_raffle.token_mint,
+        every,
+        bonus_tickets,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::StreakRewardsChanged,
+        bonus_tickets as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Zero `points_per_sol` stops accrual, zero `points_per_ticket` stops redemption
+pub fn set_loyalty_config(
+    ctx: Context<UpdateTokenRaffle>,
+    points_per_sol: u64,
+    points_per_ticket: u64,
+) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.loyalty_points_per_sol = points_per_sol;
+    sol_raffle.loyalty_points_per_ticket = points_per_ticket;
+
+    emit!(LoyaltyConfigUpdated {
+        token: sol_raffle.token_mint,
+        points_per_sol,
+        points_per_ticket,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::LoyaltyConfigChanged,
+        points_per_ticket,
+    )?;
+
+    Ok(())
+}
+
+pub fn fund_rewards_vault(ctx: Context<FundRewardsVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.funder.to_account_info(),
+        ctx.accounts.rewards_vault.to_account_info(),
+        amount,
+        None,
+    )?;
+
+    msg!("Rewards vault funded with {} lamports", amount);
+    Ok(())
+}
+
+// Accrues loyalty points for `spent` lamports
+pub fn accrue_loyalty_points(
+    player_stats: &mut Account<PlayerStats>,
+    sol_raffle: &TokenRaffle,
+    spent: u64,
+) -> Result<u64> {
+    player_stats.total_spent = player_stats
+        .total_spent
+        .checked_add(spent)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let points = (spent as u128)
+        .checked_mul(sol_raffle.loyalty_points_per_sol as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / LAMPORTS_PER_SOL as u128;
+    let points = u64::try_from(points).map_err(|_| ProgramError::ArithmeticOverflow)?;
+
+    player_stats.loyalty_points = player_stats
+        .loyalty_points
+        .checked_add(points)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(points)
+}
+
+// Undoes what a cancelled purchase earned. Points already redeemed block the cancel. The round
+// entry is taken back when this purchase counted it and nothing later moved the streak on, another
+// purchase in the same round doesn't keep it counted
+pub fn revoke_purchase_rewards(
+    player_stats: &mut PlayerStats,
+    purchase: &RoundTicketsPurchase,
+    round_id: u32,
+) -> Result<()> {
+    require!(
+        player_stats.loyalty_points >= purchase.loyalty_points,
+        RaffleError::InsufficientLoyaltyPoints
+    );
+    player_stats.loyalty_points -= purchase.loyalty_points;
+
+    let spent = purchase
+        .ticket_price
+        .checked_mul((purchase.tickets_count - purchase.bonus_tickets) as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    player_stats.total_spent = player_stats.total_spent.saturating_sub(spent);
+
+    if purchase.entry_streak == 0 || player_stats.last_round_id != Some(round_id) {
+        return Ok(());
+    }
+
+    // A streak above 1 continued from the previous round, otherwise the earlier entry was
+    // further back and no longer matters for the next one
+    player_stats.current_streak = purchase.entry_streak - 1;
+    player_stats.last_round_id = if purchase.entry_streak > 1 { round_id.checked_sub(1) } else { None };
+    if purchase.best_streak_raised {
+        player_stats.best_streak = purchase.entry_streak - 1;
+    }
+    player_stats.rounds_entered = player_stats.rounds_entered.saturating_sub(1);
+
+    Ok(())
+}
+
+// Burns points for free tickets. The tickets' value at the current price is
+// moved from rewards_vault into the round's prize so the pot stays backed
+pub fn redeem_points_for_tickets(
+    ctx: Context<RedeemPointsForTickets>,
+    round_id: u32,
+    purchase_index: u32,
+    count: u32,
+    access_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(count > 0, RaffleError::InvalidTicketCount);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+        RaffleError::MaintenanceMode
+    );
+    require!(!ctx.accounts.sol_raffle.sales_paused, RaffleError::SalesPaused);
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    let round = &mut ctx.accounts.round;
+    let player_stats = &mut ctx.accounts.player_stats;
+
+    require!(
+        sol_raffle.loyalty_points_per_ticket > 0,
+        RaffleError::LoyaltyRedemptionDisabled
+    );
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require!(!round.free_entry, RaffleError::FreeEntryRound);
+    require_entry_allowed(
+        &ctx.accounts.raffle_state,
+        sol_raffle,
+        round,
+        ctx.accounts.blocklist.as_ref(),
+        ctx.accounts.attestation.as_ref(),
+        &ctx.accounts.player.key(),
+        &access_proof,
+    )?;
+    require!(current_time < round.end_time, RaffleError::RoundNotAvailable);
+    require_eq!(
+        purchase_index,
+        round.purchases_count,
+        RaffleError::PurchaseIndexMismatch
+    );
+
+    let points_cost = sol_raffle
+        .loyalty_points_per_ticket
+        .checked_mul(count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(
+        player_stats.loyalty_points >= points_cost,
+        RaffleError::InsufficientLoyaltyPoints
+    );
+
+    let cluster_profile = check_cluster_sol_feed(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_price_feed,
+    )?;
+    let feed_reader = FeedReader::new(
+        require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?,
+        ctx.accounts.feed_failover.as_deref(),
+        require_price_cache(&ctx.accounts.raffle_state, ctx.accounts.price_cache.as_deref())?,
+        cluster_profile,
+        ctx.remaining_accounts,
+    );
+    let ticket_price = calculate_round_ticket_price(
+        &ctx.accounts.raffle_state,
+        sol_raffle,
+        round,
+        &feed_reader,
+        &ctx.accounts.reference_price_feed,
+        ctx.remaining_accounts,
+        &ctx.accounts.sol_price_feed,
+    )?;
+    require!(ticket_price > 0, RaffleError::InvalidTicketPrice);
+    snapshot_open_prices(round, &ctx.accounts.reference_price_feed, &ctx.accounts.sol_price_feed, &feed_reader)?;
+    check_price_circuit_breaker(
+        round,
+        ctx.accounts.raffle_state.max_price_deviation_bps,
+        ticket_price,
+    )?;
+
+    let reward_value = ticket_price
+        .checked_mul(count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    check_min_purchase(sol_raffle, count, reward_value)?;
+    require!(
+        ctx.accounts.rewards_vault.lamports() >= reward_value,
+        RaffleError::RewardsBudgetExhausted
+    );
+
+    let rewards_vault_seeds: &[&[&[u8]]] = &[&[b"rewards_vault", &[ctx.bumps.rewards_vault]]];
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.rewards_vault.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        reward_value,
+        Some(rewards_vault_seeds),
+    )?;
+    record_in_ledger_account(&ctx.accounts.vault_ledger, reward_value)?;
+
+    player_stats.loyalty_points -= points_cost;
+
+    let new_total = round
+        .total_tickets
+        .checked_add(count)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    append_cumulative_tickets(&ctx.accounts.round_tickets, new_total)?;
+
+    let round_tickets_purchase = &mut ctx.accounts.round_tickets_purchase;
+    initialize_round_tickets_purchase(
+        round_tickets_purchase,
+        round.key(),
+        round.purchases_count,
+        ctx.accounts.player.key(),
+        count,
+        ctx.bumps.round_tickets_purchase,
+    )?;
+    round_tickets_purchase.bonus_tickets = count;
+
+    record_wallet_purchase(
+        ctx.accounts.wallet_purchases.as_mut(),
+        round.key(),
+        ctx.accounts.player.key(),
+        ctx.bumps.wallet_purchases,
+        sol_raffle.max_purchases_per_wallet,
+    )?;
+
+    // Paid from rewards_vault, no ticket volume
+    record_raffle_purchase(
+        &mut ctx.accounts.raffle_stats,
+        sol_raffle.key(),
+        ctx.bumps.raffle_stats,
+        round.purchases_count == 0,
+        count,
+        0,
+    )?;
+    record_purchase(
+        &ctx.accounts.records,
+        round,
+        ctx.accounts.player.key(),
+        reward_value,
+        0,
+        current_time,
+    )?;
+    record_daily_purchase(
+        &mut ctx.accounts.daily_stats,
+        sol_raffle.key(),
+        ctx.bumps.daily_stats,
+        Some(player_stats),
+        count,
+        0,
+        current_time,
+    )?;
+
+    add_capped_prize(round, reward_value)?;
+    round.sponsored_amount = round
+        .sponsored_amount
+        .checked_add(reward_value)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.total_tickets = new_total;
+    round.purchases_count += 1;
+    close_at_prize_target(sol_raffle, round, current_time);
+
+    emit!(LoyaltyPointsRedeemed {
+        token: sol_raffle.token_mint,
+        round_id,
+        player: ctx.accounts.player.key(),
+        purchase_index,
+        count,
+        points_spent: points_cost,
+        points_left: player_stats.loyalty_points,
+        reward_value,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// Registers the player's participation in `round_id` and returns the streak bonus
+// tickets earned by it. Only the first purchase of a round advances the streak
+pub fn record_round_entry(
+    player_stats: &mut Account<PlayerStats>,
+    sol_raffle: &TokenRaffle,
+    player: Pubkey,
+    round_id: u32,
+    bump: u8,
+) -> Result<u32> {
+    player_stats.player = player;
+    player_stats.bump = bump;
+
+    if player_stats.last_round_id == Some(round_id) {
+        return Ok(0);
+    }
+
+    let is_consecutive = round_id
+        .checked_sub(1)
+        .map(|prev_id| player_stats.last_round_id == Some(prev_id))
+        .unwrap_or(false);
+
+    player_stats.current_streak = if is_consecutive {
+        player_stats
+            .current_streak
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+    } else {
+        1
+    };
+    player_stats.best_streak = player_stats.best_streak.max(player_stats.current_streak);
+    player_stats.rounds_entered = player_stats
+        .rounds_entered
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    player_stats.last_round_id = Some(round_id);
+
+    let every = sol_raffle.streak_bonus_every;
+    if every == 0 || !player_stats.current_streak.is_multiple_of(every) {
+        return Ok(0);
+    }
+
+    let bonus_tickets = sol_raffle.streak_bonus_tickets;
+
+    emit!(StreakBonusAwarded {
+        token: sol_raffle.token_mint,
+        round_id,
+        player,
+        streak: player_stats.current_streak,
+        bonus_tickets,
+    });
+
+    Ok(bonus_tickets)
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct FundRewardsVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// PDA for storing the loyalty rewards budget
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32, purchase_index: u32)]
+pub struct RedeemPointsForTickets<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: zero-copy tickets of the round, mutated in place
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + RoundTicketsPurchase::INIT_SPACE,
+        seeds = [
+            b"round_tickets_purchase",
+            round.key().as_ref(),
+            &purchase_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"player_stats",
+            sol_raffle.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    // Required while sol_raffle.max_purchases_per_wallet is set
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + WalletPurchases::INIT_SPACE,
+        seeds = [
+            b"wallet_purchases",
+            round.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+
+    // Lifetime totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: Records PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: price feed of the first ticket peg component, basket feeds go in remaining_accounts
+    pub reference_price_feed: AccountInfo<'info>,
+
+    /// CHECK: SOL price feed account
+    pub sol_price_feed: AccountInfo<'info>,
+
+    // Fallbacks for stale feeds, the fallback feeds go in remaining_accounts after the basket feeds
+    #[account(
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Option<Account<'info, FeedFailover>>,
+
+    // Crank-refreshed feed prices, read instead of the feeds while fresh
+    #[account(
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Option<Account<'info, PriceCache>>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+
+    // Required once the cluster profile is set up, pins the SOL feed and the oracle program
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    /// CHECK: SAS attestation of the player, required if sol_raffle.attestation_required. Validated in the handler
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PlayerStats {
+    pub player: Pubkey,
+    pub last_round_id: Option<u32>,
+    pub current_streak: u32,
+    pub best_streak: u32,
+    pub rounds_entered: u32,
+    pub bump: u8,
+    pub total_spent: u64,
+    pub loyalty_points: u64,
+    pub last_purchase_day: i64, // UTC day of the last purchase counted in DailyStats
+}
+
+// Events
+#[event]
+pub struct StreakRewardsUpdated {
+    pub token: Pubkey,
+    pub every: u32,
+    pub bonus_tickets: u32,
+}
+
+#[event]
+pub struct StreakBonusAwarded {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub player: Pubkey,
+    pub streak: u32,
+    pub bonus_tickets: u32,
+}
+
+#[event]
+pub struct LoyaltyConfigUpdated {
+    pub token: Pubkey,
+    pub points_per_sol: u64,
+    pub points_per_ticket: u64,
+}
+
+#[event]
+pub struct LoyaltyPointsRedeemed {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub player: Pubkey,
+    pub purchase_index: u32,
+    pub count: u32,
+    pub points_spent: u64,
+    pub points_left: u64,
+    pub reward_value: u64,
+    pub timestamp: i64,
+}
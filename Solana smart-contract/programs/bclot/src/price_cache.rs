@@ -0,0 +1,301 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::price_feeds::{
+    get_reference_price,
+    get_sol_price,
+    require_feed_registry,
+    FeedRegistry,
+    PriceSampling,
+    MAX_STALENESS_SLOTS,
+};
+
+pub const MAX_CACHED_FEEDS: usize = 8;
+
+// Only authority
+pub fn initialize_price_cache(ctx: Context<InitializePriceCache>) -> Result<()> {
+    let price_cache = &mut ctx.accounts.price_cache;
+    price_cache.sol_feed = Pubkey::default();
+    price_cache.max_age_slots = 0;
+    price_cache.entries = Vec::new();
+    price_cache.bump = ctx.bumps.price_cache;
+
+    ctx.accounts.raffle_state.price_cache_enabled = true;
+
+    msg!("✅ Price cache initialized");
+
+    Ok(())
+}
+
+// Only authority. `sol_feed` is refreshed the way purchases read the SOL price, `reference_feeds`
+// the way they read peg components. Feeds kept from the previous list keep their cached price.
+// max_age_slots 0 - purchases never read the cache
+pub fn set_cached_feeds(
+    ctx: Context<UpdatePriceCache>,
+    sol_feed: Pubkey,
+    reference_feeds: Vec<Pubkey>,
+    max_age_slots: u64,
+) -> Result<()> {
+    require!(
+        reference_feeds.len() < MAX_CACHED_FEEDS && max_age_slots <= MAX_STALENESS_SLOTS,
+        RaffleError::InvalidPriceCacheConfig
+    );
+    for (index, feed) in reference_feeds.iter().enumerate() {
+        require!(
+            *feed != sol_feed && !reference_feeds[..index].contains(feed),
+            RaffleError::InvalidPriceCacheConfig
+        );
+    }
+
+    let price_cache = &mut ctx.accounts.price_cache;
+    let entries = std::iter::once(&sol_feed)
+        .chain(reference_feeds.iter())
+        .map(|feed| {
+            price_cache
+                .entries
+                .iter()
+                .find(|entry| entry.feed == *feed)
+                .cloned()
+                .unwrap_or(CachedPrice {
+                    feed: *feed,
+                    price: [0; 16],
+                    refreshed_slot: 0,
+                })
+        })
+        .collect();
+
+    price_cache.sol_feed = sol_feed;
+    price_cache.max_age_slots = max_age_slots;
+    price_cache.entries = entries;
+
+    emit!(CachedFeedsUpdated {
+        sol_feed,
+        reference_feeds,
+        max_age_slots,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceCacheChanged,
+        max_age_slots,
+    )?;
+
+    Ok(())
+}
+
+// Permissionless. Reads every cached feed passed in remaining_accounts with the same checks a
+// purchase applies to the latest feed value
+pub fn refresh_price(ctx: Context<RefreshPrice>) -> Result<()> {
+    require!(!ctx.remaining_accounts.is_empty(), RaffleError::NotEnoughRemainingAccounts);
+
+    let max_confidence_bps = ctx.accounts.raffle_state.max_price_confidence_bps;
+    let feed_registry = require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?;
+    let price_cache = &mut ctx.accounts.price_cache;
+    let slot = Clock::get()?.slot;
+
+    for feed in ctx.remaining_accounts.iter() {
+        let is_sol_feed = feed.key() == price_cache.sol_feed;
+        let entry = price_cache
+            .entries
+            .iter_mut()
+            .find(|entry| entry.feed == feed.key())
+            .ok_or(RaffleError::FeedNotCached)?;
+
+        let price = if is_sol_feed {
+            get_sol_price(feed, slot, max_confidence_bps, &PriceSampling::LATEST, feed_registry)?
+        } else {
+            get_reference_price(feed, slot, max_confidence_bps, &PriceSampling::LATEST, feed_registry)?
+        };
+        entry.price = price.serialize();
+        entry.refreshed_slot = slot;
+
+        msg!("Cached {} at {}", feed.key(), price);
+    }
+
+    emit!(PriceCacheRefreshed {
+        feeds: ctx.remaining_accounts.len() as u8,
+        slot,
+    });
+
+    Ok(())
+}
+
+// With the cache set up it must be passed, so a client can't leave it out to get the feed read
+// instead of the cached price
+pub fn require_price_cache<'a>(
+    raffle_state: &RaffleState,
+    price_cache: Option<&'a PriceCache>,
+) -> Result<Option<&'a PriceCache>> {
+    match price_cache {
+        None if raffle_state.price_cache_enabled => err!(RaffleError::PriceCacheRequired),
+        price_cache => Ok(price_cache),
+    }
+}
+
+impl PriceCache {
+    // None once older than max_age_slots, the feed is read then
+    pub fn fresh_entry(&self, feed: &Pubkey, slot: u64) -> Option<&CachedPrice> {
+        if self.max_age_slots == 0 {
+            return None;
+        }
+
+        self.entries
+            .iter()
+            .find(|entry| entry.feed == *feed && entry.refreshed_slot > 0)
+            .filter(|entry| slot.saturating_sub(entry.refreshed_slot) <= self.max_age_slots)
+    }
+
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializePriceCache<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PriceCache::INIT_SPACE,
+        seeds = [b"price_cache"],
+        bump
+    )]
+    pub price_cache: Account<'info, PriceCache>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdatePriceCache<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Account<'info, PriceCache>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshPrice<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Account<'info, PriceCache>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+}
+
+// Account data structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct CachedPrice {
+    pub feed: Pubkey,
+    pub price: [u8; 16], // serialized Decimal
+    pub refreshed_slot: u64, // 0 - never refreshed
+}
+
+// Feed prices read by the refresh_price crank, purchases in Latest sampling use them while fresh
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct PriceCache {
+    pub sol_feed: Pubkey,
+    pub max_age_slots: u64,
+    #[max_len(MAX_CACHED_FEEDS)]
+    pub entries: Vec<CachedPrice>, // sol_feed first
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct CachedFeedsUpdated {
+    pub sol_feed: Pubkey,
+    pub reference_feeds: Vec<Pubkey>,
+    pub max_age_slots: u64,
+}
+
+#[event]
+pub struct PriceCacheRefreshed {
+    pub feeds: u8,
+    pub slot: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raffle_state(price_cache_enabled: bool) -> RaffleState {
+        let mut raffle_state =
+            RaffleState::try_deserialize_unchecked(&mut &vec![0u8; 8 + RaffleState::INIT_SPACE][..]).unwrap();
+        raffle_state.price_cache_enabled = price_cache_enabled;
+        raffle_state
+    }
+
+    fn price_cache() -> PriceCache {
+        PriceCache {
+            sol_feed: Pubkey::new_unique(),
+            max_age_slots: 10,
+            entries: Vec::new(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn cache_is_optional_until_set_up() {
+        let price_cache = price_cache();
+
+        assert!(require_price_cache(&raffle_state(false), None).unwrap().is_none());
+        assert!(require_price_cache(&raffle_state(false), Some(&price_cache)).unwrap().is_some());
+    }
+
+    #[test]
+    fn cache_is_required_once_set_up() {
+        let price_cache = price_cache();
+
+        assert_eq!(
+            require_price_cache(&raffle_state(true), None).unwrap_err(),
+            RaffleError::PriceCacheRequired.into()
+        );
+        assert!(require_price_cache(&raffle_state(true), Some(&price_cache)).unwrap().is_some());
+    }
+}
@@ -1,93 +1,1024 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
 use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
-use std::str::FromStr;
+use std::cell::Ref;
 use switchboard_on_demand::on_demand::accounts::pull_feed::PullFeedAccountData;
 
-use crate::RaffleError;
+use crate::{
+    PriceDecayMode,
+    PriceSampleMode,
+    PricingMode,
+    Round,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateRaffleState,
+    UpdateTokenRaffle,
+    SOL_DECIMALS,
+};
+use crate::admin::PriceBreakdownView;
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::cluster_profile::{require_cluster_profile, Cluster, ClusterProfile};
+use crate::oracle_failover::FeedReader;
 
-const LAMPORTS_PER_SOL: u64 = 1_000_000_000u64;
-const TICKET_BTC_STR: &str = "0.00005";
-pub const TICKET_BTC_SATOSHIS: u64 = 5_000; // 0.00005 BTC = 5,000 satoshi
+pub const DEFAULT_TICKET_SATOSHIS: u64 = 5_000; // 0.00005 BTC
+pub const MIN_TICKET_SATOSHIS: u64 = 100;
+pub const MAX_TICKET_SATOSHIS: u64 = 1_000_000; // 0.01 BTC
 const BTC_DECIMALS: u8 = 8;
-const USD_DECIMALS: u8 = 6;
+pub const USD_DECIMALS: u8 = 6;
+pub const MIN_MAINNET_TICKET_USD: u64 = 100_000; // $0.10, fixed prices below are devnet test prices
 
-// Calculates the price of one ticket in lamports based on BTC and SOL price feeds
+pub const MAX_REGISTERED_FEEDS: usize = 16;
+pub const MAX_PEG_COMPONENTS: usize = 3;
+const MAX_PEG_DECIMALS: u8 = 18;
+
+pub const MAX_STALENESS_SLOTS: u64 = 1500;
+pub const MAX_FEED_STALENESS_SLOTS: u64 = 9_000; // ~1 hour
+const SUBMISSION_DECIMALS: u32 = 18; // scale of raw oracle submission values
+pub const MAX_PRICE_SAMPLES: u8 = 32; // submissions kept by a pull feed
+
+// How a feed value is taken, snapshotted by each round from its raffle
+#[derive(Clone, Debug)]
+pub struct PriceSampling {
+    pub mode: PriceSampleMode,
+    pub count: u8, // Min and Median modes only
+}
+
+impl PriceSampling {
+    pub const LATEST: Self = Self { mode: PriceSampleMode::Latest, count: 0 };
+
+    pub fn for_round(round: &Round) -> Self {
+        Self {
+            mode: round.price_sample_mode.clone(),
+            count: round.price_sample_count,
+        }
+    }
+}
+
+// Calculates the price of one ticket in base units of `decimals` from the ticket peg and the SOL
+// price feed. reference_price_feed prices the first peg component, basket_feeds the rest in peg order
 pub fn calculate_ticket_price_for_sol(
-    btc_price_feed: &AccountInfo,
+    raffle_state: &RaffleState,
+    sampling: &PriceSampling,
+    feed_reader: &FeedReader,
+    reference_price_feed: &AccountInfo,
+    basket_feeds: &[AccountInfo],
     sol_price_feed: &AccountInfo,
+    decimals: u8,
 ) -> Result<u64> {
     let clock = Clock::get()?;
     msg!("Current slot: {}", clock.slot);
 
-    let sol_price = get_sol_price(sol_price_feed, clock.slot)?;
+    let sol_price = feed_reader.read(sol_price_feed, sampling, |feed| {
+        get_sol_price(feed, clock.slot, raffle_state.max_price_confidence_bps, sampling, feed_reader.feed_registry())
+    })?;
     msg!("SOL Price (Decimal): {}", sol_price);
 
-    let btc_price = get_btc_price(btc_price_feed, clock.slot)?;
-    msg!("BTC Price (Decimal): {}", btc_price);
+    let ticket_price_usd =
+        calculate_ticket_usd(raffle_state, sampling, feed_reader, reference_price_feed, basket_feeds, clock.slot)?;
+    msg!("Ticket price in USD: {}", ticket_price_usd);
 
-    let ticket_price_lamports = calculate_ticket_lamports(btc_price, sol_price)?;
+    let ticket_price_lamports = calculate_ticket_lamports(ticket_price_usd, sol_price, decimals)?;
     
     msg!("Final ticket price (lamports): {}", ticket_price_lamports);
 
     Ok(ticket_price_lamports)
 }
 
-fn get_sol_price(sol_price_feed: &AccountInfo, current_slot: u64) -> Result<Decimal> {
-    msg!("Parsing SOL price feed...");
-    let sol_data = sol_price_feed.data.borrow();
+// Only authority. Max deviation of the live ticket price from the round open price, 0 disables
+pub fn set_price_circuit_breaker(ctx: Context<UpdateRaffleState>, max_deviation_bps: u16) -> Result<()> {
+    require!(max_deviation_bps <= 10_000, RaffleError::InvalidBps);
 
-    let sol_feed = PullFeedAccountData::parse(sol_data).map_err(|e| {
-        msg!("SOL Switchboard parse failed: {:?}", e);
-        RaffleError::InvalidFeedAccount
+    ctx.accounts.raffle_state.max_price_deviation_bps = max_deviation_bps;
+
+    emit!(PriceCircuitBreakerUpdated { max_deviation_bps });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceCircuitBreakerChanged,
+        max_deviation_bps as u64,
+    )?;
+
+    Ok(())
+}
+
+// Raffles set up by initialize_token_raffle are priced off their registered feed in their token's
+// base units, initialize_sol_raffle ones have no feed pinned and are priced in lamports
+pub fn quote_decimals(sol_raffle: Option<&TokenRaffle>, sol_price_feed: &AccountInfo) -> Result<u8> {
+    let Some(sol_raffle) = sol_raffle else {
+        return Ok(SOL_DECIMALS);
+    };
+    if sol_raffle.price_feed != Pubkey::default() {
+        require_keys_eq!(sol_price_feed.key(), sol_raffle.price_feed, RaffleError::InvalidFeedAccount);
+    }
+
+    Ok(sol_raffle.token_decimals)
+}
+
+// Prices by the mode the round snapshotted at creation, a committed round price skips the feeds
+pub fn calculate_round_ticket_price(
+    raffle_state: &RaffleState,
+    sol_raffle: &TokenRaffle,
+    round: &Round,
+    feed_reader: &FeedReader,
+    reference_price_feed: &AccountInfo,
+    basket_feeds: &[AccountInfo],
+    sol_price_feed: &AccountInfo,
+) -> Result<u64> {
+    if round.ticket_price_lamports > 0 {
+        msg!("Committed round ticket price (lamports): {}", round.ticket_price_lamports);
+        return Ok(round.ticket_price_lamports);
+    }
+
+    let sampling = PriceSampling::for_round(round);
+    let decimals = quote_decimals(Some(sol_raffle), sol_price_feed)?;
+
+    match round.pricing_mode {
+        PricingMode::Pegged => calculate_ticket_price_for_sol(
+            raffle_state,
+            &sampling,
+            feed_reader,
+            reference_price_feed,
+            basket_feeds,
+            sol_price_feed,
+            decimals,
+        ),
+        PricingMode::FixedUsd => calculate_fixed_ticket_price(
+            raffle_state,
+            &sampling,
+            feed_reader,
+            round.fixed_ticket_price_usd,
+            sol_price_feed,
+            decimals,
+        ),
+    }
+}
+
+// Fixed USD price in USD_DECIMALS, only the SOL price feed is read
+pub fn calculate_fixed_ticket_price(
+    raffle_state: &RaffleState,
+    sampling: &PriceSampling,
+    feed_reader: &FeedReader,
+    fixed_ticket_price_usd: u64,
+    sol_price_feed: &AccountInfo,
+    decimals: u8,
+) -> Result<u64> {
+    let clock = Clock::get()?;
+
+    let sol_price = feed_reader.read(sol_price_feed, sampling, |feed| {
+        get_sol_price(feed, clock.slot, raffle_state.max_price_confidence_bps, sampling, feed_reader.feed_registry())
     })?;
+    msg!("SOL Price (Decimal): {}", sol_price);
+
+    let micro_usd = i64::try_from(fixed_ticket_price_usd).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    let ticket_price_usd = Decimal::new(micro_usd, USD_DECIMALS as u32);
+    msg!("Fixed ticket price in USD: {}", ticket_price_usd);
 
-    let sol_price = sol_feed
-        .get_value(current_slot, 1500, 3, false)
-        .map_err(|e| {
-            msg!("SOL Switchboard get_value failed: {:?}", e);
-            RaffleError::OracleError
-        })?;
+    let ticket_price_lamports = calculate_ticket_lamports(ticket_price_usd, sol_price, decimals)?;
 
-    Ok(sol_price)
+    msg!("Final ticket price (lamports): {}", ticket_price_lamports);
+
+    Ok(ticket_price_lamports)
 }
 
-fn get_btc_price(btc_price_feed: &AccountInfo, current_slot: u64) -> Result<Decimal> {
-    msg!("Parsing BTC price feed...");
-    let btc_data = btc_price_feed.data.borrow();
+// Only authority. Feed values whose standard deviation is above this share of the value are
+// rejected, 0 disables
+pub fn set_price_confidence(ctx: Context<UpdateRaffleState>, max_confidence_bps: u16) -> Result<()> {
+    require!(max_confidence_bps <= 10_000, RaffleError::InvalidBps);
 
-    let btc_feed = PullFeedAccountData::parse(btc_data).map_err(|e| {
-        msg!("BTC Switchboard parse failed: {:?}", e);
-        RaffleError::InvalidFeedAccount
+    ctx.accounts.raffle_state.max_price_confidence_bps = max_confidence_bps;
+
+    emit!(PriceConfidenceUpdated { max_confidence_bps });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceConfidenceChanged,
+        max_confidence_bps as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. With commit enabled, the first priced purchase of a round fixes the oracle
+// ticket price for the rest of it, price decay still applies on top. Takes effect from the next round
+pub fn set_commit_round_price(ctx: Context<UpdateTokenRaffle>, enabled: bool) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.commit_round_price = enabled;
+
+    emit!(CommitRoundPriceUpdated {
+        token_raffle: sol_raffle.key(),
+        enabled,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::CommitRoundPriceChanged,
+        enabled as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Min and Median price tickets off the `count` most recent oracle submissions
+// instead of the feed's latest result, so a single pushed value moves the price less.
+// Takes effect from the next round
+pub fn set_price_sampling(ctx: Context<UpdateTokenRaffle>, mode: PriceSampleMode, count: u8) -> Result<()> {
+    require!(
+        (mode == PriceSampleMode::Latest && count == 0)
+            || (mode != PriceSampleMode::Latest && count > 0 && count <= MAX_PRICE_SAMPLES),
+        RaffleError::InvalidPriceSamplingConfig
+    );
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.price_sample_mode = mode.clone();
+    sol_raffle.price_sample_count = count;
+
+    emit!(PriceSamplingUpdated {
+        token_raffle: sol_raffle.key(),
+        mode,
+        count,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceSamplingChanged,
+        count as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Moves an existing raffle between the ticket peg and a fixed USD price.
+// The open round keeps the mode it was created with, the switch applies from the next round.
+// A mainnet profile rejects fixed prices that decay under MIN_MAINNET_TICKET_USD
+pub fn set_pricing_mode(
+    ctx: Context<SetPricingMode>,
+    mode: PricingMode,
+    fixed_ticket_price_usd: u64,
+) -> Result<()> {
+    require!(
+        mode != PricingMode::FixedUsd
+            || (fixed_ticket_price_usd > 0 && fixed_ticket_price_usd <= i64::MAX as u64),
+        RaffleError::InvalidTicketPrice
+    );
+    let cluster_profile = require_cluster_profile(&ctx.accounts.raffle_state, ctx.accounts.cluster_profile.as_deref())?;
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    if mode == PricingMode::FixedUsd && is_mainnet(cluster_profile) {
+        check_mainnet_floor(
+            fixed_ticket_price_usd,
+            lowest_price_bps(&sol_raffle.price_decay_mode, sol_raffle.price_decay_end_bps),
+        )?;
+    }
+
+    sol_raffle.pricing_mode = mode.clone();
+    sol_raffle.fixed_ticket_price_usd = fixed_ticket_price_usd;
+
+    emit!(PricingModeUpdated {
+        token_raffle: sol_raffle.key(),
+        mode: mode.clone(),
+        fixed_ticket_price_usd,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PricingModeChanged,
+        mode as u64,
+    )?;
+
+    Ok(())
+}
+
+// An empty peg falls back to ticket_price_satoshis of BTC, priced by whichever feed is passed
+pub fn ticket_peg(raffle_state: &RaffleState) -> Vec<PegComponent> {
+    if raffle_state.ticket_peg.is_empty() {
+        vec![PegComponent {
+            feed: Pubkey::default(),
+            amount: raffle_state.ticket_price_satoshis,
+            decimals: BTC_DECIMALS,
+        }]
+    } else {
+        raffle_state.ticket_peg.clone()
+    }
+}
+
+// Only authority. Pegs the ticket to one asset or a basket, empty `components` restores the BTC peg.
+// That one takes whichever feed is passed, so with a cluster profile the peg must name its feeds.
+// A mainnet profile rejects a peg worth under MIN_MAINNET_TICKET_USD at the current feed values.
+// remaining_accounts: the feed of every component, in order
+pub fn set_ticket_peg(ctx: Context<SetTicketPeg>, components: Vec<PegComponent>) -> Result<()> {
+    require!(
+        components.len() <= MAX_PEG_COMPONENTS && ctx.remaining_accounts.len() == components.len(),
+        RaffleError::InvalidTicketPeg
+    );
+    require!(
+        !components.is_empty() || !ctx.accounts.raffle_state.cluster_profile_enabled,
+        RaffleError::TicketPegFeedRequired
+    );
+
+    for (component, feed) in components.iter().zip(ctx.remaining_accounts.iter()) {
+        require!(
+            component.feed != Pubkey::default()
+                && component.feed == feed.key()
+                && component.amount > 0
+                && component.amount <= i64::MAX as u64
+                && component.decimals <= MAX_PEG_DECIMALS,
+            RaffleError::InvalidTicketPeg
+        );
+        check_feed_account(feed)?;
+    }
+
+    let raffle_state = &ctx.accounts.raffle_state;
+    let cluster_profile = require_cluster_profile(raffle_state, ctx.accounts.cluster_profile.as_deref())?;
+    if is_mainnet(cluster_profile) {
+        let feed_reader = FeedReader::new(
+            require_feed_registry(raffle_state, ctx.accounts.feed_registry.as_deref())?,
+            None,
+            None,
+            cluster_profile,
+            ctx.remaining_accounts,
+        );
+        let peg_usd = peg_usd(&components, ctx.remaining_accounts, raffle_state.max_price_confidence_bps, &feed_reader)?;
+        check_mainnet_floor(peg_usd, 10_000)?;
+    }
+
+    ctx.accounts.raffle_state.ticket_peg = components.clone();
+
+    emit!(TicketPegUpdated { components: components.clone() });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::TicketPegChanged,
+        components.len() as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Base ticket price in satoshis, applies to the next priced purchase
+pub fn set_ticket_price_satoshis(ctx: Context<UpdateRaffleState>, ticket_satoshis: u64) -> Result<()> {
+    require!(
+        (MIN_TICKET_SATOSHIS..=MAX_TICKET_SATOSHIS).contains(&ticket_satoshis),
+        RaffleError::InvalidTicketPrice
+    );
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    let old_satoshis = raffle_state.ticket_price_satoshis;
+    raffle_state.ticket_price_satoshis = ticket_satoshis;
+
+    emit!(TicketPriceUpdated {
+        old_satoshis,
+        new_satoshis: ticket_satoshis,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::TicketPriceChanged,
+        ticket_satoshis,
+    )?;
+
+    Ok(())
+}
+
+// Runs with the first priced purchase, before check_price_circuit_breaker records the open ticket
+// price. The reference price is the first peg component's feed, 0 in FixedUsd mode. The purchase
+// pricing already passed the confidence check on the same values
+pub fn snapshot_open_prices(
+    round: &mut Round,
+    reference_price_feed: &AccountInfo,
+    sol_price_feed: &AccountInfo,
+    feed_reader: &FeedReader,
+) -> Result<()> {
+    if round.open_ticket_price != 0 {
+        return Ok(());
+    }
+
+    let clock = Clock::get()?;
+    let sampling = PriceSampling::for_round(round);
+    let sol_price = feed_reader.read(sol_price_feed, &sampling, |feed| {
+        get_sol_price(feed, clock.slot, 0, &sampling, feed_reader.feed_registry())
+    })?;
+    round.open_sol_price_usd = to_micro_usd(sol_price)?;
+    round.open_reference_price_usd = match round.pricing_mode {
+        PricingMode::Pegged => to_micro_usd(feed_reader.read(reference_price_feed, &sampling, |feed| {
+            get_reference_price(feed, clock.slot, 0, &sampling, feed_reader.feed_registry())
+        })?)?,
+        PricingMode::FixedUsd => 0,
+    };
+    round.open_price_slot = clock.slot;
+
+    msg!(
+        "Round {} opens at SOL {} / reference {} micro-USD",
+        round.round_id,
+        round.open_sol_price_usd,
+        round.open_reference_price_usd
+    );
+
+    Ok(())
+}
+
+// The first priced purchase records the open price, and commits it for rounds created with
+// commit_price. Later ones must stay within the band
+pub fn check_price_circuit_breaker(
+    round: &mut Round,
+    max_deviation_bps: u16,
+    ticket_price: u64,
+) -> Result<()> {
+    if round.open_ticket_price == 0 {
+        round.open_ticket_price = ticket_price;
+        if round.commit_price {
+            round.ticket_price_lamports = ticket_price;
+            msg!("✅ Round {} ticket price committed at {} lamports", round.round_id, ticket_price);
+        }
+        return Ok(());
+    }
+
+    if max_deviation_bps == 0 {
+        return Ok(());
+    }
+
+    let deviation = ticket_price.abs_diff(round.open_ticket_price) as u128;
+    let max_deviation = (round.open_ticket_price as u128)
+        .checked_mul(max_deviation_bps as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / 10_000;
+
+    if deviation > max_deviation {
+        msg!(
+            "Ticket price {} deviates from round open price {}",
+            ticket_price,
+            round.open_ticket_price
+        );
+        return err!(RaffleError::PriceCircuitBreaker);
+    }
+
+    Ok(())
+}
+
+// Only authority. Tickets cost `start_bps` of the oracle price at round start and fall to
+// `end_bps` at round end. Stepwise mode drops the price `steps` times at even intervals.
+// A mainnet profile rejects a schedule ending under MIN_MAINNET_TICKET_USD, a pegged raffle's
+// ticket is valued at the current feed values. remaining_accounts: the feed of every peg
+// component, in order, pegged raffles on mainnet only
+pub fn set_price_decay(
+    ctx: Context<SetPriceDecay>,
+    mode: PriceDecayMode,
+    start_bps: u16,
+    end_bps: u16,
+    steps: u8,
+) -> Result<()> {
+    require!(
+        start_bps <= 10_000 && end_bps > 0 && end_bps <= start_bps,
+        RaffleError::InvalidPriceDecayConfig
+    );
+    require!(
+        mode != PriceDecayMode::Stepwise || steps > 0,
+        RaffleError::InvalidPriceDecayConfig
+    );
+
+    let raffle_state = &ctx.accounts.raffle_state;
+    let cluster_profile = require_cluster_profile(raffle_state, ctx.accounts.cluster_profile.as_deref())?;
+    if is_mainnet(cluster_profile) {
+        let ticket_usd = match ctx.accounts.sol_raffle.pricing_mode {
+            PricingMode::FixedUsd => ctx.accounts.sol_raffle.fixed_ticket_price_usd,
+            PricingMode::Pegged => {
+                let feed_reader = FeedReader::new(
+                    require_feed_registry(raffle_state, ctx.accounts.feed_registry.as_deref())?,
+                    None,
+                    None,
+                    cluster_profile,
+                    ctx.remaining_accounts,
+                );
+                peg_usd(
+                    &ticket_peg(raffle_state),
+                    ctx.remaining_accounts,
+                    raffle_state.max_price_confidence_bps,
+                    &feed_reader,
+                )?
+            }
+        };
+        check_mainnet_floor(ticket_usd, lowest_price_bps(&mode, end_bps))?;
+    }
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.price_decay_mode = mode.clone();
+    sol_raffle.price_decay_start_bps = start_bps;
+    sol_raffle.price_decay_end_bps = end_bps;
+    sol_raffle.price_decay_steps = steps;
+
+    msg!(
+        "✅ Cadence {} price decay {:?}: {} -> {} bps",
+        sol_raffle.cadence_id,
+        mode,
+        start_bps,
+        end_bps
+    );
+
+    emit!(PriceDecayUpdated {
+        token_raffle: sol_raffle.key(),
+        mode,
+        start_bps,
+        end_bps,
+        steps,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceDecayChanged,
+        end_bps as u64,
+    )?;
+
+    Ok(())
+}
+
+// Ticket price at `now` after the raffle's decay schedule over the round's lifetime.
+// `oracle_price` is the live price the circuit breaker checked
+pub fn apply_price_decay(
+    sol_raffle: &TokenRaffle,
+    round: &Round,
+    oracle_price: u64,
+    now: i64,
+) -> Result<u64> {
+    let duration = round.end_time.saturating_sub(round.start_time);
+    if sol_raffle.price_decay_mode == PriceDecayMode::None || duration <= 0 {
+        return Ok(oracle_price);
+    }
+
+    let start_bps = sol_raffle.price_decay_start_bps as u128;
+    let decay_bps = start_bps - sol_raffle.price_decay_end_bps as u128;
+    let elapsed = now.clamp(round.start_time, round.end_time) - round.start_time;
+
+    let decayed_bps = match sol_raffle.price_decay_mode {
+        PriceDecayMode::None => 0,
+        PriceDecayMode::Linear => decay_bps * elapsed as u128 / duration as u128,
+        PriceDecayMode::Stepwise => {
+            // steps + 1 even intervals, the last one is priced at end_bps
+            let steps = sol_raffle.price_decay_steps as u128;
+            let step = (elapsed as u128 * (steps + 1) / duration as u128).min(steps);
+            decay_bps * step / steps
+        }
+    };
+
+    let price = (oracle_price as u128)
+        .checked_mul(start_bps - decayed_bps)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / 10_000;
+
+    u64::try_from(price).map_err(|_| ProgramError::ArithmeticOverflow.into())
+}
+
+pub fn get_sol_price(
+    sol_price_feed: &AccountInfo,
+    current_slot: u64,
+    max_confidence_bps: u16,
+    sampling: &PriceSampling,
+    feed_registry: Option<&FeedRegistry>,
+) -> Result<Decimal> {
+    msg!("Parsing SOL price feed...");
+    let limits = FeedRegistry::read_limits(feed_registry, &sol_price_feed.key(), 3);
+    read_feed_price(sol_price_feed, current_slot, &limits, max_confidence_bps, sampling)
+}
+
+// Runs a purchase's feed update, typically the Switchboard submit instruction with its oracle
+// signatures, CPI'd into the program owning the SOL feed. Returns the remaining accounts
+// left for pricing
+pub fn update_feeds<'a, 'info>(
+    feed_update: Option<&FeedUpdate>,
+    sol_price_feed: &AccountInfo<'info>,
+    switchboard_program: Option<&AccountInfo<'info>>,
+    remaining_accounts: &'a [AccountInfo<'info>],
+) -> Result<&'a [AccountInfo<'info>]> {
+    let Some(feed_update) = feed_update else {
+        return Ok(remaining_accounts);
+    };
+
+    let switchboard_program = switchboard_program.ok_or(RaffleError::MissingSwitchboardProgram)?;
+    require!(
+        switchboard_program.key() == *sol_price_feed.owner && switchboard_program.executable,
+        RaffleError::InvalidFeedUpdate
+    );
+
+    let split = remaining_accounts
+        .len()
+        .checked_sub(feed_update.accounts_len as usize)
+        .ok_or(RaffleError::NotEnoughRemainingAccounts)?;
+    let (price_accounts, update_accounts) = remaining_accounts.split_at(split);
+
+    let instruction = Instruction {
+        program_id: switchboard_program.key(),
+        accounts: update_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data: feed_update.data.clone(),
+    };
+
+    let mut account_infos = update_accounts.to_vec();
+    account_infos.push(switchboard_program.clone());
+    invoke(&instruction, &account_infos)?;
+
+    msg!("Feeds updated through {}", switchboard_program.key());
+
+    Ok(price_accounts)
+}
+
+// Slot of the SOL feed result a purchase was priced with, kept for audits
+pub fn sol_price_slot(sol_price_feed: &AccountInfo) -> Result<u64> {
+    result_slot(sol_price_feed)
+}
+
+fn result_slot(feed: &AccountInfo) -> Result<u64> {
+    let clock = Clock::get()?;
+    let pull_feed = parse_feed(feed).map_err(|reason| oracle_error(feed, reason, clock.slot))?;
+
+    Ok(pull_feed.result.slot)
+}
+
+// The parts of the live ticket price, read the way calculate_ticket_price_for_sol and
+// calculate_fixed_ticket_price do. With a round, in its pricing mode and price sampling, else the
+// raffle's pricing mode, pegged without a raffle
+pub fn price_breakdown(
+    raffle_state: &RaffleState,
+    sol_raffle: Option<&TokenRaffle>,
+    round: Option<&Round>,
+    feed_reader: &FeedReader,
+    reference_price_feed: &AccountInfo,
+    basket_feeds: &[AccountInfo],
+    sol_price_feed: &AccountInfo,
+) -> Result<PriceBreakdownView> {
+    let clock = Clock::get()?;
+    let max_confidence_bps = raffle_state.max_price_confidence_bps;
+    let decimals = quote_decimals(sol_raffle, sol_price_feed)?;
+
+    let (sampling, fixed_ticket_price_usd) = match (sol_raffle, round) {
+        (_, Some(round)) => {
+            let fixed_ticket_price_usd = match round.pricing_mode {
+                PricingMode::FixedUsd => Some(round.fixed_ticket_price_usd),
+                PricingMode::Pegged => None,
+            };
+            (PriceSampling::for_round(round), fixed_ticket_price_usd)
+        }
+        (Some(sol_raffle), None) if sol_raffle.pricing_mode == PricingMode::FixedUsd => {
+            (PriceSampling::LATEST, Some(sol_raffle.fixed_ticket_price_usd))
+        }
+        _ => (PriceSampling::LATEST, None),
+    };
+    let sampling = &sampling;
+
+    let sol_price = feed_reader.read(sol_price_feed, sampling, |feed| {
+        get_sol_price(feed, clock.slot, max_confidence_bps, sampling, feed_reader.feed_registry())
     })?;
 
-    let btc_price = btc_feed
-        .get_value(current_slot, 1500, 1, false)
-        .map_err(|e| {
-            msg!("BTC Switchboard get_value failed: {:?}", e);
-            RaffleError::OracleError
-        })?;
+    let (reference_price, ticket_price_usd, reference_slot) = match fixed_ticket_price_usd {
+        Some(fixed_ticket_price_usd) => {
+            let micro_usd = i64::try_from(fixed_ticket_price_usd).map_err(|_| ProgramError::ArithmeticOverflow)?;
+            (Decimal::ZERO, Decimal::new(micro_usd, USD_DECIMALS as u32), 0)
+        }
+        None => {
+            let reference_price = feed_reader.read(reference_price_feed, sampling, |feed| {
+                get_reference_price(feed, clock.slot, max_confidence_bps, sampling, feed_reader.feed_registry())
+            })?;
+            let ticket_price_usd =
+                calculate_ticket_usd(raffle_state, sampling, feed_reader, reference_price_feed, basket_feeds, clock.slot)?;
+            (reference_price, ticket_price_usd, result_slot(reference_price_feed)?)
+        }
+    };
+
+    Ok(PriceBreakdownView {
+        reference_usd: to_micro_usd(reference_price)?,
+        sol_usd: to_micro_usd(sol_price)?,
+        ticket_usd: to_micro_usd(ticket_price_usd)?,
+        ticket_lamports: calculate_ticket_lamports(ticket_price_usd, sol_price, decimals)?,
+        reference_slot,
+        sol_slot: result_slot(sol_price_feed)?,
+        slot: clock.slot,
+    })
+}
 
-    Ok(btc_price)
+pub fn get_reference_price(
+    reference_price_feed: &AccountInfo,
+    current_slot: u64,
+    max_confidence_bps: u16,
+    sampling: &PriceSampling,
+    feed_registry: Option<&FeedRegistry>,
+) -> Result<Decimal> {
+    msg!("Parsing reference price feed {}...", reference_price_feed.key());
+    let limits = FeedRegistry::read_limits(feed_registry, &reference_price_feed.key(), 1);
+    read_feed_price(reference_price_feed, current_slot, &limits, max_confidence_bps, sampling)
 }
 
-fn calculate_ticket_lamports(btc_price: Decimal, sol_price: Decimal) -> Result<u64> {
-    let ticket_price_btc =
-        Decimal::from_str(TICKET_BTC_STR).map_err(|_| ProgramError::ArithmeticOverflow)?;
-    msg!("Ticket price in BTC: {}", ticket_price_btc);
+// Every failure names the feed as the failing account, so clients can tell which one to
+// refresh or swap for a fallback
+fn read_feed_price(
+    feed: &AccountInfo,
+    current_slot: u64,
+    limits: &FeedLimits,
+    max_confidence_bps: u16,
+    sampling: &PriceSampling,
+) -> Result<Decimal> {
+    let pull_feed = parse_feed(feed).map_err(|reason| oracle_error(feed, reason, current_slot))?;
+
+    let price = match sampling.mode {
+        PriceSampleMode::Latest => pull_feed
+            .get_value(current_slot, limits.max_staleness_slots, limits.min_samples, false)
+            .map_err(|e| {
+                msg!("Switchboard get_value failed: {:?}", e);
+                let fresh_samples = fresh_samples(&pull_feed, current_slot, limits.max_staleness_slots).len();
+                oracle_error(feed, sample_failure(fresh_samples, limits.min_samples as usize), current_slot)
+            })?,
+        _ => sample_feed_value(feed, &pull_feed, current_slot, limits.max_staleness_slots, sampling)?,
+    };
+    if price <= Decimal::ZERO {
+        msg!("Feed value {} is not positive", price);
+        return Err(oracle_error(feed, OracleFailureReason::NonPositive, current_slot));
+    }
+    check_price_confidence(feed, &pull_feed, price, max_confidence_bps, current_slot)?;
+
+    Ok(price)
+}
+
+// Every feed account is parsed here. Only On-Demand pull feeds price tickets, a legacy V2
+// aggregator is told apart from other data so clients move to its pull feed instead of retrying
+fn parse_feed<'a>(feed: &'a AccountInfo) -> std::result::Result<Ref<'a, PullFeedAccountData>, OracleFailureReason> {
+    let parse_error = match PullFeedAccountData::parse(feed.data.borrow()) {
+        Ok(pull_feed) => return Ok(pull_feed),
+        Err(error) => error,
+    };
+    msg!("Switchboard parse failed: {:?}", parse_error);
+
+    if is_legacy_aggregator(feed) {
+        msg!("Feed {} is a legacy Switchboard aggregator", feed.key());
+        return Err(OracleFailureReason::LegacyFeed);
+    }
+
+    Err(OracleFailureReason::Parse)
+}
+
+fn is_legacy_aggregator(feed: &AccountInfo) -> bool {
+    let discriminator = hash(b"account:AggregatorAccountData").to_bytes();
+
+    feed.try_borrow_data()
+        .map(|data| data.len() >= 8 && data[..8] == discriminator[..8])
+        .unwrap_or(false)
+}
+
+// The event outlives the failed transaction in its logs, a fallback feed that fails while a
+// later one prices the purchase is reported too
+fn oracle_error(feed: &AccountInfo, reason: OracleFailureReason, slot: u64) -> Error {
+    emit!(OracleFailure {
+        feed: feed.key(),
+        reason: reason.clone(),
+        slot,
+    });
+
+    let error = match reason {
+        OracleFailureReason::Parse => RaffleError::OracleParseFailed,
+        OracleFailureReason::LegacyFeed => RaffleError::OracleLegacyFeed,
+        OracleFailureReason::Stale => RaffleError::OracleStale,
+        OracleFailureReason::NotEnoughSamples => RaffleError::NotEnoughPriceSamples,
+        OracleFailureReason::NonPositive => RaffleError::OracleNonPositivePrice,
+        OracleFailureReason::LowConfidence => RaffleError::LowConfidencePrice,
+        OracleFailureReason::Other => RaffleError::OracleError,
+    };
+
+    error!(error).with_account_name(feed.key().to_string())
+}
 
-    let ticket_price_usd = btc_price
-        .checked_mul(ticket_price_btc)
+// Why a feed with `fresh_samples` samples can't give a value needing `required`
+fn sample_failure(fresh_samples: usize, required: usize) -> OracleFailureReason {
+    if fresh_samples == 0 {
+        OracleFailureReason::Stale
+    } else if fresh_samples < required {
+        OracleFailureReason::NotEnoughSamples
+    } else {
+        OracleFailureReason::Other
+    }
+}
+
+// (slot, value) of the submissions at most `max_staleness_slots` old
+fn fresh_samples(pull_feed: &PullFeedAccountData, current_slot: u64, max_staleness_slots: u64) -> Vec<(u64, i128)> {
+    pull_feed
+        .submissions
+        .iter()
+        .filter(|submission| {
+            submission.slot > 0 && current_slot.saturating_sub(submission.slot) <= max_staleness_slots
+        })
+        .map(|submission| (submission.slot, submission.value))
+        .collect()
+}
+
+// Min or median of the `count` most recent submissions that aren't stale, an even count takes
+// the mean of the two middle values
+fn sample_feed_value(
+    feed: &AccountInfo,
+    pull_feed: &PullFeedAccountData,
+    current_slot: u64,
+    max_staleness_slots: u64,
+    sampling: &PriceSampling,
+) -> Result<Decimal> {
+    let mut samples = fresh_samples(pull_feed, current_slot, max_staleness_slots);
+
+    let count = sampling.count as usize;
+    if samples.len() < count {
+        msg!("Feed has {} fresh samples, {} required", samples.len(), count);
+        return Err(oracle_error(feed, sample_failure(samples.len(), count), current_slot));
+    }
+
+    samples.sort_unstable_by_key(|&(slot, _)| std::cmp::Reverse(slot));
+    let mut values: Vec<i128> = samples[..count].iter().map(|(_, value)| *value).collect();
+    values.sort_unstable();
+
+    let value = match sampling.mode {
+        PriceSampleMode::Min => values[0],
+        _ if count.is_multiple_of(2) => (values[count / 2 - 1] + values[count / 2]) / 2,
+        _ => values[count / 2],
+    };
+    msg!("{:?} of {} samples: {}", sampling.mode, count, value);
+
+    Decimal::try_from_i128_with_scale(value, SUBMISSION_DECIMALS)
+        .map_err(|_| ProgramError::ArithmeticOverflow.into())
+}
+
+// The oracles' standard deviation around the value stands for its confidence interval
+fn check_price_confidence(
+    feed: &AccountInfo,
+    pull_feed: &PullFeedAccountData,
+    price: Decimal,
+    max_confidence_bps: u16,
+    current_slot: u64,
+) -> Result<()> {
+    if max_confidence_bps == 0 {
+        return Ok(());
+    }
+
+    let std_dev = pull_feed
+        .result
+        .std_dev()
+        .ok_or_else(|| oracle_error(feed, OracleFailureReason::LowConfidence, current_slot))?;
+    let max_std_dev = price
+        .checked_mul(Decimal::from(max_confidence_bps))
+        .and_then(|bps| bps.checked_div(Decimal::from(10_000u64)))
         .ok_or(ProgramError::ArithmeticOverflow)?;
-    msg!("Ticket price in USD: {}", ticket_price_usd);
 
+    if std_dev > max_std_dev {
+        msg!("Price {} has std dev {}, above {} bps", price, std_dev, max_confidence_bps);
+        return Err(oracle_error(feed, OracleFailureReason::LowConfidence, current_slot));
+    }
+
+    Ok(())
+}
+
+fn is_mainnet(cluster_profile: Option<&ClusterProfile>) -> bool {
+    cluster_profile.is_some_and(|cluster_profile| cluster_profile.cluster == Cluster::Mainnet)
+}
+
+// Lowest share of the oracle price a ticket sells at under the decay schedule
+fn lowest_price_bps(mode: &PriceDecayMode, end_bps: u16) -> u16 {
+    if *mode == PriceDecayMode::None { 10_000 } else { end_bps }
+}
+
+// `ticket_usd` in micro-USD before price decay
+fn check_mainnet_floor(ticket_usd: u64, lowest_bps: u16) -> Result<()> {
+    let lowest_usd = ticket_usd as u128 * lowest_bps as u128 / 10_000;
+    if lowest_usd < MIN_MAINNET_TICKET_USD as u128 {
+        msg!("Ticket price {} micro-USD falls to {}", ticket_usd, lowest_usd);
+        return err!(RaffleError::MainnetPriceFloor);
+    }
+    Ok(())
+}
+
+// Value of `components` at the latest feed values, in micro-USD. `feeds` in component order
+fn peg_usd(
+    components: &[PegComponent],
+    feeds: &[AccountInfo],
+    max_confidence_bps: u16,
+    feed_reader: &FeedReader,
+) -> Result<u64> {
+    require!(feeds.len() >= components.len(), RaffleError::InvalidFeedAccount);
+    let current_slot = Clock::get()?.slot;
+
+    let mut ticket_usd = Decimal::ZERO;
+    for (component, feed) in components.iter().zip(feeds.iter()) {
+        let component_usd = get_component_usd(
+            component,
+            feed,
+            current_slot,
+            max_confidence_bps,
+            &PriceSampling::LATEST,
+            feed_reader,
+        )?;
+        ticket_usd = ticket_usd
+            .checked_add(component_usd)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    to_micro_usd(ticket_usd)
+}
+
+fn get_component_usd(
+    component: &PegComponent,
+    feed: &AccountInfo,
+    current_slot: u64,
+    max_confidence_bps: u16,
+    sampling: &PriceSampling,
+    feed_reader: &FeedReader,
+) -> Result<Decimal> {
+    require!(
+        component.feed == Pubkey::default() || component.feed == feed.key(),
+        RaffleError::InvalidFeedAccount
+    );
+
+    let price = feed_reader.read(feed, sampling, |feed| {
+        get_reference_price(feed, current_slot, max_confidence_bps, sampling, feed_reader.feed_registry())
+    })?;
+    let amount = i64::try_from(component.amount).map_err(|_| ProgramError::ArithmeticOverflow)?;
+    let component_usd = price
+        .checked_mul(Decimal::new(amount, component.decimals as u32))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    msg!("Peg component {} {}e-{}: {} USD", feed.key(), amount, component.decimals, component_usd);
+
+    Ok(component_usd)
+}
+
+// Sum of every peg component's amount times its USD price
+fn calculate_ticket_usd(
+    raffle_state: &RaffleState,
+    sampling: &PriceSampling,
+    feed_reader: &FeedReader,
+    reference_price_feed: &AccountInfo,
+    basket_feeds: &[AccountInfo],
+    current_slot: u64,
+) -> Result<Decimal> {
+    require!(
+        !raffle_state.ticket_peg.is_empty() || !raffle_state.cluster_profile_enabled,
+        RaffleError::TicketPegFeedRequired
+    );
+    let peg = ticket_peg(raffle_state);
+    require!(basket_feeds.len() + 1 >= peg.len(), RaffleError::InvalidFeedAccount);
+    let max_confidence_bps = raffle_state.max_price_confidence_bps;
+
+    let mut ticket_price_usd = Decimal::ZERO;
+
+    for (index, component) in peg.iter().enumerate() {
+        let component_usd = match index {
+            0 => get_component_usd(
+                component,
+                reference_price_feed,
+                current_slot,
+                max_confidence_bps,
+                sampling,
+                feed_reader,
+            )?,
+            _ => get_component_usd(
+                component,
+                &basket_feeds[index - 1],
+                current_slot,
+                max_confidence_bps,
+                sampling,
+                feed_reader,
+            )?,
+        };
+
+        ticket_price_usd = ticket_price_usd
+            .checked_add(component_usd)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    Ok(ticket_price_usd)
+}
+
+fn to_micro_usd(price: Decimal) -> Result<u64> {
+    price
+        .checked_mul(Decimal::from(10u64.pow(USD_DECIMALS as u32)))
+        .and_then(|micro_usd| micro_usd.round().to_u64())
+        .ok_or(ProgramError::ArithmeticOverflow.into())
+}
+
+// In base units of `decimals`, lamports at SOL_DECIMALS
+fn calculate_ticket_lamports(ticket_price_usd: Decimal, sol_price: Decimal, decimals: u8) -> Result<u64> {
     let ticket_price_sol = ticket_price_usd
         .checked_div(sol_price)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     msg!("Ticket price in SOL: {}", ticket_price_sol);
 
     let lamports_decimal = ticket_price_sol
-        .checked_mul(Decimal::from(LAMPORTS_PER_SOL))
+        .checked_mul(Decimal::from(10u64.pow(decimals as u32)))
         .ok_or(ProgramError::ArithmeticOverflow)?;
     msg!("Lamports (Decimal): {}", lamports_decimal);
 
@@ -102,3 +1033,463 @@ fn calculate_ticket_lamports(btc_price: Decimal, sol_price: Decimal) -> Result<u
 
     Ok(lamports)
 }
+
+// Only authority
+pub fn initialize_feed_registry(ctx: Context<InitializeFeedRegistry>) -> Result<()> {
+    let feed_registry = &mut ctx.accounts.feed_registry;
+    feed_registry.feeds = Vec::new();
+    feed_registry.feed_limits = Vec::new();
+    feed_registry.bump = ctx.bumps.feed_registry;
+
+    ctx.accounts.raffle_state.feed_registry_enabled = true;
+
+    msg!("✅ Feed registry initialized");
+
+    Ok(())
+}
+
+// Only authority. Approves the Switchboard feed quoting `mint`, replacing an earlier one
+pub fn register_price_feed(ctx: Context<UpdateFeedRegistry>, mint: Pubkey, feed: Pubkey) -> Result<()> {
+    let feed_registry = &mut ctx.accounts.feed_registry;
+
+    match feed_registry.feeds.iter_mut().find(|entry| entry.mint == mint) {
+        // A new feed starts from the default read limits
+        Some(entry) if entry.feed != feed => {
+            let replaced = std::mem::replace(&mut entry.feed, feed);
+            feed_registry.feed_limits.retain(|limits| limits.feed != replaced);
+        }
+        Some(_) => {}
+        None => {
+            require!(
+                feed_registry.feeds.len() < MAX_REGISTERED_FEEDS,
+                RaffleError::FeedRegistryFull
+            );
+            feed_registry.feeds.push(RegisteredFeed { mint, feed });
+        }
+    }
+
+    emit!(PriceFeedRegistered { mint, feed });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceFeedRegistered,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. How stale and how thinly sampled a registered feed may be when read, tuned to
+// its update cadence. 0 - the default for that value
+pub fn set_feed_read_limits(
+    ctx: Context<UpdateFeedRegistry>,
+    mint: Pubkey,
+    max_staleness_slots: u64,
+    min_samples: u8,
+) -> Result<()> {
+    require!(
+        max_staleness_slots <= MAX_FEED_STALENESS_SLOTS && min_samples <= MAX_PRICE_SAMPLES,
+        RaffleError::InvalidFeedReadLimits
+    );
+
+    let feed_registry = &mut ctx.accounts.feed_registry;
+    let feed = feed_registry
+        .feeds
+        .iter()
+        .find(|entry| entry.mint == mint)
+        .map(|entry| entry.feed)
+        .ok_or(RaffleError::FeedNotRegistered)?;
+
+    let limits = RegisteredFeedLimits {
+        feed,
+        max_staleness_slots,
+        min_samples,
+    };
+    match feed_registry.feed_limits.iter_mut().find(|limits| limits.feed == feed) {
+        Some(entry) => *entry = limits,
+        None => feed_registry.feed_limits.push(limits),
+    }
+
+    emit!(FeedReadLimitsUpdated {
+        mint,
+        feed,
+        max_staleness_slots,
+        min_samples,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::FeedReadLimitsChanged,
+        max_staleness_slots,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Existing raffles keep their feed, only new raffles are affected
+pub fn unregister_price_feed(ctx: Context<UpdateFeedRegistry>, mint: Pubkey) -> Result<()> {
+    let feed_registry = &mut ctx.accounts.feed_registry;
+    let feed = feed_registry
+        .feeds
+        .iter()
+        .find(|entry| entry.mint == mint)
+        .map(|entry| entry.feed)
+        .ok_or(RaffleError::FeedNotRegistered)?;
+    feed_registry.feeds.retain(|entry| entry.mint != mint);
+    feed_registry.feed_limits.retain(|limits| limits.feed != feed);
+
+    emit!(PriceFeedUnregistered { mint });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PriceFeedUnregistered,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// With the registry set up it must be passed, so leaving it out doesn't drop the per-feed limits
+pub fn require_feed_registry<'a>(
+    raffle_state: &RaffleState,
+    feed_registry: Option<&'a FeedRegistry>,
+) -> Result<Option<&'a FeedRegistry>> {
+    match feed_registry {
+        None if raffle_state.feed_registry_enabled => err!(RaffleError::FeedRegistryRequired),
+        feed_registry => Ok(feed_registry),
+    }
+}
+
+// get_value parameters a feed is read with
+pub struct FeedLimits {
+    pub max_staleness_slots: u64,
+    pub min_samples: u32,
+}
+
+impl FeedRegistry {
+    // The registered feed's limits, MAX_STALENESS_SLOTS and `default_min_samples` where unset
+    pub fn read_limits(feed_registry: Option<&Self>, feed: &Pubkey, default_min_samples: u32) -> FeedLimits {
+        let entry = feed_registry.and_then(|feed_registry| {
+            feed_registry.feed_limits.iter().find(|limits| limits.feed == *feed)
+        });
+
+        FeedLimits {
+            max_staleness_slots: entry
+                .map(|entry| entry.max_staleness_slots)
+                .filter(|slots| *slots > 0)
+                .unwrap_or(MAX_STALENESS_SLOTS),
+            min_samples: entry
+                .map(|entry| entry.min_samples as u32)
+                .filter(|samples| *samples > 0)
+                .unwrap_or(default_min_samples),
+        }
+    }
+
+    pub fn is_registered(&self, mint: &Pubkey, feed: &Pubkey) -> bool {
+        self.feeds
+            .iter()
+            .any(|entry| entry.mint == *mint && entry.feed == *feed)
+    }
+}
+
+// Rejects accounts that don't parse as a Switchboard pull feed
+pub fn check_feed_account(feed: &AccountInfo) -> Result<()> {
+    parse_feed(feed).map_err(|_| RaffleError::InvalidFeedAccount)?;
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeFeedRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FeedRegistry::INIT_SPACE,
+        seeds = [b"feed_registry"],
+        bump
+    )]
+    pub feed_registry: Account<'info, FeedRegistry>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct UpdateFeedRegistry<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Account<'info, FeedRegistry>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetPricingMode<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    // Required once the cluster profile is set up
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetTicketPeg<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    // Required once the cluster profile is set up
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Required once the registry is set up, read on mainnet only
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetPriceDecay<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    // Required once the cluster profile is set up
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Required once the registry is set up, read for pegged raffles on mainnet only
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+}
+
+// Account data structs
+// `amount` units of the asset quoted by `feed`, scaled down by 10^decimals
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct PegComponent {
+    pub feed: Pubkey, // default - legacy BTC peg, any feed accepted
+    pub amount: u64,
+    pub decimals: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum OracleFailureReason {
+    Parse, // 0 - Not a Switchboard pull feed
+    Stale, // 1 - No submission within the staleness limit
+    NotEnoughSamples, // 2 - Fresh submissions, fewer than required
+    NonPositive, // 3 - Value at or below zero
+    LowConfidence, // 4 - Std dev above max_price_confidence_bps
+    Other, // 5 - Rejected by the feed for another reason
+    LegacyFeed, // 6 - Switchboard V2 aggregator, not an On-Demand pull feed
+}
+
+// Instruction data for the feed owner program, its accounts are the last `accounts_len`
+// remaining accounts
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct FeedUpdate {
+    pub data: Vec<u8>,
+    pub accounts_len: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct RegisteredFeed {
+    pub mint: Pubkey,
+    pub feed: Pubkey, // Switchboard pull feed quoting the mint in USD
+}
+
+// Kept apart from RegisteredFeed so the registry layout only grows at the end
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
+pub struct RegisteredFeedLimits {
+    pub feed: Pubkey,
+    pub max_staleness_slots: u64, // 0 - MAX_STALENESS_SLOTS
+    pub min_samples: u8, // 0 - 3 for the SOL feed, 1 for others
+}
+
+// Price feeds approved for new raffles, one per mint
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct FeedRegistry {
+    #[max_len(MAX_REGISTERED_FEEDS)]
+    pub feeds: Vec<RegisteredFeed>,
+    pub bump: u8,
+    #[max_len(MAX_REGISTERED_FEEDS)]
+    pub feed_limits: Vec<RegisteredFeedLimits>, // set_feed_read_limits, unlisted feeds use the defaults
+}
+
+// Events
+#[event]
+pub struct OracleFailure {
+    pub feed: Pubkey,
+    pub reason: OracleFailureReason,
+    pub slot: u64,
+}
+
+#[event]
+pub struct PriceCircuitBreakerUpdated {
+    pub max_deviation_bps: u16,
+}
+
+#[event]
+pub struct PriceConfidenceUpdated {
+    pub max_confidence_bps: u16,
+}
+
+#[event]
+pub struct PriceDecayUpdated {
+    pub token_raffle: Pubkey,
+    pub mode: PriceDecayMode,
+    pub start_bps: u16,
+    pub end_bps: u16,
+    pub steps: u8,
+}
+
+#[event]
+pub struct FeedReadLimitsUpdated {
+    pub mint: Pubkey,
+    pub feed: Pubkey,
+    pub max_staleness_slots: u64,
+    pub min_samples: u8,
+}
+
+#[event]
+pub struct PriceFeedRegistered {
+    pub mint: Pubkey,
+    pub feed: Pubkey,
+}
+
+#[event]
+pub struct PriceFeedUnregistered {
+    pub mint: Pubkey,
+}
+
+#[event]
+pub struct TicketPriceUpdated {
+    pub old_satoshis: u64,
+    pub new_satoshis: u64,
+}
+
+#[event]
+pub struct TicketPegUpdated {
+    pub components: Vec<PegComponent>,
+}
+
+#[event]
+pub struct CommitRoundPriceUpdated {
+    pub token_raffle: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct PriceSamplingUpdated {
+    pub token_raffle: Pubkey,
+    pub mode: PriceSampleMode,
+    pub count: u8,
+}
+
+#[event]
+pub struct PricingModeUpdated {
+    pub token_raffle: Pubkey,
+    pub mode: PricingMode,
+    pub fixed_ticket_price_usd: u64,
+}
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RaffleError,
+    TokenRaffle,
+    UpdateTokenRaffle,
+    transfer,
+};
+use crate::audit::{record_audit, AuditAction};
+use crate::jackpot::MegaJackpot;
+use crate::solvency::record_in_ledger_account;
+
+// Only authority. Rounds opened from now on stop growing their prize at the cap, the excess
+// of later purchases goes to the next round's pot. None - uncapped
+pub fn set_prize_cap(ctx: Context<UpdateTokenRaffle>, prize_cap_lamports: Option<u64>) -> Result<()> {
+    require!(prize_cap_lamports != Some(0), RaffleError::InvalidAmount);
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.prize_cap_lamports = prize_cap_lamports;
+
+    emit!(PrizeCapUpdated {
+        cadence_id: sol_raffle.cadence_id,
+        prize_cap_lamports,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PrizeCapChanged,
+        prize_cap_lamports.unwrap_or(0),
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Rounds opened from now on are drawn with at least this prize, shortfalls
+// come from insurance_vault and then the mega jackpot. 0 - no floor
+pub fn set_min_prize(ctx: Context<UpdateTokenRaffle>, min_prize_lamports: u64) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.min_prize_lamports = min_prize_lamports;
+
+    emit!(MinPrizeUpdated {
+        cadence_id: sol_raffle.cadence_id,
+        min_prize_lamports,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::MinPrizeChanged,
+        min_prize_lamports,
+    )?;
+
+    Ok(())
+}
+
+// Runs once as the round moves to Drawing, its pot is final by then. Insurance is always drawn
+// first, the mega jackpot only covers what it can't, so the jackpot is skipped along with it when
+// insurance_vault isn't passed. A shortfall left uncovered doesn't block the draw. Returns the
+// amount added
+pub fn top_up_min_prize<'info>(
+    round: &mut Round,
+    vault: &AccountInfo<'info>,
+    vault_ledger: &AccountInfo<'info>,
+    insurance_vault: Option<&AccountInfo<'info>>,
+    mega_jackpot: Option<&mut Account<'info, MegaJackpot>>,
+    system_program: &AccountInfo<'info>,
+) -> Result<u64> {
+    let shortfall = round.min_prize_lamports.saturating_sub(round.prize_amount);
+    if shortfall == 0 {
+        return Ok(0);
+    }
+    let Some(insurance_vault) = insurance_vault else {
+        msg!("Round {} prize floor not topped up, insurance_vault not passed", round.round_id);
+        return Ok(0);
+    };
+
+    // The vault stays rent exempt
+    let available = insurance_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    let from_insurance = shortfall.min(available);
+
+    if from_insurance > 0 {
+        let (_, insurance_vault_bump) = Pubkey::find_program_address(&[b"insurance_vault"], &crate::id());
+        let insurance_vault_seeds: &[&[&[u8]]] = &[&[b"insurance_vault", &[insurance_vault_bump]]];
+
+        transfer(
+            system_program.clone(),
+            insurance_vault.clone(),
+            vault.clone(),
+            from_insurance,
+            Some(insurance_vault_seeds),
+        )?;
+    }
+
+    let mut from_jackpot = 0;
+    if let Some(mega_jackpot) = mega_jackpot {
+        from_jackpot = (shortfall - from_insurance).min(mega_jackpot.balance);
+
+        if from_jackpot > 0 {
+            **mega_jackpot.to_account_info().try_borrow_mut_lamports()? -= from_jackpot;
+            **vault.try_borrow_mut_lamports()? += from_jackpot;
+
+            mega_jackpot.balance -= from_jackpot;
+            mega_jackpot.total_paid = mega_jackpot
+                .total_paid
+                .checked_add(from_jackpot)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+    }
+
+    let topped_up = from_insurance + from_jackpot;
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(topped_up)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.prize_top_up = topped_up;
+    record_in_ledger_account(vault_ledger, topped_up)?;
+
+    msg!("✅ Round {} prize topped up by {} lamports", round.round_id, topped_up);
+
+    emit!(MinPrizeToppedUp {
+        round_id: round.round_id,
+        from_insurance,
+        from_jackpot,
+        prize_amount: round.prize_amount,
+        min_prize_lamports: round.min_prize_lamports,
+    });
+
+    Ok(topped_up)
+}
+
+// Adds a purchase's prize part to the round, above the cap it's held in carryover_out
+// until the round is drawn. Returns the part held back
+pub fn add_capped_prize(round: &mut Round, prize_amount: u64) -> Result<u64> {
+    let room = match round.prize_cap_lamports {
+        Some(prize_cap) => prize_cap.saturating_sub(round.prize_amount),
+        None => u64::MAX,
+    };
+    let kept = prize_amount.min(room);
+    let excess = prize_amount - kept;
+
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(kept)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.carryover_out = round
+        .carryover_out
+        .checked_add(excess)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(excess)
+}
+
+// Cancelled purchases take their prize part back from the held excess first
+pub fn remove_capped_prize(round: &mut Round, prize_amount: u64) -> Result<()> {
+    let from_carryover = prize_amount.min(round.carryover_out);
+    round.carryover_out -= from_carryover;
+    round.prize_amount = round
+        .prize_amount
+        .checked_sub(prize_amount - from_carryover)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// The lamports stay in sol_vault, only the round they are owed to changes
+pub fn pass_on_carryover(sol_raffle: &mut TokenRaffle, amount: u64) -> Result<()> {
+    sol_raffle.carryover = sol_raffle
+        .carryover
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Events
+#[event]
+pub struct PrizeCapUpdated {
+    pub cadence_id: u8,
+    pub prize_cap_lamports: Option<u64>,
+}
+
+#[event]
+pub struct MinPrizeUpdated {
+    pub cadence_id: u8,
+    pub min_prize_lamports: u64,
+}
+
+#[event]
+pub struct MinPrizeToppedUp {
+    pub round_id: u32,
+    pub from_insurance: u64,
+    pub from_jackpot: u64,
+    pub prize_amount: u64,
+    pub min_prize_lamports: u64,
+}
@@ -0,0 +1,937 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RoundTicketsPurchase,
+    RaffleError,
+    RaffleState,
+    RoundAccounts,
+    RoundBumps,
+    TokenRaffle,
+    PendingQueue,
+    UpdateRaffleState,
+    append_cumulative_tickets,
+    create_round_accounts,
+    initialize_round_tickets_purchase,
+    transfer,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::access_list::require_entry_allowed;
+use crate::blocklist::Blocklist;
+use crate::cluster_profile::{check_cluster_sol_feed, ClusterProfile};
+use crate::daily_stats::{record_daily_purchase, utc_day, DailyStats};
+use crate::oracle_failover::{FeedReader, FeedFailover};
+use crate::price_cache::{require_price_cache, PriceCache};
+use crate::price_feeds::{require_feed_registry, FeedRegistry};
+use crate::price_feeds::{calculate_round_ticket_price, check_price_circuit_breaker, snapshot_open_prices};
+use crate::prize_limits::add_capped_prize;
+use crate::purchase_limits::{record_wallet_purchase, WalletPurchases};
+use crate::raffle_stats::{record_raffle_purchase, RaffleStats};
+use crate::records::record_purchase;
+use crate::schedule::close_at_prize_target;
+use crate::solvency::record_in_ledger_account;
+
+// Only authority. Pubkey::default() removes the operator
+pub fn set_operator(ctx: Context<UpdateRaffleState>, operator: Pubkey) -> Result<()> {
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    let old_operator = raffle_state.operator;
+    raffle_state.operator = operator;
+
+    emit!(OperatorChanged {
+        old_operator,
+        new_operator: operator,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::OperatorChanged,
+        0,
+    )?;
+
+    Ok(())
+}
+
+pub fn fund_promo_vault(ctx: Context<FundPromoVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.funder.to_account_info(),
+        ctx.accounts.promo_vault.to_account_info(),
+        amount,
+        None,
+    )?;
+
+    msg!("Promo vault funded with {} lamports", amount);
+    Ok(())
+}
+
+// Only authority
+pub fn withdraw_promo_vault(ctx: Context<WithdrawPromoVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    let promo_vault_seeds: &[&[&[u8]]] = &[&[b"promo_vault", &[ctx.bumps.promo_vault]]];
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.promo_vault.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        amount,
+        Some(promo_vault_seeds),
+    )?;
+
+    msg!("Withdrawn {} lamports from promo vault", amount);
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PromoVaultWithdrawn,
+        amount,
+    )?;
+
+    Ok(())
+}
+
+// Appends a zero-cost purchase for `player`. The tickets' value at the current
+// price is moved from promo_vault into the round's prize so the pot stays backed
+pub fn grant_promo_tickets(
+    ctx: Context<GrantPromoTickets>,
+    round_id: u32,
+    purchase_index: u32,
+    player: Pubkey,
+    count: u32,
+    access_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(count > 0, RaffleError::InvalidTicketCount);
+    require!(
+        ctx.accounts.raffle_state.is_operator(&ctx.accounts.operator.key()),
+        RaffleError::Unauthorized
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+        RaffleError::MaintenanceMode
+    );
+    require!(!ctx.accounts.sol_raffle.sales_paused, RaffleError::SalesPaused);
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    let round = &mut ctx.accounts.round;
+
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require_entry_allowed(
+        &ctx.accounts.raffle_state,
+        sol_raffle,
+        round,
+        ctx.accounts.blocklist.as_ref(),
+        ctx.accounts.attestation.as_ref(),
+        &player,
+        &access_proof,
+    )?;
+    require!(current_time < round.end_time, RaffleError::RoundNotAvailable);
+    require_eq!(
+        purchase_index,
+        round.purchases_count,
+        RaffleError::PurchaseIndexMismatch
+    );
+
+    let cluster_profile = check_cluster_sol_feed(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_price_feed,
+    )?;
+    let feed_reader = FeedReader::new(
+        require_feed_registry(&ctx.accounts.raffle_state, ctx.accounts.feed_registry.as_deref())?,
+        ctx.accounts.feed_failover.as_deref(),
+        require_price_cache(&ctx.accounts.raffle_state, ctx.accounts.price_cache.as_deref())?,
+        cluster_profile,
+        ctx.remaining_accounts,
+    );
+    let ticket_price = calculate_round_ticket_price(
+        &ctx.accounts.raffle_state,
+        sol_raffle,
+        round,
+        &feed_reader,
+        &ctx.accounts.reference_price_feed,
+        ctx.remaining_accounts,
+        &ctx.accounts.sol_price_feed,
+    )?;
+    require!(ticket_price > 0, RaffleError::InvalidTicketPrice);
+    snapshot_open_prices(round, &ctx.accounts.reference_price_feed, &ctx.accounts.sol_price_feed, &feed_reader)?;
+    check_price_circuit_breaker(
+        round,
+        ctx.accounts.raffle_state.max_price_deviation_bps,
+        ticket_price,
+    )?;
+
+    let promo_value = ticket_price
+        .checked_mul(count as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // The vault stays rent exempt
+    require!(
+        ctx.accounts.promo_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)) >= promo_value,
+        RaffleError::PromoBudgetExhausted
+    );
+
+    let promo_vault_seeds: &[&[&[u8]]] = &[&[b"promo_vault", &[ctx.bumps.promo_vault]]];
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.promo_vault.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        promo_value,
+        Some(promo_vault_seeds),
+    )?;
+    record_in_ledger_account(&ctx.accounts.vault_ledger, promo_value)?;
+
+    let new_total = round
+        .total_tickets
+        .checked_add(count)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    append_cumulative_tickets(&ctx.accounts.round_tickets, new_total)?;
+
+    initialize_round_tickets_purchase(
+        &mut ctx.accounts.round_tickets_purchase,
+        round.key(),
+        round.purchases_count,
+        player,
+        count,
+        ctx.bumps.round_tickets_purchase,
+    )?;
+
+    record_wallet_purchase(
+        ctx.accounts.wallet_purchases.as_mut(),
+        round.key(),
+        player,
+        ctx.bumps.wallet_purchases,
+        sol_raffle.max_purchases_per_wallet,
+    )?;
+
+    // Paid from promo_vault, no ticket volume
+    record_raffle_purchase(
+        &mut ctx.accounts.raffle_stats,
+        sol_raffle.key(),
+        ctx.bumps.raffle_stats,
+        round.purchases_count == 0,
+        count,
+        0,
+    )?;
+    record_purchase(&ctx.accounts.records, round, player, promo_value, 0, current_time)?;
+    record_daily_purchase(
+        &mut ctx.accounts.daily_stats,
+        sol_raffle.key(),
+        ctx.bumps.daily_stats,
+        None,
+        count,
+        0,
+        current_time,
+    )?;
+
+    add_capped_prize(round, promo_value)?;
+    round.sponsored_amount = round
+        .sponsored_amount
+        .checked_add(promo_value)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.total_tickets = new_total;
+    round.purchases_count += 1;
+    close_at_prize_target(sol_raffle, round, current_time);
+
+    msg!("✅ Granted {} promo tickets to {} in round {}", count, player, round_id);
+
+    emit!(PromoTicketsGranted {
+        token: sol_raffle.token_mint,
+        round_id,
+        player,
+        operator: ctx.accounts.operator.key(),
+        purchase_index,
+        count,
+        promo_value,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// Creates the next round as a free-entry round. The whole prize is deposited
+// from promo_vault, tickets cost nothing and are capped per wallet
+pub fn open_free_entry_round(
+    ctx: Context<OpenFreeEntryRound>,
+    round_id: u32,
+    prize_amount: u64,
+    max_tickets_per_wallet: u32,
+) -> Result<()> {
+    require!(prize_amount > 0, RaffleError::InvalidAmount);
+    require!(max_tickets_per_wallet > 0, RaffleError::InvalidTicketCount);
+    require!(
+        ctx.accounts.raffle_state.is_operator(&ctx.accounts.operator.key()),
+        RaffleError::Unauthorized
+    );
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+        RaffleError::MaintenanceMode
+    );
+    require!(!ctx.accounts.sol_raffle.sales_paused, RaffleError::SalesPaused);
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+
+    require!(
+        ctx.accounts.round.data_len() == 0,
+        RaffleError::RoundAlreadyExists
+    );
+    if let Some(end_time) = sol_raffle.current_round_end_time {
+        require!(current_time >= end_time, RaffleError::RoundNotEndedYet);
+    }
+    // The vault stays rent exempt
+    require!(
+        ctx.accounts.promo_vault.lamports().saturating_sub(Rent::get()?.minimum_balance(0)) >= prize_amount,
+        RaffleError::PromoBudgetExhausted
+    );
+
+    create_round_accounts(
+        ctx.program_id,
+        sol_raffle,
+        &mut *ctx.accounts.pending_queue.load_mut()?,
+        RoundAccounts {
+            round: &ctx.accounts.round,
+            round_tickets: &ctx.accounts.round_tickets,
+            rent_vault: &ctx.accounts.rent_vault,
+            system_program: &ctx.accounts.system_program,
+        },
+        round_id,
+        RoundBumps {
+            round: ctx.bumps.round,
+            round_tickets: ctx.bumps.round_tickets,
+            rent_vault: ctx.bumps.rent_vault,
+        },
+        current_time,
+    )?;
+
+    let promo_vault_seeds: &[&[&[u8]]] = &[&[b"promo_vault", &[ctx.bumps.promo_vault]]];
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.promo_vault.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        prize_amount,
+        Some(promo_vault_seeds),
+    )?;
+    record_in_ledger_account(&ctx.accounts.vault_ledger, prize_amount)?;
+
+    let end_time = {
+        let mut round_data = ctx.accounts.round.try_borrow_mut_data()?;
+        let mut round = Round::try_deserialize(&mut &round_data[..])?;
+
+        round.free_entry = true;
+        round.free_entry_max_tickets = max_tickets_per_wallet;
+        add_capped_prize(&mut round, prize_amount)?;
+        round.sponsored_amount = prize_amount;
+
+        let mut writer = &mut round_data[..];
+        round.try_serialize(&mut writer)?;
+        round.end_time
+    };
+
+    msg!("✅ Free-entry round {} opened with {} lamports prize", round_id, prize_amount);
+
+    emit!(FreeEntryRoundOpened {
+        token: sol_raffle.token_mint,
+        round_id,
+        prize_amount,
+        max_tickets_per_wallet,
+        start_time: current_time,
+        end_time,
+    });
+
+    Ok(())
+}
+
+pub fn enter_free_round(
+    ctx: Context<EnterFreeRound>,
+    round_id: u32,
+    purchase_index: u32,
+    count: u32,
+    access_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(count > 0, RaffleError::InvalidTicketCount);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(
+        !ctx.accounts.raffle_state.is_in_maintenance(current_time),
+        RaffleError::MaintenanceMode
+    );
+    require!(!ctx.accounts.sol_raffle.sales_paused, RaffleError::SalesPaused);
+
+    let round = &mut ctx.accounts.round;
+
+    require!(round.free_entry, RaffleError::NotFreeEntryRound);
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require_entry_allowed(
+        &ctx.accounts.raffle_state,
+        &ctx.accounts.sol_raffle,
+        round,
+        ctx.accounts.blocklist.as_ref(),
+        ctx.accounts.attestation.as_ref(),
+        &ctx.accounts.player.key(),
+        &access_proof,
+    )?;
+    require!(current_time < round.end_time, RaffleError::RoundNotAvailable);
+    require_eq!(
+        purchase_index,
+        round.purchases_count,
+        RaffleError::PurchaseIndexMismatch
+    );
+
+    let free_entry_claim = &mut ctx.accounts.free_entry_claim;
+    let claimed = free_entry_claim
+        .tickets_count
+        .checked_add(count)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(
+        claimed <= round.free_entry_max_tickets,
+        RaffleError::FreeEntryLimitReached
+    );
+
+    free_entry_claim.round = round.key();
+    free_entry_claim.player = ctx.accounts.player.key();
+    free_entry_claim.tickets_count = claimed;
+    free_entry_claim.bump = ctx.bumps.free_entry_claim;
+
+    record_wallet_purchase(
+        ctx.accounts.wallet_purchases.as_mut(),
+        round.key(),
+        ctx.accounts.player.key(),
+        ctx.bumps.wallet_purchases,
+        ctx.accounts.sol_raffle.max_purchases_per_wallet,
+    )?;
+
+    let new_total = round
+        .total_tickets
+        .checked_add(count)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    append_cumulative_tickets(&ctx.accounts.round_tickets, new_total)?;
+
+    initialize_round_tickets_purchase(
+        &mut ctx.accounts.round_tickets_purchase,
+        round.key(),
+        round.purchases_count,
+        ctx.accounts.player.key(),
+        count,
+        ctx.bumps.round_tickets_purchase,
+    )?;
+
+    // Free, no ticket volume
+    record_raffle_purchase(
+        &mut ctx.accounts.raffle_stats,
+        ctx.accounts.sol_raffle.key(),
+        ctx.bumps.raffle_stats,
+        round.purchases_count == 0,
+        count,
+        0,
+    )?;
+    record_purchase(&ctx.accounts.records, round, ctx.accounts.player.key(), 0, 0, current_time)?;
+    record_daily_purchase(
+        &mut ctx.accounts.daily_stats,
+        ctx.accounts.sol_raffle.key(),
+        ctx.bumps.daily_stats,
+        None,
+        count,
+        0,
+        current_time,
+    )?;
+
+    round.total_tickets = new_total;
+    round.purchases_count += 1;
+
+    emit!(FreeEntryClaimed {
+        token: ctx.accounts.sol_raffle.token_mint,
+        round_id,
+        player: ctx.accounts.player.key(),
+        purchase_index,
+        count,
+        wallet_tickets: claimed,
+        timestamp: current_time,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct FundPromoVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// PDA for storing the promo budget
+    #[account(
+        mut,
+        seeds = [b"promo_vault"],
+        bump
+    )]
+    pub promo_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPromoVault<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"promo_vault"],
+        bump
+    )]
+    pub promo_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32, purchase_index: u32, player: Pubkey)]
+pub struct GrantPromoTickets<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: zero-copy tickets of the round, mutated in place
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + RoundTicketsPurchase::INIT_SPACE,
+        seeds = [
+            b"round_tickets_purchase",
+            round.key().as_ref(),
+            &purchase_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+
+    // Required while sol_raffle.max_purchases_per_wallet is set
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + WalletPurchases::INIT_SPACE,
+        seeds = [
+            b"wallet_purchases",
+            round.key().as_ref(),
+            player.as_ref()
+        ],
+        bump
+    )]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+
+    // Lifetime totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: Records PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = operator,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    // Authority or operator, checked in the handler
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"promo_vault"],
+        bump
+    )]
+    pub promo_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    /// CHECK: price feed of the first ticket peg component, basket feeds go in remaining_accounts
+    pub reference_price_feed: AccountInfo<'info>,
+
+    /// CHECK: SOL price feed account
+    pub sol_price_feed: AccountInfo<'info>,
+
+    // Fallbacks for stale feeds, the fallback feeds go in remaining_accounts after the basket feeds
+    #[account(
+        seeds = [b"feed_failover"],
+        bump = feed_failover.bump
+    )]
+    pub feed_failover: Option<Account<'info, FeedFailover>>,
+
+    // Crank-refreshed feed prices, read instead of the feeds while fresh
+    #[account(
+        seeds = [b"price_cache"],
+        bump = price_cache.bump
+    )]
+    pub price_cache: Option<Account<'info, PriceCache>>,
+
+    // Per-feed read limits, the defaults without it
+    #[account(
+        seeds = [b"feed_registry"],
+        bump = feed_registry.bump
+    )]
+    pub feed_registry: Option<Account<'info, FeedRegistry>>,
+
+    // Required once the cluster profile is set up, pins the SOL feed and the oracle program
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    /// CHECK: SAS attestation of the player, required if sol_raffle.attestation_required. Validated in the handler
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct OpenFreeEntryRound<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump = pending_queue.load()?.bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    /// CHECK: Must not exist yet - created via CPI
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump
+    )]
+    pub round: UncheckedAccount<'info>,
+
+    /// CHECK: Must not exist yet - created via CPI
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rent_vault"],
+        bump
+    )]
+    pub rent_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"promo_vault"],
+        bump
+    )]
+    pub promo_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    // Authority or operator, checked in the handler
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32, purchase_index: u32)]
+pub struct EnterFreeRound<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: zero-copy tickets of the round, mutated in place
+    #[account(
+        mut,
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + RoundTicketsPurchase::INIT_SPACE,
+        seeds = [
+            b"round_tickets_purchase",
+            round.key().as_ref(),
+            &purchase_index.to_le_bytes()
+        ],
+        bump
+    )]
+    pub round_tickets_purchase: Account<'info, RoundTicketsPurchase>,
+
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + FreeEntryClaim::INIT_SPACE,
+        seeds = [
+            b"free_entry",
+            round.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub free_entry_claim: Account<'info, FreeEntryClaim>,
+
+    // Required while sol_raffle.max_purchases_per_wallet is set
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + WalletPurchases::INIT_SPACE,
+        seeds = [
+            b"wallet_purchases",
+            round.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump
+    )]
+    pub wallet_purchases: Option<Account<'info, WalletPurchases>>,
+
+    // Lifetime totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: Records PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    /// CHECK: SAS attestation of the player, required if sol_raffle.attestation_required. Validated in the handler
+    pub attestation: Option<UncheckedAccount<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace)]
+pub struct FreeEntryClaim {
+    pub round: Pubkey, //connection with Round struct
+    pub player: Pubkey,
+    pub tickets_count: u32,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct OperatorChanged {
+    pub old_operator: Pubkey,
+    pub new_operator: Pubkey,
+}
+
+#[event]
+pub struct PromoTicketsGranted {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub player: Pubkey,
+    pub operator: Pubkey,
+    pub purchase_index: u32,
+    pub count: u32,
+    pub promo_value: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FreeEntryRoundOpened {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub prize_amount: u64,
+    pub max_tickets_per_wallet: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct FreeEntryClaimed {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub player: Pubkey,
+    pub purchase_index: u32,
+    pub count: u32,
+    pub wallet_tickets: u32,
+    pub timestamp: i64,
+}
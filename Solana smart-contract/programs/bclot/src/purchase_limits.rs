@@ -0,0 +1,184 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RaffleError,
+    TokenRaffle,
+    UpdateTokenRaffle,
+};
+use crate::audit::{record_audit, AuditAction};
+
+// Only authority. Caps the RoundTicketsPurchase accounts one wallet may get per round,
+// bought, rolled, redeemed, granted or free, independent of the ticket count. 0 - unlimited, WalletPurchases
+// accounts are only created while it is set
+pub fn set_max_purchases_per_wallet(ctx: Context<UpdateTokenRaffle>, max_purchases: u32) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.max_purchases_per_wallet = max_purchases;
+
+    emit!(MaxPurchasesPerWalletUpdated {
+        token_raffle: sol_raffle.key(),
+        max_purchases,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::MaxPurchasesPerWalletChanged,
+        max_purchases as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Keeps dust purchases out of the cumulative ticket index, 0 - no minimum
+pub fn set_min_purchase(
+    ctx: Context<UpdateTokenRaffle>,
+    min_tickets: u32,
+    min_lamports: u64,
+) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.min_tickets_per_purchase = min_tickets;
+    sol_raffle.min_purchase_lamports = min_lamports;
+
+    emit!(MinPurchaseUpdated {
+        token_raffle: sol_raffle.key(),
+        min_tickets,
+        min_lamports,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::MinPurchaseChanged,
+        min_lamports,
+    )?;
+
+    Ok(())
+}
+
+// Checked on every paid purchase, bought, rolled or redeemed. Promo grants and free-entry
+// claims cost the player nothing and have caps of their own
+pub fn check_min_purchase(sol_raffle: &TokenRaffle, count: u32, cost: u64) -> Result<()> {
+    require!(
+        count >= sol_raffle.min_tickets_per_purchase && cost >= sol_raffle.min_purchase_lamports,
+        RaffleError::PurchaseTooSmall
+    );
+
+    Ok(())
+}
+
+// Called by every purchase. The count is kept only while the cap is set, so a cap enabled
+// mid-round counts the wallet's purchases from then on
+pub fn record_wallet_purchase(
+    wallet_purchases: Option<&mut Account<WalletPurchases>>,
+    round: Pubkey,
+    player: Pubkey,
+    bump: Option<u8>,
+    max_purchases: u32,
+) -> Result<()> {
+    if max_purchases == 0 {
+        return Ok(());
+    }
+    let (Some(wallet_purchases), Some(bump)) = (wallet_purchases, bump) else {
+        return err!(RaffleError::WalletPurchasesRequired);
+    };
+
+    wallet_purchases.round = round;
+    wallet_purchases.player = player;
+    wallet_purchases.bump = bump;
+
+    let purchases_count = wallet_purchases
+        .purchases_count
+        .checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(
+        purchases_count <= max_purchases,
+        RaffleError::WalletPurchaseLimitReached
+    );
+    wallet_purchases.purchases_count = purchases_count;
+
+    Ok(())
+}
+
+// Called by cancel_purchase, a cancelled purchase stops counting against the cap
+pub fn release_wallet_purchase(wallet_purchases: Option<&mut Account<WalletPurchases>>) {
+    if let Some(wallet_purchases) = wallet_purchases {
+        wallet_purchases.purchases_count = wallet_purchases.purchases_count.saturating_sub(1);
+    }
+}
+
+// Permissionless, returns the rent to the wallet once the round no longer accepts purchases
+pub fn close_wallet_purchases(ctx: Context<CloseWalletPurchases>, round_id: u32) -> Result<()> {
+    let round = &ctx.accounts.round;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        round.status != RoundStatus::Open || now >= round.end_time,
+        RaffleError::RoundNotEndedYet
+    );
+
+    msg!("✅ Purchase count of {} in round {} closed", ctx.accounts.player.key(), round_id);
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct CloseWalletPurchases<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = player,
+        seeds = [
+            b"wallet_purchases",
+            round.key().as_ref(),
+            player.key().as_ref()
+        ],
+        bump = wallet_purchases.bump,
+        has_one = player
+    )]
+    pub wallet_purchases: Account<'info, WalletPurchases>,
+
+    #[account(mut)]
+    pub player: SystemAccount<'info>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace)]
+pub struct WalletPurchases {
+    pub round: Pubkey, //connection with Round struct
+    pub player: Pubkey,
+    pub purchases_count: u32,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct MaxPurchasesPerWalletUpdated {
+    pub token_raffle: Pubkey,
+    pub max_purchases: u32,
+}
+
+#[event]
+pub struct MinPurchaseUpdated {
+    pub token_raffle: Pubkey,
+    pub min_tickets: u32,
+    pub min_lamports: u64,
+}
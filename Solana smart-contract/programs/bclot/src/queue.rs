@@ -0,0 +1,158 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use std::cell::RefMut;
+
+use crate::{
+    RaffleError,
+    RoundStatus,
+    TokenRaffle,
+};
+
+pub const PENDING_QUEUE_CAPACITY: usize = 256;
+
+// Rounds waiting for a VRF draw, one queue per TokenRaffle
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct PendingQueue {
+    pub token_raffle: Pubkey, //connection with TokenRaffle struct
+    pub rounds: [u32; PENDING_QUEUE_CAPACITY], // ring buffer
+    pub head: u16,
+    pub len: u16,
+    pub bump: u8,
+    pub padding: [u8; 3],
+}
+
+impl PendingQueue {
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn front(&self) -> Option<u32> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.rounds[self.head as usize])
+    }
+
+    pub fn contains(&self, round_id: u32) -> bool {
+        (0..self.len as usize)
+            .any(|i| self.rounds[(self.head as usize + i) % PENDING_QUEUE_CAPACITY] == round_id)
+    }
+
+    pub fn push(&mut self, round_id: u32) -> Result<()> {
+        require!(
+            (self.len as usize) < PENDING_QUEUE_CAPACITY,
+            RaffleError::PendingQueueFull
+        );
+
+        let tail = (self.head as usize + self.len as usize) % PENDING_QUEUE_CAPACITY;
+        self.rounds[tail] = round_id;
+        self.len += 1;
+
+        Ok(())
+    }
+
+    // Drops a round wherever it is, keeping the order of the others. Settled rounds are removed
+    // this way too, a retry or a cancellation can settle a round that is not at the front
+    pub fn remove(&mut self, round_id: u32) -> bool {
+        let len = self.len as usize;
+        let Some(pos) = (0..len)
+            .position(|i| self.rounds[(self.head as usize + i) % PENDING_QUEUE_CAPACITY] == round_id)
+        else {
+            return false;
+        };
+
+        for i in pos..len - 1 {
+            self.rounds[(self.head as usize + i) % PENDING_QUEUE_CAPACITY] =
+                self.rounds[(self.head as usize + i + 1) % PENDING_QUEUE_CAPACITY];
+        }
+        self.len -= 1;
+
+        true
+    }
+}
+
+// Permissionless, the payer funds the account. Creates the queue of a raffle initialized before
+// PendingQueue existed and moves over the rounds still waiting in TokenRaffle.pending_rounds
+pub fn initialize_pending_queue(ctx: Context<InitializePendingQueue>) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+
+    let mut pending_queue = ctx.accounts.pending_queue.load_init()?;
+    pending_queue.token_raffle = sol_raffle.key();
+    pending_queue.bump = ctx.bumps.pending_queue;
+
+    for round_id in std::mem::take(&mut sol_raffle.pending_rounds) {
+        pending_queue.push(round_id)?;
+    }
+
+    msg!("✅ Pending queue initialized with {} rounds", pending_queue.len);
+
+    Ok(())
+}
+
+// Queues the current round once it has ended, returns the queued round
+pub fn enqueue_expired_round(
+    sol_raffle: &TokenRaffle,
+    pending_queue: &mut PendingQueue,
+    now: i64,
+) -> Result<Option<u32>> {
+    let (Some(round_id), Some(end_time)) = (sol_raffle.current_round_id, sol_raffle.current_round_end_time) else {
+        return Ok(None);
+    };
+
+    if now < end_time
+        || !matches!(sol_raffle.current_round_status, RoundStatus::Open | RoundStatus::Locked)
+        || pending_queue.contains(round_id)
+    {
+        return Ok(None);
+    }
+
+    pending_queue.push(round_id)?;
+    msg!("✅ Added round {} to pending", round_id);
+
+    Ok(Some(round_id))
+}
+
+// Borrows a PendingQueue from raw account data, used where only an AccountInfo is available (VRF callback)
+pub fn load_pending_queue_mut<'a>(info: &'a AccountInfo) -> Result<RefMut<'a, PendingQueue>> {
+    require!(info.owner == &crate::id(), RaffleError::PendingQueueNotInitialized);
+
+    let data = info.try_borrow_mut_data()?;
+    require!(
+        data.len() >= 8 + std::mem::size_of::<PendingQueue>(),
+        RaffleError::InvalidAccountSize
+    );
+    require!(
+        data[..8] == *PendingQueue::DISCRIMINATOR,
+        RaffleError::PendingQueueNotInitialized
+    );
+
+    Ok(RefMut::map(data, |data| {
+        bytemuck::from_bytes_mut(&mut data[8..8 + std::mem::size_of::<PendingQueue>()])
+    }))
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializePendingQueue<'info> {
+    #[account(
+        mut,
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<PendingQueue>(),
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
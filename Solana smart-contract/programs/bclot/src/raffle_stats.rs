@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    TokenRaffle,
+};
+use crate::daily_stats::{record_daily_prize_paid, DailyStats};
+use crate::winner_history::record_winner;
+
+pub fn get_raffle_stats(ctx: Context<GetRaffleStats>) -> Result<RaffleStats> {
+    Ok((*ctx.accounts.raffle_stats).clone())
+}
+
+// Called by every purchase. `first_purchase` counts the round towards lifetime_rounds,
+// so rounds that sold nothing are left out
+pub fn record_raffle_purchase(
+    raffle_stats: &mut Account<RaffleStats>,
+    token_raffle: Pubkey,
+    bump: u8,
+    first_purchase: bool,
+    tickets: u32,
+    volume: u64,
+) -> Result<()> {
+    raffle_stats.token_raffle = token_raffle;
+    raffle_stats.bump = bump;
+
+    if first_purchase {
+        raffle_stats.lifetime_rounds = raffle_stats
+            .lifetime_rounds
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    raffle_stats.lifetime_tickets = raffle_stats
+        .lifetime_tickets
+        .checked_add(tickets as u64)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    raffle_stats.total_volume = raffle_stats
+        .total_volume
+        .checked_add(volume)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(())
+}
+
+// Accounts a prize payout records into, the stats accounts are created by the payer when missing
+pub struct PayoutStats<'a, 'info> {
+    pub raffle_stats: &'a mut Account<'info, RaffleStats>,
+    pub raffle_stats_bump: u8,
+    pub daily_stats: &'a mut Account<'info, DailyStats>,
+    pub daily_stats_bump: u8,
+    pub winner_history: &'a AccountInfo<'info>,
+}
+
+// Called by every payout path. `amount` is what this payout released, a vested prize pays in
+// installments and counts once, on the first one, which also adds the winner to WinnerHistory
+pub fn record_payout(stats: PayoutStats, round: &Round, amount: u64, first_payment: bool) -> Result<()> {
+    let raffle_stats = stats.raffle_stats;
+    raffle_stats.token_raffle = round.token_raffle;
+    raffle_stats.bump = stats.raffle_stats_bump;
+
+    raffle_stats.total_prizes_paid = raffle_stats
+        .total_prizes_paid
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if first_payment {
+        raffle_stats.prizes_paid_count = raffle_stats
+            .prizes_paid_count
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        record_winner(stats.winner_history, round)?;
+    }
+    record_daily_prize_paid(
+        stats.daily_stats,
+        round.token_raffle,
+        stats.daily_stats_bump,
+        amount,
+        first_payment,
+    )?;
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct GetRaffleStats<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump = raffle_stats.bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+}
+
+// Account data structs
+// Lifetime totals of one TokenRaffle, created by its first purchase or payout
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct RaffleStats {
+    pub token_raffle: Pubkey, //connection with TokenRaffle struct
+    pub lifetime_rounds: u32, // rounds with at least one purchase
+    pub lifetime_tickets: u64, // bonus tickets included
+    pub total_volume: u64, // lamports paid for tickets
+    pub total_prizes_paid: u64,
+    pub prizes_paid_count: u32,
+    pub bump: u8,
+}
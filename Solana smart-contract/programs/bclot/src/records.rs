@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+    Round,
+};
+
+// Only authority
+pub fn initialize_records(ctx: Context<InitializeRecords>) -> Result<()> {
+    let records = &mut ctx.accounts.records;
+    records.biggest_prize = RecordEntry::default();
+    records.biggest_purchase = RecordEntry::default();
+    records.longest_streak = RecordEntry::default();
+    records.bump = ctx.bumps.records;
+
+    msg!("✅ Records initialized");
+
+    Ok(())
+}
+
+pub fn get_records(ctx: Context<GetRecords>) -> Result<Records> {
+    Ok((*ctx.accounts.records).clone())
+}
+
+impl RecordEntry {
+    // Ties keep the earlier holder
+    fn beat(
+        &mut self,
+        kind: RecordKind,
+        holder: Pubkey,
+        token_raffle: Pubkey,
+        round_id: u32,
+        value: u64,
+        timestamp: i64,
+    ) {
+        if value <= self.value {
+            return;
+        }
+
+        *self = RecordEntry {
+            holder,
+            token_raffle,
+            round_id,
+            value,
+            timestamp,
+        };
+
+        emit!(RecordBroken {
+            kind,
+            holder,
+            token_raffle,
+            round_id,
+            value,
+            timestamp,
+        });
+    }
+}
+
+fn load_records(records_info: &AccountInfo) -> Result<Option<Records>> {
+    if records_info.data_is_empty() || records_info.owner != &crate::id() {
+        return Ok(None);
+    }
+
+    Ok(Some(Records::try_deserialize(&mut &records_info.try_borrow_data()?[..])?))
+}
+
+// Every purchase path passes the records, they are skipped until initialized. `cost` is the
+// value of the purchase's tickets, `streak` the buyer's current_streak after it, 0 on paths that
+// don't advance the streak
+pub fn record_purchase(
+    records_info: &AccountInfo,
+    round: &Round,
+    buyer: Pubkey,
+    cost: u64,
+    streak: u32,
+    timestamp: i64,
+) -> Result<()> {
+    let Some(mut records) = load_records(records_info)? else {
+        return Ok(());
+    };
+
+    records.biggest_purchase.beat(
+        RecordKind::BiggestPurchase,
+        buyer,
+        round.token_raffle,
+        round.round_id,
+        cost,
+        timestamp,
+    );
+    records.longest_streak.beat(
+        RecordKind::LongestStreak,
+        buyer,
+        round.token_raffle,
+        round.round_id,
+        streak as u64,
+        timestamp,
+    );
+    records.try_serialize(&mut &mut records_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+// Called by set_winner_address once the draw's winner is known, skipped until the records are
+// initialized
+pub fn record_prize(records_info: &AccountInfo, round: &Round) -> Result<()> {
+    let Some(winner) = round.winner_address else {
+        return Ok(());
+    };
+    let Some(mut records) = load_records(records_info)? else {
+        return Ok(());
+    };
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    records.biggest_prize.beat(
+        RecordKind::BiggestPrize,
+        winner,
+        round.token_raffle,
+        round.round_id,
+        round.prize_amount,
+        timestamp,
+    );
+    records.try_serialize(&mut &mut records_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeRecords<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Records::INIT_SPACE,
+        seeds = [b"records"],
+        bump
+    )]
+    pub records: Account<'info, Records>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetRecords<'info> {
+    #[account(
+        seeds = [b"records"],
+        bump = records.bump
+    )]
+    pub records: Account<'info, Records>,
+}
+
+// Account data structs
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace, Debug)]
+pub enum RecordKind {
+    BiggestPrize,
+    BiggestPurchase,
+    LongestStreak,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, InitSpace, Debug)]
+pub struct RecordEntry {
+    pub holder: Pubkey, // winner or buyer, default - not set yet
+    pub token_raffle: Pubkey,
+    pub round_id: u32,
+    pub value: u64, // lamports, rounds for the streak
+    pub timestamp: i64,
+}
+
+// All-time records across every raffle, for the stats page
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct Records {
+    pub biggest_prize: RecordEntry,
+    pub biggest_purchase: RecordEntry, // tickets value of one purchase, prize rolls, promo grants and redemptions included
+    pub longest_streak: RecordEntry, // consecutive rounds entered in one raffle
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct RecordBroken {
+    pub kind: RecordKind,
+    pub holder: Pubkey,
+    pub token_raffle: Pubkey,
+    pub round_id: u32,
+    pub value: u64,
+    pub timestamp: i64,
+}
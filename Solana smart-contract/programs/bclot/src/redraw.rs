@@ -0,0 +1,356 @@
+use anchor_lang::prelude::*;
+use orao_solana_vrf_cb::{
+    program::OraoVrfCb,
+    state::{client::Client, network_state::NetworkState},
+    CB_CLIENT_ACCOUNT_SEED, CB_CONFIG_ACCOUNT_SEED,
+};
+use crate::{
+    ClientState,
+    PendingQueue,
+    Round,
+    RoundStatus,
+    RoundTickets,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateTokenRaffle,
+    VrfRequestAccounts,
+    CLIENT_STATE_SEED,
+    submit_vrf_request,
+};
+use crate::audit::{record_audit, AuditAction};
+use crate::cluster_profile::ClusterProfile;
+use crate::house_ticket::is_house_ticket;
+use crate::keeper::{require_keeper, KeeperRegistry};
+use crate::migration::Versioned;
+use crate::vrf::record_randomness_request;
+
+// Only authority. A prize still unclaimed `redraw_after_seconds` after the draw may be drawn
+// again once, without the first winner's tickets. 0 - disabled
+pub fn set_redraw_after(ctx: Context<UpdateTokenRaffle>, redraw_after_seconds: i64) -> Result<()> {
+    require!(redraw_after_seconds >= 0, RaffleError::InvalidAmount);
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.redraw_after_seconds = redraw_after_seconds;
+
+    emit!(RedrawConfigUpdated {
+        cadence_id: sol_raffle.cadence_id,
+        redraw_after_seconds,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::RedrawConfigChanged,
+        redraw_after_seconds as u64,
+    )?;
+
+    Ok(())
+}
+
+// Permissionless unless the keeper allowlist is on. The first draw is kept in the round's
+// first_winner_* fields, the callback picks again and the authority sets the new winner address.
+// A redraw whose callback never lands is requested again here, under the same cooldown and
+// retry limit as the first draw's requests
+pub fn redraw<'info>(
+    ctx: Context<'_, '_, '_, 'info, Redraw<'info>>,
+    round_id: u32,
+    seeds: Vec<[u8; 32]>,
+) -> Result<()> {
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        seeds.len(),
+        RaffleError::SeedMismatch
+    );
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    require_keeper(
+        raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+
+    let sol_raffle = &ctx.accounts.sol_raffle;
+    let round = &mut ctx.accounts.round;
+    round.check_version()?;
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(sol_raffle.redraw_after_seconds > 0, RaffleError::RedrawDisabled);
+    require!(
+        round.status == RoundStatus::Completed,
+        RaffleError::RoundNotCompleted
+    );
+    require!(!round.prize_claimed, RaffleError::PrizeAlreadyClaimed);
+    require!(!round.frozen, RaffleError::RoundFrozen);
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+
+    if round.redraw_pending {
+        msg!("🔁 Requesting the redraw of round {} again", round_id);
+    } else {
+        require!(round.first_winner_purchase_index.is_none(), RaffleError::AlreadyRedrawn);
+        require!(
+            now >= round.completed_at.saturating_add(sol_raffle.redraw_after_seconds),
+            RaffleError::RedrawTooEarly
+        );
+
+        let winner_ticket_index = round.winner_ticket_index.ok_or(RaffleError::RoundNotCompleted)?;
+        require!(round.winner_purchase_index.is_some(), RaffleError::WinnerNotFinalized);
+        // A house win has nothing left to claim
+        require!(
+            !is_house_ticket(round, winner_ticket_index),
+            RaffleError::PrizeAlreadyClaimed
+        );
+
+        round.first_winner_purchase_index = round.winner_purchase_index;
+        round.first_winner_ticket_index = round.winner_ticket_index;
+        round.first_winner_address = round.winner_address;
+
+        // Redrawing without other tickets would pick the same purchase, or nothing at all
+        {
+            let round_tickets = ctx.accounts.round_tickets.load()?;
+            round_tickets.check_version()?;
+            require!(
+                drawable_tickets(&round_tickets, round)? > 0,
+                RaffleError::NothingToRedraw
+            );
+        }
+        round.winner_purchase_index = None;
+        round.winner_ticket_index = None;
+        round.winner_address = None;
+        round.redraw_pending = true;
+        // The redraw's requests get a retry budget of their own
+        round.request_count = 0;
+
+        msg!("🔁 Redrawing round {}", round_id);
+
+        emit!(RoundRedrawRequested {
+            round_id,
+            first_winner_purchase_index: round.first_winner_purchase_index,
+            first_winner_address: round.first_winner_address,
+            timestamp: now,
+        });
+    }
+    record_randomness_request(raffle_state, round, now)?;
+
+    let pending_queue_bump = ctx.accounts.pending_queue.load()?.bump;
+
+    submit_vrf_request(
+        raffle_state,
+        ctx.accounts.cluster_profile.as_deref(),
+        &ctx.accounts.sol_raffle,
+        round_id,
+        VrfRequestAccounts {
+            pending_queue: ctx.accounts.pending_queue.to_account_info(),
+            pending_queue_bump,
+            vrf: ctx.accounts.vrf.to_account_info(),
+            vrf_fee_vault: ctx.accounts.vrf_fee_vault.to_account_info(),
+            vrf_fee_vault_bump: ctx.bumps.vrf_fee_vault,
+            client_state: ctx.accounts.client_state.to_account_info(),
+            client_state_bump: ctx.accounts.client_state.bump,
+            client: ctx.accounts.client.to_account_info(),
+            network_state: ctx.accounts.network_state.to_account_info(),
+            treasury: ctx.accounts.treasury.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        },
+        ctx.remaining_accounts,
+        &seeds,
+    )
+}
+
+// Maps a ticket drawn from the pool without the first winner's range back to a real index
+pub fn skip_excluded_range(round_tickets: &RoundTickets, round: &Round, drawn_index: u32) -> u32 {
+    match excluded_range(round_tickets, round) {
+        Some((start, end)) if drawn_index >= start => drawn_index + (end - start),
+        _ => drawn_index,
+    }
+}
+
+// Tickets a draw picks from, a redraw leaves the first winner's out
+pub fn drawable_tickets(round_tickets: &RoundTickets, round: &Round) -> Result<u32> {
+    let tickets = round
+        .total_tickets
+        .checked_add(round.house_tickets)
+        .and_then(|tickets| tickets.checked_sub(excluded_tickets(round_tickets, round)))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(tickets)
+}
+
+// Tickets the redraw leaves out of the pool
+fn excluded_tickets(round_tickets: &RoundTickets, round: &Round) -> u32 {
+    excluded_range(round_tickets, round)
+        .map(|(start, end)| end - start)
+        .unwrap_or(0)
+}
+
+fn excluded_range(round_tickets: &RoundTickets, round: &Round) -> Option<(u32, u32)> {
+    let index = round.first_winner_purchase_index? as usize;
+    if index >= round_tickets.len as usize {
+        return None;
+    }
+    let start = if index == 0 { 0 } else { round_tickets.get_ticket_at(index - 1) };
+
+    Some((start, round_tickets.get_ticket_at(index)))
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct Redraw<'info> {
+    #[account(
+        mut,
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    // Only its key and bump are used, the callback expects it
+    #[account(
+        seeds = [b"pending_queue", sol_raffle.key().as_ref()],
+        bump = pending_queue.load()?.bump
+    )]
+    pub pending_queue: AccountLoader<'info, PendingQueue>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump = round_tickets.load()?.bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+
+    pub keeper: Signer<'info>,
+
+    // Required while the keeper allowlist is enabled
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
+    // Randomness request accounts, see RequestRandomness
+    #[account(
+        mut,
+        seeds = [b"vrf_fee_vault"],
+        bump
+    )]
+    pub vrf_fee_vault: SystemAccount<'info>,
+
+    pub vrf: Program<'info, OraoVrfCb>,
+
+    #[account(
+        mut,
+        seeds = [CLIENT_STATE_SEED],
+        bump = client_state.bump
+    )]
+    pub client_state: Account<'info, ClientState>,
+
+    #[account(
+        mut,
+        seeds = [CB_CLIENT_ACCOUNT_SEED, crate::id().as_ref(), client_state.key().as_ref()],
+        seeds::program = orao_solana_vrf_cb::id(),
+        bump = client.bump,
+    )]
+    pub client: Account<'info, Client>,
+
+    #[account(
+        mut,
+        seeds = [CB_CONFIG_ACCOUNT_SEED],
+        seeds::program = orao_solana_vrf_cb::id(),
+        bump = network_state.bump,
+    )]
+    pub network_state: Account<'info, NetworkState>,
+
+    /// CHECK: Treasury ORAO VRF
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    // Required once the cluster profile is set up
+    #[account(
+        seeds = [b"cluster_profile"],
+        bump = cluster_profile.bump
+    )]
+    pub cluster_profile: Option<Account<'info, ClusterProfile>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Events
+#[event]
+pub struct RedrawConfigUpdated {
+    pub cadence_id: u8,
+    pub redraw_after_seconds: i64,
+}
+
+#[event]
+pub struct RoundRedrawRequested {
+    pub round_id: u32,
+    pub first_winner_purchase_index: Option<u32>,
+    pub first_winner_address: Option<Pubkey>,
+    pub timestamp: i64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytemuck::Zeroable;
+
+    fn round(total_tickets: u32, house_tickets: u32, first_winner_purchase_index: Option<u32>) -> Round {
+        let mut round = Round::try_deserialize_unchecked(&mut &vec![0u8; 8 + Round::INIT_SPACE][..]).unwrap();
+        round.total_tickets = total_tickets;
+        round.house_tickets = house_tickets;
+        round.first_winner_purchase_index = first_winner_purchase_index;
+        round
+    }
+
+    fn round_tickets(cumulative: &[u32]) -> RoundTickets {
+        let mut round_tickets = RoundTickets::zeroed();
+        for tickets in cumulative {
+            round_tickets.add_cumulative(*tickets).unwrap();
+        }
+        round_tickets
+    }
+
+    #[test]
+    fn draw_counts_house_tickets() {
+        let round_tickets = round_tickets(&[3, 7, 10]);
+
+        assert_eq!(drawable_tickets(&round_tickets, &round(10, 2, None)).unwrap(), 12);
+    }
+
+    #[test]
+    fn redraw_leaves_first_winner_out() {
+        let round_tickets = round_tickets(&[3, 7, 10]);
+        let round = round(10, 0, Some(1));
+
+        assert_eq!(drawable_tickets(&round_tickets, &round).unwrap(), 6);
+        assert_eq!(skip_excluded_range(&round_tickets, &round, 2), 2);
+        assert_eq!(skip_excluded_range(&round_tickets, &round, 3), 7);
+    }
+
+    #[test]
+    fn redraw_of_single_purchase_has_nothing_to_draw() {
+        let round_tickets = round_tickets(&[5]);
+
+        assert_eq!(drawable_tickets(&round_tickets, &round(5, 0, Some(0))).unwrap(), 0);
+        assert_eq!(drawable_tickets(&round_tickets, &round(5, 1, Some(0))).unwrap(), 1);
+    }
+}
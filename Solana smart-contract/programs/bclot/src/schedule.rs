@@ -0,0 +1,234 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateRaffleState,
+    UpdateTokenRaffle,
+    NY_OFFSET,
+    SECONDS_IN_DAY,
+};
+use crate::audit::{record_audit, AuditAction};
+use crate::jackpot::MAX_JACKPOT_BPS;
+
+const DAYS_IN_WEEK: i64 = 7;
+const EPOCH_WEEKDAY: i64 = 4; // 1970-01-01 was a Thursday, 0 - Sunday
+
+// Only authority. New rounds and purchases fail inside [start, end), pass 0, 0 to cancel
+pub fn schedule_maintenance(ctx: Context<UpdateRaffleState>, start: i64, end: i64) -> Result<()> {
+    require!(
+        (start == 0 && end == 0) || (start >= 0 && end > start),
+        RaffleError::InvalidMaintenanceWindow
+    );
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    raffle_state.maintenance_start = start;
+    raffle_state.maintenance_end = end;
+
+    emit!(MaintenanceScheduled { start, end });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::MaintenanceScheduled,
+        start as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. `weekday` is 0 (Sunday) .. 6 (Saturday) in NY time, None disables mega rounds
+pub fn set_mega_round_config(
+    ctx: Context<UpdateTokenRaffle>,
+    weekday: Option<u8>,
+    fee_percentage: u8,
+    duration: i64,
+    extra_jackpot_bps: u16,
+) -> Result<()> {
+    if let Some(weekday) = weekday {
+        require!((weekday as i64) < DAYS_IN_WEEK, RaffleError::InvalidMegaRoundConfig);
+        require!(fee_percentage <= 100, RaffleError::InvalidMegaRoundConfig);
+        require!(
+            is_valid_round_duration(duration),
+            RaffleError::InvalidRoundDuration
+        );
+        require!(extra_jackpot_bps <= MAX_JACKPOT_BPS, RaffleError::InvalidBps);
+    }
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.mega_round_weekday = weekday;
+    sol_raffle.mega_round_fee_percentage = fee_percentage;
+    sol_raffle.mega_round_duration = duration;
+    sol_raffle.mega_round_jackpot_bps = extra_jackpot_bps;
+
+    emit!(MegaRoundConfigUpdated {
+        cadence_id: sol_raffle.cadence_id,
+        weekday,
+        fee_percentage,
+        duration,
+        extra_jackpot_bps,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::MegaRoundConfigChanged,
+        fee_percentage as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Rounds opened from now on close as soon as their pot reaches the target
+// and become drawable right away, None - timed rounds only
+pub fn set_prize_target(ctx: Context<UpdateTokenRaffle>, prize_target_lamports: Option<u64>) -> Result<()> {
+    require!(prize_target_lamports != Some(0), RaffleError::InvalidAmount);
+
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.prize_target_lamports = prize_target_lamports;
+
+    emit!(PrizeTargetUpdated {
+        cadence_id: sol_raffle.cadence_id,
+        prize_target_lamports,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::PrizeTargetChanged,
+        prize_target_lamports.unwrap_or(0),
+    )?;
+
+    Ok(())
+}
+
+// Called after a purchase adds to the pot. Moving end_time to now stops further purchases
+// and lets rollover and request_randomness treat the round as ended
+pub fn close_at_prize_target(sol_raffle: &mut TokenRaffle, round: &mut Round, now: i64) {
+    let Some(prize_target) = round.prize_target_lamports else {
+        return;
+    };
+    if round.prize_amount < prize_target || round.end_time <= now {
+        return;
+    }
+
+    round.end_time = now;
+    if sol_raffle.current_round_id == Some(round.round_id) {
+        sol_raffle.current_round_end_time = Some(now);
+    }
+
+    msg!("✅ Round {} reached its prize target of {} lamports", round.round_id, prize_target);
+
+    emit!(PrizeTargetReached {
+        cadence_id: sol_raffle.cadence_id,
+        round_id: round.round_id,
+        prize_amount: round.prize_amount,
+        prize_target_lamports: prize_target,
+        timestamp: now,
+    });
+}
+
+// Rounds are aligned to NY midnight, so the duration has to split a day evenly
+pub fn get_next_ny_anchor_time(current_timestamp: i64, round_duration: i64) -> i64 {
+    let ny_time = current_timestamp - NY_OFFSET;
+    let ny_seconds_in_day = ny_time % SECONDS_IN_DAY;
+    let ny_today_anchor = ny_time - ny_seconds_in_day;
+
+    let slot = ny_seconds_in_day / round_duration + 1;
+
+    ny_today_anchor + slot * round_duration + NY_OFFSET
+}
+
+pub fn is_valid_round_duration(round_duration: i64) -> bool {
+    round_duration > 0 && SECONDS_IN_DAY % round_duration == 0
+}
+
+pub fn is_mega_round_day(sol_raffle: &TokenRaffle, current_timestamp: i64) -> bool {
+    match sol_raffle.mega_round_weekday {
+        Some(weekday) => {
+            let ny_days = (current_timestamp - NY_OFFSET).div_euclid(SECONDS_IN_DAY);
+            (ny_days + EPOCH_WEEKDAY) % DAYS_IN_WEEK == weekday as i64
+        }
+        None => false,
+    }
+}
+
+// Returns (end_time, mega_round) for a round created at `current_timestamp`
+pub fn next_round_schedule(sol_raffle: &TokenRaffle, current_timestamp: i64) -> (i64, bool) {
+    let mega_round = is_mega_round_day(sol_raffle, current_timestamp);
+    let duration = if mega_round {
+        sol_raffle.mega_round_duration
+    } else {
+        sol_raffle.round_duration
+    };
+
+    (get_next_ny_anchor_time(current_timestamp, duration), mega_round)
+}
+
+// Evaluated at purchase time against the pot before the purchase, the result is kept in the purchase
+pub fn round_fee_bps(raffle_state: &RaffleState, sol_raffle: &TokenRaffle, round: &Round) -> u16 {
+    if round.mega_round {
+        return sol_raffle.mega_round_fee_percentage as u16 * 100;
+    }
+
+    match sol_raffle
+        .fee_tiers
+        .iter()
+        .rev()
+        .find(|tier| round.prize_amount >= tier.min_pot_lamports)
+    {
+        Some(tier) => tier.fee_bps,
+        None => sol_raffle.fee_percentage.unwrap_or(raffle_state.entrance_fee_percentage) as u16 * 100,
+    }
+}
+
+pub fn round_jackpot_bps(raffle_state: &RaffleState, sol_raffle: &TokenRaffle, round: &Round) -> u16 {
+    if round.mega_round {
+        raffle_state.jackpot_bps.saturating_add(sol_raffle.mega_round_jackpot_bps)
+    } else {
+        raffle_state.jackpot_bps
+    }
+}
+
+// Events
+#[event]
+pub struct RoundCreated {
+    pub token: Pubkey,
+    pub cadence_id: u8,
+    pub round_id: u32,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub mega_round: bool,
+    pub carryover: u64, // prize carried in from earlier rounds
+}
+
+#[event]
+pub struct MaintenanceScheduled {
+    pub start: i64,
+    pub end: i64,
+}
+
+#[event]
+pub struct MegaRoundConfigUpdated {
+    pub cadence_id: u8,
+    pub weekday: Option<u8>,
+    pub fee_percentage: u8,
+    pub duration: i64,
+    pub extra_jackpot_bps: u16,
+}
+
+#[event]
+pub struct PrizeTargetUpdated {
+    pub cadence_id: u8,
+    pub prize_target_lamports: Option<u64>,
+}
+
+#[event]
+pub struct PrizeTargetReached {
+    pub cadence_id: u8,
+    pub round_id: u32,
+    pub prize_amount: u64,
+    pub prize_target_lamports: u64,
+    pub timestamp: i64,
+}
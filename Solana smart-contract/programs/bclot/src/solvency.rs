@@ -0,0 +1,169 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+};
+
+// Only authority. `outstanding` seeds the ledger with the unpaid prizes and commissions
+// of rounds that existed before it
+pub fn initialize_vault_ledger(ctx: Context<InitializeVaultLedger>, outstanding: u64) -> Result<()> {
+    let vault_ledger = &mut ctx.accounts.vault_ledger;
+    vault_ledger.outstanding = outstanding;
+    vault_ledger.bump = ctx.bumps.vault_ledger;
+
+    msg!("✅ Vault ledger initialized, {} lamports outstanding", outstanding);
+
+    Ok(())
+}
+
+impl VaultLedger {
+    // Lamports that entered sol_vault on behalf of a round's prize or commission
+    pub fn record(&mut self, amount: u64) -> Result<()> {
+        self.outstanding = self
+            .outstanding
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Saturating so a ledger seeded too low never blocks a payout
+    pub fn release(&mut self, amount: u64) {
+        self.outstanding = self.outstanding.saturating_sub(amount);
+    }
+}
+
+// The ledger is optional until initialize_vault_ledger, callers pass its PDA either way and an
+// empty account is skipped. Once it exists the seeds make it impossible to leave out
+fn load_vault_ledger(vault_ledger_info: &AccountInfo) -> Result<Option<VaultLedger>> {
+    if vault_ledger_info.data_is_empty() || vault_ledger_info.owner != &crate::id() {
+        return Ok(None);
+    }
+
+    Ok(Some(VaultLedger::try_deserialize(&mut &vault_ledger_info.try_borrow_data()?[..])?))
+}
+
+pub fn record_in_ledger_account(vault_ledger_info: &AccountInfo, amount: u64) -> Result<()> {
+    let Some(mut vault_ledger) = load_vault_ledger(vault_ledger_info)? else {
+        return Ok(());
+    };
+
+    vault_ledger.record(amount)?;
+    vault_ledger.try_serialize(&mut &mut vault_ledger_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+pub fn release_in_ledger_account(vault_ledger_info: &AccountInfo, amount: u64) -> Result<()> {
+    let Some(mut vault_ledger) = load_vault_ledger(vault_ledger_info)? else {
+        return Ok(());
+    };
+
+    vault_ledger.release(amount);
+    vault_ledger.try_serialize(&mut &mut vault_ledger_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
+
+// 0 before the ledger is initialized
+pub fn ledger_outstanding(vault_ledger_info: &AccountInfo) -> Result<u64> {
+    Ok(load_vault_ledger(vault_ledger_info)?.map_or(0, |vault_ledger| vault_ledger.outstanding))
+}
+
+// Called before any payout out of sol_vault, a shortfall stops payouts instead of
+// letting earlier winners drain what later rounds are owed. The failed transaction drops
+// events, check_solvency records the shortfall
+pub fn require_solvent(vault_ledger_info: &AccountInfo, vault: &AccountInfo, round_id: u32) -> Result<()> {
+    let outstanding = ledger_outstanding(vault_ledger_info)?;
+    let vault_balance = vault.lamports();
+    if vault_balance >= outstanding {
+        return Ok(());
+    }
+
+    msg!(
+        "🚨 Round {}: sol_vault holds {} lamports, {} outstanding",
+        round_id,
+        vault_balance,
+        outstanding
+    );
+
+    err!(RaffleError::InsolventVault)
+}
+
+// Permissionless. Emits VaultInsolvent when sol_vault holds less than the ledger owes, for
+// monitoring. Succeeds either way so the event is kept
+pub fn check_solvency(ctx: Context<CheckSolvency>) -> Result<()> {
+    let outstanding = ledger_outstanding(&ctx.accounts.vault_ledger)?;
+    let vault_balance = ctx.accounts.vault.lamports();
+    if vault_balance >= outstanding {
+        return Ok(());
+    }
+
+    emit!(VaultInsolvent {
+        vault_balance,
+        outstanding,
+        shortfall: outstanding - vault_balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeVaultLedger<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + VaultLedger::INIT_SPACE,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: Account<'info, VaultLedger>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CheckSolvency<'info> {
+    #[account(
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+}
+
+// Account data structs
+// Prize and commission lamports sol_vault owes across all rounds
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct VaultLedger {
+    pub outstanding: u64,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct VaultInsolvent {
+    pub vault_balance: u64,
+    pub outstanding: u64,
+    pub shortfall: u64,
+    pub timestamp: i64,
+}
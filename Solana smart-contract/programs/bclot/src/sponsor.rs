@@ -0,0 +1,405 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RoundTicketsPurchase,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+};
+use crate::solvency::record_in_ledger_account;
+
+const MAX_MATCH_RATIO_BPS: u16 = 50_000; // 5:1
+
+// Anyone may add lamports to a round's prize pool. Boosts bypass the entrance fee
+pub fn boost_prize(ctx: Context<BoostPrize>, round_id: u32, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    let round = &mut ctx.accounts.round;
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.sponsored_amount = round
+        .sponsored_amount
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    record_in_ledger_account(&ctx.accounts.vault_ledger, amount)?;
+
+    msg!("✅ Round {} prize boosted by {} lamports", round_id, amount);
+
+    emit!(PrizeBoosted {
+        token: ctx.accounts.sol_raffle.token_mint,
+        round_id,
+        sponsor: ctx.accounts.sponsor.key(),
+        amount,
+        sponsored_amount: round.sponsored_amount,
+        prize_amount: round.prize_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Sponsor escrows `cap` lamports that match player contributions at `ratio_bps`
+// (10_000 = 1:1) while the round is open. Approved by the authority or operator, one match
+// per round at a time
+pub fn create_sponsor_match(
+    ctx: Context<CreateSponsorMatch>,
+    round_id: u32,
+    ratio_bps: u16,
+    cap: u64,
+) -> Result<()> {
+    require!(
+        ctx.accounts.raffle_state.is_operator(&ctx.accounts.operator.key()),
+        RaffleError::Unauthorized
+    );
+    require!(cap > 0, RaffleError::InvalidAmount);
+    require!(
+        ratio_bps > 0 && ratio_bps <= MAX_MATCH_RATIO_BPS,
+        RaffleError::InvalidMatchRatio
+    );
+
+    let round = &mut ctx.accounts.round;
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require!(
+        round.sponsor_match == Pubkey::default(),
+        RaffleError::SponsorMatchActive
+    );
+    round.sponsor_match = ctx.accounts.sponsor_match.key();
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.sponsor_match.to_account_info(),
+            },
+        ),
+        cap,
+    )?;
+
+    let sponsor_match = &mut ctx.accounts.sponsor_match;
+    sponsor_match.round = ctx.accounts.round.key();
+    sponsor_match.sponsor = ctx.accounts.sponsor.key();
+    sponsor_match.ratio_bps = ratio_bps;
+    sponsor_match.cap = cap;
+    sponsor_match.matched = 0;
+    sponsor_match.bump = ctx.bumps.sponsor_match;
+
+    emit!(SponsorMatchCreated {
+        round_id,
+        sponsor: sponsor_match.sponsor,
+        ratio_bps,
+        cap,
+    });
+
+    Ok(())
+}
+
+// Returns unused escrow to the sponsor once the round no longer accepts purchases
+pub fn close_sponsor_match(ctx: Context<CloseSponsorMatch>, round_id: u32) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        round.status != RoundStatus::Open || now >= round.end_time,
+        RaffleError::RoundNotEndedYet
+    );
+    if round.sponsor_match == ctx.accounts.sponsor_match.key() {
+        round.sponsor_match = Pubkey::default();
+    }
+
+    let sponsor_match = &ctx.accounts.sponsor_match;
+    let refunded = sponsor_match.remaining();
+
+    msg!("✅ Sponsor match for round {} closed, {} lamports returned", round_id, refunded);
+
+    emit!(SponsorMatchClosed {
+        round_id,
+        sponsor: sponsor_match.sponsor,
+        matched: sponsor_match.matched,
+        refunded,
+    });
+
+    Ok(())
+}
+
+// The round's open match must be passed, leaving it out would skip the matching
+pub fn check_sponsor_match(round: &Round, sponsor_match: Option<Pubkey>) -> Result<()> {
+    match sponsor_match {
+        Some(sponsor_match) => require_keys_eq!(sponsor_match, round.sponsor_match, RaffleError::SponsorMatchRequired),
+        None => require_keys_eq!(round.sponsor_match, Pubkey::default(), RaffleError::SponsorMatchRequired),
+    }
+    Ok(())
+}
+
+// Moves matching funds for a purchase from the escrow into sol_vault and the round's pot
+pub fn apply_sponsor_match<'info>(
+    sponsor_match: &mut Account<'info, SponsorMatch>,
+    vault: &AccountInfo<'info>,
+    round: &mut Round,
+    contribution: u64,
+) -> Result<u64> {
+    let wanted = (contribution as u128)
+        .checked_mul(sponsor_match.ratio_bps as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / 10_000;
+    let matched = std::cmp::min(wanted, sponsor_match.remaining() as u128) as u64;
+
+    if matched == 0 {
+        return Ok(0);
+    }
+
+    **sponsor_match.to_account_info().try_borrow_mut_lamports()? -= matched;
+    **vault.try_borrow_mut_lamports()? += matched;
+
+    sponsor_match.matched = sponsor_match
+        .matched
+        .checked_add(matched)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(matched)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.sponsored_amount = round
+        .sponsored_amount
+        .checked_add(matched)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    emit!(SponsorMatched {
+        round_id: round.round_id,
+        sponsor: sponsor_match.sponsor,
+        contribution,
+        matched,
+        total_matched: sponsor_match.matched,
+    });
+
+    Ok(matched)
+}
+
+// Takes a cancelled purchase's matching back out of the round's pot, the caller returns the
+// lamports from sol_vault to the escrow. The match can't close while the round is open, so a
+// matched purchase still has it as the round's open match
+pub fn unmatch_purchase(
+    sponsor_match: Option<&mut SponsorMatch>,
+    round: &mut Round,
+    purchase: &RoundTicketsPurchase,
+) -> Result<u64> {
+    let matched = purchase.sponsor_matched;
+    if matched == 0 {
+        return Ok(0);
+    }
+    let sponsor_match = sponsor_match.ok_or(RaffleError::SponsorMatchRequired)?;
+
+    sponsor_match.matched = sponsor_match
+        .matched
+        .checked_sub(matched)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.prize_amount = round
+        .prize_amount
+        .checked_sub(matched)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.sponsored_amount = round
+        .sponsored_amount
+        .checked_sub(matched)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    Ok(matched)
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct BoostPrize<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct CreateSponsorMatch<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        init,
+        payer = sponsor,
+        space = 8 + SponsorMatch::INIT_SPACE,
+        seeds = [
+            b"sponsor_match",
+            round.key().as_ref(),
+            sponsor.key().as_ref()
+        ],
+        bump
+    )]
+    pub sponsor_match: Account<'info, SponsorMatch>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct CloseSponsorMatch<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        close = sponsor,
+        seeds = [
+            b"sponsor_match",
+            round.key().as_ref(),
+            sponsor.key().as_ref()
+        ],
+        bump = sponsor_match.bump,
+        has_one = sponsor
+    )]
+    pub sponsor_match: Account<'info, SponsorMatch>,
+
+    /// CHECK: receives the unused escrow, validated via has_one
+    #[account(mut)]
+    pub sponsor: AccountInfo<'info>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct SponsorMatch {
+    pub round: Pubkey, //connection with Round struct
+    pub sponsor: Pubkey,
+    pub ratio_bps: u16,
+    pub cap: u64,
+    pub matched: u64,
+    pub bump: u8,
+}
+
+impl SponsorMatch {
+    pub fn remaining(&self) -> u64 {
+        self.cap.saturating_sub(self.matched)
+    }
+}
+
+// Events
+#[event]
+pub struct PrizeBoosted {
+    pub token: Pubkey,
+    pub round_id: u32,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub sponsored_amount: u64,
+    pub prize_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SponsorMatchCreated {
+    pub round_id: u32,
+    pub sponsor: Pubkey,
+    pub ratio_bps: u16,
+    pub cap: u64,
+}
+
+#[event]
+pub struct SponsorMatched {
+    pub round_id: u32,
+    pub sponsor: Pubkey,
+    pub contribution: u64,
+    pub matched: u64,
+    pub total_matched: u64,
+}
+
+#[event]
+pub struct SponsorMatchClosed {
+    pub round_id: u32,
+    pub sponsor: Pubkey,
+    pub matched: u64,
+    pub refunded: u64,
+}
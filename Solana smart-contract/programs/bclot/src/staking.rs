@@ -0,0 +1,817 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    pubkey,
+    sysvar,
+};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{
+    Round,
+    RoundStatus,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateTokenRaffle,
+    SECONDS_IN_DAY,
+    transfer,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::keeper::{require_keeper, KeeperRegistry};
+use crate::migration::Versioned;
+use crate::solvency::{record_in_ledger_account, release_in_ledger_account};
+
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey = pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+
+// Holds lamports on their way into and out of the stake pool, owns the pool tokens
+pub const STAKE_ESCROW_SEED: &[u8] = b"stake_escrow";
+
+// StakePoolInstruction tags, data is the tag followed by a u64 amount
+const DEPOSIT_SOL_TAG: u8 = 14;
+const WITHDRAW_SOL_TAG: u8 = 16;
+
+// Only authority. Binds the program to one SPL stake pool and creates its pool token account
+pub fn initialize_staking(ctx: Context<InitializeStaking>) -> Result<()> {
+    let staking_config = &mut ctx.accounts.staking_config;
+    staking_config.stake_pool = ctx.accounts.stake_pool.key();
+    staking_config.pool_mint = ctx.accounts.pool_mint.key();
+    staking_config.bump = ctx.bumps.staking_config;
+
+    msg!("✅ Staking initialized with stake pool {}", staking_config.stake_pool);
+
+    Ok(())
+}
+
+// Only authority. Minimum time between yield sweeps, 0 - sweep any time
+pub fn set_yield_sweep_interval(ctx: Context<SetYieldSweepInterval>, interval_seconds: i64) -> Result<()> {
+    require!(interval_seconds >= 0, RaffleError::InvalidAmount);
+
+    ctx.accounts.staking_config.sweep_interval_seconds = interval_seconds;
+
+    emit!(YieldSweepIntervalUpdated { interval_seconds });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::YieldSweepIntervalChanged,
+        interval_seconds as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Opt-in per raffle, only rounds of a day or longer are staked.
+// Yield goes to the round's prize, or is swept to insurance_vault with `yield_to_insurance`
+pub fn set_staking_mode(
+    ctx: Context<UpdateTokenRaffle>,
+    enabled: bool,
+    yield_to_insurance: bool,
+) -> Result<()> {
+    let sol_raffle = &mut ctx.accounts.sol_raffle;
+    sol_raffle.staking_enabled = enabled;
+    sol_raffle.staking_yield_to_insurance = yield_to_insurance;
+
+    emit!(StakingModeUpdated {
+        token_raffle: sol_raffle.key(),
+        enabled,
+        yield_to_insurance,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::StakingModeChanged,
+        enabled as u64,
+    )?;
+
+    Ok(())
+}
+
+// Keeper crank. Deposits the round's prize not yet staked into the stake pool. Can run
+// repeatedly while the round is open to pick up later purchases
+pub fn stake_prize_pool(ctx: Context<StakePrizePool>, round_id: u32) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+
+    let round = &mut ctx.accounts.round;
+    round.check_version()?;
+
+    require!(
+        ctx.accounts.sol_raffle.staking_enabled
+            && round.end_time - round.start_time >= SECONDS_IN_DAY,
+        RaffleError::StakingNotAvailable
+    );
+    require!(round.status == RoundStatus::Open, RaffleError::RoundNotOpen);
+    require!(!round.frozen, RaffleError::RoundFrozen);
+
+    let amount = round.prize_amount.saturating_sub(round.staked_lamports);
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    // Staked lamports stop being an obligation of sol_vault until unstaked
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.stake_escrow.try_borrow_mut_lamports()? += amount;
+    release_in_ledger_account(&ctx.accounts.vault_ledger, amount)?;
+    ctx.accounts.staking_config.total_principal = ctx
+        .accounts
+        .staking_config
+        .total_principal
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let pool_tokens_before = ctx.accounts.pool_token_account.amount;
+
+    deposit_sol(
+        StakePoolCpiAccounts {
+            stake_pool: ctx.accounts.stake_pool.to_account_info(),
+            pool_withdraw_authority: ctx.accounts.pool_withdraw_authority.to_account_info(),
+            reserve_stake: ctx.accounts.reserve_stake.to_account_info(),
+            manager_fee_account: ctx.accounts.manager_fee_account.to_account_info(),
+            pool_mint: ctx.accounts.pool_mint.to_account_info(),
+            stake_escrow: ctx.accounts.stake_escrow.to_account_info(),
+            pool_token_account: ctx.accounts.pool_token_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            stake_pool_program: ctx.accounts.stake_pool_program.to_account_info(),
+        },
+        ctx.accounts.system_program.to_account_info(),
+        amount,
+        ctx.bumps.stake_escrow,
+    )?;
+
+    ctx.accounts.pool_token_account.reload()?;
+    let pool_tokens = ctx.accounts.pool_token_account.amount - pool_tokens_before;
+
+    round.staked_lamports = round
+        .staked_lamports
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.stake_pool_tokens = round
+        .stake_pool_tokens
+        .checked_add(pool_tokens)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("✅ Round {} staked {} lamports for {} pool tokens", round_id, amount, pool_tokens);
+
+    emit!(PrizePoolStaked {
+        round_id,
+        amount,
+        pool_tokens,
+        staked_lamports: round.staked_lamports,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Permissionless once the round has ended. Redeems the round's pool tokens, the principal
+// returns to sol_vault and the yield goes to the prize or stays in stake_escrow for
+// sweep_staking_yield. Claims and cancellations wait for this
+pub fn unstake_prize_pool(ctx: Context<UnstakePrizePool>, round_id: u32) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    round.check_version()?;
+
+    require!(round.stake_pool_tokens > 0, RaffleError::InvalidAmount);
+    require!(
+        round.status != RoundStatus::Open || Clock::get()?.unix_timestamp >= round.end_time,
+        RaffleError::RoundNotEndedYet
+    );
+
+    let escrow_balance_before = ctx.accounts.stake_escrow.lamports();
+    let stake_escrow_seeds: &[&[&[u8]]] = &[&[STAKE_ESCROW_SEED, &[ctx.bumps.stake_escrow]]];
+
+    withdraw_sol(
+        StakePoolCpiAccounts {
+            stake_pool: ctx.accounts.stake_pool.to_account_info(),
+            pool_withdraw_authority: ctx.accounts.pool_withdraw_authority.to_account_info(),
+            reserve_stake: ctx.accounts.reserve_stake.to_account_info(),
+            manager_fee_account: ctx.accounts.manager_fee_account.to_account_info(),
+            pool_mint: ctx.accounts.pool_mint.to_account_info(),
+            stake_escrow: ctx.accounts.stake_escrow.to_account_info(),
+            pool_token_account: ctx.accounts.pool_token_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            stake_pool_program: ctx.accounts.stake_pool_program.to_account_info(),
+        },
+        ctx.accounts.clock.to_account_info(),
+        ctx.accounts.stake_history.to_account_info(),
+        ctx.accounts.stake_program.to_account_info(),
+        round.stake_pool_tokens,
+        ctx.bumps.stake_escrow,
+    )?;
+
+    let received = ctx.accounts.stake_escrow.lamports() - escrow_balance_before;
+    let principal = round.staked_lamports;
+    let yield_amount = received.saturating_sub(principal);
+    // Pool fees can exceed the yield of a short stake, the prize absorbs the difference
+    let loss = principal.saturating_sub(received);
+    // Yield left in stake_escrow must keep it rent exempt until swept, a smaller amount goes
+    // to the prize instead
+    let to_insurance = ctx.accounts.sol_raffle.staking_yield_to_insurance
+        && yield_amount > 0
+        && escrow_balance_before + yield_amount >= Rent::get()?.minimum_balance(0);
+
+    let to_vault = if to_insurance { received - yield_amount } else { received };
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.stake_escrow.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        to_vault,
+        Some(stake_escrow_seeds),
+    )?;
+    record_in_ledger_account(&ctx.accounts.vault_ledger, to_vault)?;
+
+    let staking_config = &mut ctx.accounts.staking_config;
+    staking_config.total_principal = staking_config.total_principal.saturating_sub(principal);
+    if to_insurance {
+        staking_config.realize_yield(yield_amount)?;
+    }
+
+    round.prize_amount = round
+        .prize_amount
+        .checked_add(to_vault)
+        .and_then(|amount| amount.checked_sub(principal))
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    if !to_insurance {
+        round.staking_yield = round
+            .staking_yield
+            .checked_add(yield_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+    round.staking_loss = round
+        .staking_loss
+        .checked_add(loss)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    round.staked_lamports = 0;
+    round.stake_pool_tokens = 0;
+
+    msg!(
+        "✅ Round {} unstaked {} lamports, yield {} to {}",
+        round_id,
+        received,
+        yield_amount,
+        if to_insurance { "insurance" } else { "prize" }
+    );
+
+    emit!(PrizePoolUnstaked {
+        round_id,
+        principal,
+        received,
+        yield_amount,
+        loss,
+        to_insurance,
+        prize_amount: round.prize_amount,
+        total_principal: staking_config.total_principal,
+        unswept_yield: staking_config.unswept_yield(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Keeper crank. Moves yield realized for insurance out of stake_escrow into insurance_vault,
+// at most once per sweep_interval_seconds
+pub fn sweep_staking_yield(ctx: Context<SweepStakingYield>) -> Result<()> {
+    require_keeper(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.keeper_registry.as_ref(),
+        &ctx.accounts.keeper.key(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let staking_config = &mut ctx.accounts.staking_config;
+
+    require!(
+        now >= staking_config.last_sweep_time.saturating_add(staking_config.sweep_interval_seconds),
+        RaffleError::YieldSweepTooEarly
+    );
+
+    let unswept = staking_config.unswept_yield();
+    require!(unswept > 0, RaffleError::InvalidAmount);
+
+    // stake_escrow is left empty or rent exempt, stray lamports short of that go along
+    let escrow_balance = ctx.accounts.stake_escrow.lamports();
+    let amount = if escrow_balance - unswept < Rent::get()?.minimum_balance(0) {
+        escrow_balance
+    } else {
+        unswept
+    };
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.stake_escrow.to_account_info(),
+        ctx.accounts.insurance_vault.to_account_info(),
+        amount,
+        Some(&[&[STAKE_ESCROW_SEED, &[ctx.bumps.stake_escrow]]]),
+    )?;
+
+    staking_config.total_yield_swept += unswept;
+    staking_config.last_sweep_time = now;
+
+    msg!("✅ Swept {} lamports of staking yield to insurance", amount);
+
+    emit!(StakingYieldSwept {
+        amount,
+        total_yield_realized: staking_config.total_yield_realized,
+        total_yield_swept: staking_config.total_yield_swept,
+        insurance_balance: ctx.accounts.insurance_vault.lamports(),
+        timestamp: now,
+    });
+
+    Ok(())
+}
+
+pub struct StakePoolCpiAccounts<'info> {
+    pub stake_pool: AccountInfo<'info>,
+    pub pool_withdraw_authority: AccountInfo<'info>,
+    pub reserve_stake: AccountInfo<'info>,
+    pub manager_fee_account: AccountInfo<'info>,
+    pub pool_mint: AccountInfo<'info>,
+    pub stake_escrow: AccountInfo<'info>,
+    pub pool_token_account: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub stake_pool_program: AccountInfo<'info>,
+}
+
+// DepositSol of `amount` lamports held by stake_escrow, minting into pool_token_account
+pub fn deposit_sol<'info>(
+    accounts: StakePoolCpiAccounts<'info>,
+    system_program: AccountInfo<'info>,
+    amount: u64,
+    stake_escrow_bump: u8,
+) -> Result<()> {
+    let mut data = vec![DEPOSIT_SOL_TAG];
+    data.extend_from_slice(&amount.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: SPL_STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(accounts.pool_withdraw_authority.key(), false),
+            AccountMeta::new(accounts.reserve_stake.key(), false),
+            AccountMeta::new(accounts.stake_escrow.key(), true),
+            AccountMeta::new(accounts.pool_token_account.key(), false),
+            AccountMeta::new(accounts.manager_fee_account.key(), false),
+            // No referrer, the referral fee comes back to us
+            AccountMeta::new(accounts.pool_token_account.key(), false),
+            AccountMeta::new(accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(system_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.stake_pool,
+            accounts.pool_withdraw_authority,
+            accounts.reserve_stake,
+            accounts.stake_escrow,
+            accounts.pool_token_account,
+            accounts.manager_fee_account,
+            accounts.pool_mint,
+            system_program,
+            accounts.token_program,
+            accounts.stake_pool_program,
+        ],
+        &[&[STAKE_ESCROW_SEED, &[stake_escrow_bump]]],
+    )?;
+
+    Ok(())
+}
+
+// WithdrawSol burning `pool_tokens` from pool_token_account, lamports land in stake_escrow
+pub fn withdraw_sol<'info>(
+    accounts: StakePoolCpiAccounts<'info>,
+    clock: AccountInfo<'info>,
+    stake_history: AccountInfo<'info>,
+    stake_program: AccountInfo<'info>,
+    pool_tokens: u64,
+    stake_escrow_bump: u8,
+) -> Result<()> {
+    let mut data = vec![WITHDRAW_SOL_TAG];
+    data.extend_from_slice(&pool_tokens.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: SPL_STAKE_POOL_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.stake_pool.key(), false),
+            AccountMeta::new_readonly(accounts.pool_withdraw_authority.key(), false),
+            AccountMeta::new_readonly(accounts.stake_escrow.key(), true),
+            AccountMeta::new(accounts.pool_token_account.key(), false),
+            AccountMeta::new(accounts.reserve_stake.key(), false),
+            AccountMeta::new(accounts.stake_escrow.key(), false),
+            AccountMeta::new(accounts.manager_fee_account.key(), false),
+            AccountMeta::new(accounts.pool_mint.key(), false),
+            AccountMeta::new_readonly(clock.key(), false),
+            AccountMeta::new_readonly(stake_history.key(), false),
+            AccountMeta::new_readonly(stake_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.stake_pool,
+            accounts.pool_withdraw_authority,
+            accounts.stake_escrow,
+            accounts.pool_token_account,
+            accounts.reserve_stake,
+            accounts.manager_fee_account,
+            accounts.pool_mint,
+            clock,
+            stake_history,
+            stake_program,
+            accounts.token_program,
+            accounts.stake_pool_program,
+        ],
+        &[&[STAKE_ESCROW_SEED, &[stake_escrow_bump]]],
+    )?;
+
+    Ok(())
+}
+
+impl StakingConfig {
+    pub fn realize_yield(&mut self, amount: u64) -> Result<()> {
+        self.total_yield_realized = self
+            .total_yield_realized
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    // Realized yield still held in stake_escrow
+    pub fn unswept_yield(&self) -> u64 {
+        self.total_yield_realized - self.total_yield_swept
+    }
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeStaking<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + StakingConfig::INIT_SPACE,
+        seeds = [b"staking_config"],
+        bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// CHECK: stake pool state, validated by the stake pool program on every CPI
+    #[account(owner = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    pub pool_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA owning the pool tokens
+    #[account(
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"stake_pool_tokens"],
+        bump,
+        token::mint = pool_mint,
+        token::authority = stake_escrow
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct StakePrizePool<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_pool_tokens"],
+        bump
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut, address = staking_config.stake_pool)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    pub pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = staking_config.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
+    /// CHECK: program id checked by address
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct UnstakePrizePool<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake_pool_tokens"],
+        bump
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut, address = staking_config.stake_pool)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    pub pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = staking_config.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: sysvar checked by address
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: program id checked by address
+    #[account(address = STAKE_PROGRAM_ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    /// CHECK: program id checked by address
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetYieldSweepInterval<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+pub struct SweepStakingYield<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [b"keeper_registry"],
+        bump = keeper_registry.bump
+    )]
+    pub keeper_registry: Option<Account<'info, KeeperRegistry>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+// The SPL stake pool idle prize pools are deposited into
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct StakingConfig {
+    pub stake_pool: Pubkey,
+    pub pool_mint: Pubkey,
+    pub bump: u8,
+    pub total_principal: u64, // prize lamports currently in the stake pool
+    pub total_yield_realized: u64, // routed to insurance, held in stake_escrow until swept
+    pub total_yield_swept: u64,
+    pub sweep_interval_seconds: i64,
+    pub last_sweep_time: i64,
+}
+
+// Events
+#[event]
+pub struct StakingModeUpdated {
+    pub token_raffle: Pubkey,
+    pub enabled: bool,
+    pub yield_to_insurance: bool,
+}
+
+#[event]
+pub struct PrizePoolStaked {
+    pub round_id: u32,
+    pub amount: u64,
+    pub pool_tokens: u64,
+    pub staked_lamports: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PrizePoolUnstaked {
+    pub round_id: u32,
+    pub principal: u64,
+    pub received: u64,
+    pub yield_amount: u64,
+    pub loss: u64,
+    pub to_insurance: bool,
+    pub prize_amount: u64,
+    pub total_principal: u64,
+    pub unswept_yield: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StakingYieldSwept {
+    pub amount: u64,
+    pub total_yield_realized: u64,
+    pub total_yield_swept: u64,
+    pub insurance_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct YieldSweepIntervalUpdated {
+    pub interval_seconds: i64,
+}
@@ -0,0 +1,183 @@
+use anchor_lang::Discriminator;
+
+// Every account type the program owns, in one place for indexers and Geyser plugins building
+// the crate with `no-entrypoint`. Borsh accounts decode with AccountDeserialize::try_deserialize,
+// zero-copy ones (AuditLog, PendingQueue, RefundBitmap, RoundTickets, WinnerHistory) with bytemuck past the
+// 8-byte discriminator
+pub use crate::{
+    ClaimPrizeTicket,
+    ClientState,
+    RaffleState,
+    Round,
+    RoundTickets,
+    RoundTicketsPurchase,
+    TokenRaffle,
+    VRFContext,
+};
+pub use crate::audit::AuditLog;
+pub use crate::blocklist::Blocklist;
+pub use crate::cancellation::RefundBitmap;
+pub use crate::cluster_profile::ClusterProfile;
+pub use crate::commission::CommissionVault;
+pub use crate::daily_stats::DailyStats;
+pub use crate::delegate::ClaimDelegate;
+pub use crate::draw_proof::DrawProof;
+pub use crate::fee_discount::{FeeDiscountConfig, StakerPosition};
+pub use crate::jackpot::MegaJackpot;
+pub use crate::keeper::KeeperRegistry;
+pub use crate::metadata::{ProgramMetadata, RaffleMetadata};
+pub use crate::migration::ProgramVersion;
+pub use crate::oracle_failover::FeedFailover;
+pub use crate::player_stats::PlayerStats;
+pub use crate::price_cache::PriceCache;
+pub use crate::price_feeds::FeedRegistry;
+pub use crate::promo::FreeEntryClaim;
+pub use crate::purchase_limits::WalletPurchases;
+pub use crate::queue::PendingQueue;
+pub use crate::raffle_stats::RaffleStats;
+pub use crate::records::Records;
+pub use crate::solvency::VaultLedger;
+pub use crate::sponsor::SponsorMatch;
+pub use crate::staking::StakingConfig;
+pub use crate::treasury::TreasuryPosition;
+pub use crate::tuktuk::TukTukTask;
+pub use crate::vesting::VestingAccount;
+pub use crate::winner_history::WinnerHistory;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AccountKind {
+    AuditLog,
+    Blocklist,
+    ClaimDelegate,
+    ClaimPrizeTicket,
+    ClientState,
+    ClusterProfile,
+    CommissionVault,
+    DailyStats,
+    DrawProof,
+    FeeDiscountConfig,
+    FeedFailover,
+    FeedRegistry,
+    FreeEntryClaim,
+    KeeperRegistry,
+    MegaJackpot,
+    PendingQueue,
+    PlayerStats,
+    PriceCache,
+    ProgramMetadata,
+    ProgramVersion,
+    RaffleMetadata,
+    RaffleState,
+    RaffleStats,
+    Records,
+    RefundBitmap,
+    Round,
+    RoundTickets,
+    RoundTicketsPurchase,
+    SponsorMatch,
+    StakerPosition,
+    StakingConfig,
+    TokenRaffle,
+    TreasuryPosition,
+    TukTukTask,
+    VaultLedger,
+    VestingAccount,
+    VRFContext,
+    WalletPurchases,
+    WinnerHistory,
+}
+
+impl AccountKind {
+    pub const ALL: [AccountKind; 39] = [
+        AccountKind::AuditLog,
+        AccountKind::Blocklist,
+        AccountKind::ClaimDelegate,
+        AccountKind::ClaimPrizeTicket,
+        AccountKind::ClientState,
+        AccountKind::ClusterProfile,
+        AccountKind::CommissionVault,
+        AccountKind::DailyStats,
+        AccountKind::DrawProof,
+        AccountKind::FeeDiscountConfig,
+        AccountKind::FeedFailover,
+        AccountKind::FeedRegistry,
+        AccountKind::FreeEntryClaim,
+        AccountKind::KeeperRegistry,
+        AccountKind::MegaJackpot,
+        AccountKind::PendingQueue,
+        AccountKind::PlayerStats,
+        AccountKind::PriceCache,
+        AccountKind::ProgramMetadata,
+        AccountKind::ProgramVersion,
+        AccountKind::RaffleMetadata,
+        AccountKind::RaffleState,
+        AccountKind::RaffleStats,
+        AccountKind::Records,
+        AccountKind::RefundBitmap,
+        AccountKind::Round,
+        AccountKind::RoundTickets,
+        AccountKind::RoundTicketsPurchase,
+        AccountKind::SponsorMatch,
+        AccountKind::StakerPosition,
+        AccountKind::StakingConfig,
+        AccountKind::TokenRaffle,
+        AccountKind::TreasuryPosition,
+        AccountKind::TukTukTask,
+        AccountKind::VaultLedger,
+        AccountKind::VestingAccount,
+        AccountKind::VRFContext,
+        AccountKind::WalletPurchases,
+        AccountKind::WinnerHistory,
+    ];
+
+    pub fn discriminator(self) -> &'static [u8] {
+        match self {
+            AccountKind::AuditLog => AuditLog::DISCRIMINATOR,
+            AccountKind::Blocklist => Blocklist::DISCRIMINATOR,
+            AccountKind::ClaimDelegate => ClaimDelegate::DISCRIMINATOR,
+            AccountKind::ClaimPrizeTicket => ClaimPrizeTicket::DISCRIMINATOR,
+            AccountKind::ClientState => ClientState::DISCRIMINATOR,
+            AccountKind::ClusterProfile => ClusterProfile::DISCRIMINATOR,
+            AccountKind::CommissionVault => CommissionVault::DISCRIMINATOR,
+            AccountKind::DailyStats => DailyStats::DISCRIMINATOR,
+            AccountKind::DrawProof => DrawProof::DISCRIMINATOR,
+            AccountKind::FeeDiscountConfig => FeeDiscountConfig::DISCRIMINATOR,
+            AccountKind::FeedFailover => FeedFailover::DISCRIMINATOR,
+            AccountKind::FeedRegistry => FeedRegistry::DISCRIMINATOR,
+            AccountKind::FreeEntryClaim => FreeEntryClaim::DISCRIMINATOR,
+            AccountKind::KeeperRegistry => KeeperRegistry::DISCRIMINATOR,
+            AccountKind::MegaJackpot => MegaJackpot::DISCRIMINATOR,
+            AccountKind::PendingQueue => PendingQueue::DISCRIMINATOR,
+            AccountKind::PlayerStats => PlayerStats::DISCRIMINATOR,
+            AccountKind::PriceCache => PriceCache::DISCRIMINATOR,
+            AccountKind::ProgramMetadata => ProgramMetadata::DISCRIMINATOR,
+            AccountKind::ProgramVersion => ProgramVersion::DISCRIMINATOR,
+            AccountKind::RaffleMetadata => RaffleMetadata::DISCRIMINATOR,
+            AccountKind::RaffleState => RaffleState::DISCRIMINATOR,
+            AccountKind::RaffleStats => RaffleStats::DISCRIMINATOR,
+            AccountKind::Records => Records::DISCRIMINATOR,
+            AccountKind::RefundBitmap => RefundBitmap::DISCRIMINATOR,
+            AccountKind::Round => Round::DISCRIMINATOR,
+            AccountKind::RoundTickets => RoundTickets::DISCRIMINATOR,
+            AccountKind::RoundTicketsPurchase => RoundTicketsPurchase::DISCRIMINATOR,
+            AccountKind::SponsorMatch => SponsorMatch::DISCRIMINATOR,
+            AccountKind::StakerPosition => StakerPosition::DISCRIMINATOR,
+            AccountKind::StakingConfig => StakingConfig::DISCRIMINATOR,
+            AccountKind::TokenRaffle => TokenRaffle::DISCRIMINATOR,
+            AccountKind::TreasuryPosition => TreasuryPosition::DISCRIMINATOR,
+            AccountKind::TukTukTask => TukTukTask::DISCRIMINATOR,
+            AccountKind::VaultLedger => VaultLedger::DISCRIMINATOR,
+            AccountKind::VestingAccount => VestingAccount::DISCRIMINATOR,
+            AccountKind::VRFContext => VRFContext::DISCRIMINATOR,
+            AccountKind::WalletPurchases => WalletPurchases::DISCRIMINATOR,
+            AccountKind::WinnerHistory => WinnerHistory::DISCRIMINATOR,
+        }
+    }
+
+    // None for data the program didn't write, the SOL vaults hold none
+    pub fn from_account_data(data: &[u8]) -> Option<Self> {
+        AccountKind::ALL
+            .into_iter()
+            .find(|kind| data.starts_with(kind.discriminator()))
+    }
+}
@@ -0,0 +1,458 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use crate::{
+    RaffleError,
+    RaffleState,
+    transfer,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::commission::CommissionVault;
+use crate::staking::{
+    deposit_sol,
+    withdraw_sol,
+    StakePoolCpiAccounts,
+    StakingConfig,
+    SPL_STAKE_POOL_PROGRAM_ID,
+    STAKE_ESCROW_SEED,
+    STAKE_PROGRAM_ID,
+};
+
+// Only authority. Parked commission earns yield in the stake pool from initialize_staking
+pub fn initialize_treasury(ctx: Context<InitializeTreasury>, treasurer: Pubkey) -> Result<()> {
+    let treasury_position = &mut ctx.accounts.treasury_position;
+    treasury_position.venue = ctx.accounts.staking_config.stake_pool;
+    treasury_position.treasurer = treasurer;
+    treasury_position.bump = ctx.bumps.treasury_position;
+
+    msg!("✅ Treasury initialized, treasurer {}", treasurer);
+
+    Ok(())
+}
+
+// Only authority
+pub fn set_treasurer(ctx: Context<SetTreasurer>, treasurer: Pubkey) -> Result<()> {
+    let treasury_position = &mut ctx.accounts.treasury_position;
+    let old_treasurer = treasury_position.treasurer;
+    treasury_position.treasurer = treasurer;
+
+    emit!(TreasurerChanged {
+        old_treasurer,
+        new_treasurer: treasurer,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::TreasurerChanged,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Only treasurer. Moves accrued commission out of commission_vault into the venue,
+// it can't be withdrawn by the beneficiary until withdraw_treasury brings it back
+pub fn deposit_treasury(ctx: Context<DepositTreasury>, amount: u64) -> Result<()> {
+    let commission_vault = &mut ctx.accounts.commission_vault;
+
+    require!(amount > 0, RaffleError::InvalidAmount);
+    require!(
+        commission_vault.balance >= amount,
+        RaffleError::InsufficientVaultBalance
+    );
+
+    commission_vault.balance -= amount;
+    **commission_vault.to_account_info().try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.stake_escrow.try_borrow_mut_lamports()? += amount;
+
+    let pool_tokens_before = ctx.accounts.pool_token_account.amount;
+
+    deposit_sol(
+        StakePoolCpiAccounts {
+            stake_pool: ctx.accounts.stake_pool.to_account_info(),
+            pool_withdraw_authority: ctx.accounts.pool_withdraw_authority.to_account_info(),
+            reserve_stake: ctx.accounts.reserve_stake.to_account_info(),
+            manager_fee_account: ctx.accounts.manager_fee_account.to_account_info(),
+            pool_mint: ctx.accounts.pool_mint.to_account_info(),
+            stake_escrow: ctx.accounts.stake_escrow.to_account_info(),
+            pool_token_account: ctx.accounts.pool_token_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            stake_pool_program: ctx.accounts.stake_pool_program.to_account_info(),
+        },
+        ctx.accounts.system_program.to_account_info(),
+        amount,
+        ctx.bumps.stake_escrow,
+    )?;
+
+    ctx.accounts.pool_token_account.reload()?;
+    let pool_tokens = ctx.accounts.pool_token_account.amount - pool_tokens_before;
+
+    let treasury_position = &mut ctx.accounts.treasury_position;
+    treasury_position.principal = treasury_position
+        .principal
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    treasury_position.pool_tokens = treasury_position
+        .pool_tokens
+        .checked_add(pool_tokens)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("✅ Treasury deposited {} lamports for {} pool tokens", amount, pool_tokens);
+
+    emit!(TreasuryDeposited {
+        treasurer: ctx.accounts.treasurer.key(),
+        amount,
+        pool_tokens,
+        principal: treasury_position.principal,
+        commission_balance: ctx.accounts.commission_vault.balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Only treasurer. Redeems `pool_tokens` of the position back into commission_vault,
+// principal is released pro rata and anything above it counts as accrued commission
+pub fn withdraw_treasury(ctx: Context<WithdrawTreasury>, pool_tokens: u64) -> Result<()> {
+    let position_pool_tokens = ctx.accounts.treasury_position.pool_tokens;
+    require!(
+        pool_tokens > 0 && pool_tokens <= position_pool_tokens,
+        RaffleError::InvalidAmount
+    );
+
+    let principal = (ctx.accounts.treasury_position.principal as u128 * pool_tokens as u128
+        / position_pool_tokens as u128) as u64;
+    let escrow_balance_before = ctx.accounts.stake_escrow.lamports();
+
+    withdraw_sol(
+        StakePoolCpiAccounts {
+            stake_pool: ctx.accounts.stake_pool.to_account_info(),
+            pool_withdraw_authority: ctx.accounts.pool_withdraw_authority.to_account_info(),
+            reserve_stake: ctx.accounts.reserve_stake.to_account_info(),
+            manager_fee_account: ctx.accounts.manager_fee_account.to_account_info(),
+            pool_mint: ctx.accounts.pool_mint.to_account_info(),
+            stake_escrow: ctx.accounts.stake_escrow.to_account_info(),
+            pool_token_account: ctx.accounts.pool_token_account.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+            stake_pool_program: ctx.accounts.stake_pool_program.to_account_info(),
+        },
+        ctx.accounts.clock.to_account_info(),
+        ctx.accounts.stake_history.to_account_info(),
+        ctx.accounts.stake_program.to_account_info(),
+        pool_tokens,
+        ctx.bumps.stake_escrow,
+    )?;
+
+    let received = ctx.accounts.stake_escrow.lamports() - escrow_balance_before;
+    let yield_amount = received.saturating_sub(principal);
+    let loss = principal.saturating_sub(received);
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.stake_escrow.to_account_info(),
+        ctx.accounts.commission_vault.to_account_info(),
+        received,
+        Some(&[&[STAKE_ESCROW_SEED, &[ctx.bumps.stake_escrow]]]),
+    )?;
+
+    let commission_vault = &mut ctx.accounts.commission_vault;
+    commission_vault.balance = commission_vault
+        .balance
+        .checked_add(received)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    commission_vault.total_accrued = commission_vault
+        .total_accrued
+        .checked_add(yield_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let treasury_position = &mut ctx.accounts.treasury_position;
+    treasury_position.principal -= principal;
+    treasury_position.pool_tokens -= pool_tokens;
+    treasury_position.realized_yield = treasury_position
+        .realized_yield
+        .checked_add(yield_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    treasury_position.realized_loss = treasury_position
+        .realized_loss
+        .checked_add(loss)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("✅ Treasury withdrew {} lamports, yield {}", received, yield_amount);
+
+    emit!(TreasuryWithdrawn {
+        treasurer: ctx.accounts.treasurer.key(),
+        pool_tokens,
+        principal,
+        received,
+        yield_amount,
+        loss,
+        commission_balance: commission_vault.balance,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeTreasury<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TreasuryPosition::INIT_SPACE,
+        seeds = [b"treasury_position"],
+        bump
+    )]
+    pub treasury_position: Account<'info, TreasuryPosition>,
+
+    #[account(address = staking_config.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA owning the pool tokens
+    #[account(
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: UncheckedAccount<'info>,
+
+    // Kept apart from the prize pools' tokens
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"treasury_pool_tokens"],
+        bump,
+        token::mint = pool_mint,
+        token::authority = stake_escrow
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Only authority
+#[derive(Accounts)]
+pub struct SetTreasurer<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_position"],
+        bump = treasury_position.bump
+    )]
+    pub treasury_position: Account<'info, TreasuryPosition>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+#[derive(Accounts)]
+pub struct DepositTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_position"],
+        bump = treasury_position.bump,
+        has_one = treasurer @ RaffleError::Unauthorized
+    )]
+    pub treasury_position: Account<'info, TreasuryPosition>,
+
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"commission_vault"],
+        bump = commission_vault.bump
+    )]
+    pub commission_vault: Account<'info, CommissionVault>,
+
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_pool_tokens"],
+        bump
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut, address = treasury_position.venue)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    pub pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = staking_config.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    /// CHECK: program id checked by address
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"treasury_position"],
+        bump = treasury_position.bump,
+        has_one = treasurer @ RaffleError::Unauthorized
+    )]
+    pub treasury_position: Account<'info, TreasuryPosition>,
+
+    pub treasurer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"commission_vault"],
+        bump = commission_vault.bump
+    )]
+    pub commission_vault: Account<'info, CommissionVault>,
+
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_ESCROW_SEED],
+        bump
+    )]
+    pub stake_escrow: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury_pool_tokens"],
+        bump
+    )]
+    pub pool_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut, address = treasury_position.venue)]
+    pub stake_pool: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    pub pool_withdraw_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub reserve_stake: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the stake pool program
+    #[account(mut)]
+    pub manager_fee_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = staking_config.pool_mint)]
+    pub pool_mint: Account<'info, Mint>,
+
+    pub clock: Sysvar<'info, Clock>,
+
+    /// CHECK: sysvar checked by address
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+
+    /// CHECK: program id checked by address
+    #[account(address = STAKE_PROGRAM_ID)]
+    pub stake_program: UncheckedAccount<'info>,
+
+    /// CHECK: program id checked by address
+    #[account(address = SPL_STAKE_POOL_PROGRAM_ID)]
+    pub stake_pool_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+// Commission parked in the yield venue by the treasurer
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct TreasuryPosition {
+    pub venue: Pubkey, // stake pool
+    pub treasurer: Pubkey,
+    pub principal: u64, // commission lamports currently deposited
+    pub pool_tokens: u64,
+    pub realized_yield: u64,
+    pub realized_loss: u64,
+    pub bump: u8,
+}
+
+// Events
+#[event]
+pub struct TreasurerChanged {
+    pub old_treasurer: Pubkey,
+    pub new_treasurer: Pubkey,
+}
+
+#[event]
+pub struct TreasuryDeposited {
+    pub treasurer: Pubkey,
+    pub amount: u64,
+    pub pool_tokens: u64,
+    pub principal: u64,
+    pub commission_balance: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub treasurer: Pubkey,
+    pub pool_tokens: u64,
+    pub principal: u64,
+    pub received: u64,
+    pub yield_amount: u64,
+    pub loss: u64,
+    pub commission_balance: u64,
+    pub timestamp: i64,
+}
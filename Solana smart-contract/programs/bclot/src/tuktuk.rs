@@ -0,0 +1,421 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    hash::hash,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    pubkey,
+};
+use crate::{
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    transfer,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+pub const TUKTUK_PROGRAM_ID: Pubkey = pubkey!("tuktukUrfhXT6ZT77QTU8RQtvgL967uRuVagWF57zVA");
+
+// Funds crank rewards and signs as queue authority, must be added to the task queue off-chain
+pub const TUKTUK_QUEUE_AUTHORITY_SEED: &[u8] = b"tuktuk_queue_authority";
+
+pub fn fund_tuktuk_vault(ctx: Context<FundTukTukVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.funder.to_account_info(),
+        ctx.accounts.queue_authority.to_account_info(),
+        amount,
+        None,
+    )?;
+
+    msg!("TukTuk vault funded with {} lamports", amount);
+    Ok(())
+}
+
+// Only authority
+pub fn withdraw_tuktuk_vault(ctx: Context<WithdrawTukTukVault>, amount: u64) -> Result<()> {
+    require!(amount > 0, RaffleError::InvalidAmount);
+
+    let queue_authority_seeds: &[&[&[u8]]] =
+        &[&[TUKTUK_QUEUE_AUTHORITY_SEED, &[ctx.bumps.queue_authority]]];
+
+    transfer(
+        ctx.accounts.system_program.to_account_info(),
+        ctx.accounts.queue_authority.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        amount,
+        Some(queue_authority_seeds),
+    )?;
+
+    msg!("Withdrawn {} lamports from TukTuk vault", amount);
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::TukTukVaultWithdrawn,
+        amount,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Creates the task config for a raffle and queues the first round end task
+pub fn register_tuktuk_task(ctx: Context<RegisterTukTukTask>, args: TukTukTaskArgs) -> Result<()> {
+    let tuktuk_task = &mut ctx.accounts.tuktuk_task;
+    tuktuk_task.token_raffle = ctx.accounts.sol_raffle.key();
+    tuktuk_task.task_queue = ctx.accounts.task_queue.key();
+    tuktuk_task.bump = ctx.bumps.tuktuk_task;
+
+    queue_round_end_task(
+        tuktuk_task,
+        &ctx.accounts.sol_raffle,
+        TukTukQueueAccounts {
+            queue_authority: ctx.accounts.queue_authority.to_account_info(),
+            task_queue_authority: ctx.accounts.task_queue_authority.to_account_info(),
+            task_queue: ctx.accounts.task_queue.to_account_info(),
+            task: ctx.accounts.task.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            tuktuk_program: ctx.accounts.tuktuk_program.to_account_info(),
+        },
+        ctx.bumps.queue_authority,
+        args,
+    )
+}
+
+// Only authority. Queues the task again for the current round end, tasks are removed once run
+pub fn refresh_tuktuk_task(ctx: Context<RefreshTukTukTask>, args: TukTukTaskArgs) -> Result<()> {
+    queue_round_end_task(
+        &mut ctx.accounts.tuktuk_task,
+        &ctx.accounts.sol_raffle,
+        TukTukQueueAccounts {
+            queue_authority: ctx.accounts.queue_authority.to_account_info(),
+            task_queue_authority: ctx.accounts.task_queue_authority.to_account_info(),
+            task_queue: ctx.accounts.task_queue.to_account_info(),
+            task: ctx.accounts.task.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            tuktuk_program: ctx.accounts.tuktuk_program.to_account_info(),
+        },
+        ctx.bumps.queue_authority,
+        args,
+    )
+}
+
+struct TukTukQueueAccounts<'info> {
+    queue_authority: AccountInfo<'info>,
+    task_queue_authority: AccountInfo<'info>,
+    task_queue: AccountInfo<'info>,
+    task: AccountInfo<'info>,
+    system_program: AccountInfo<'info>,
+    tuktuk_program: AccountInfo<'info>,
+}
+
+fn queue_round_end_task<'info>(
+    tuktuk_task: &mut Account<'info, TukTukTask>,
+    sol_raffle: &Account<'info, TokenRaffle>,
+    accounts: TukTukQueueAccounts<'info>,
+    queue_authority_bump: u8,
+    args: TukTukTaskArgs,
+) -> Result<()> {
+    // The compiled transaction may only call into this program
+    let transaction = &args.transaction;
+    require!(
+        !transaction.instructions.is_empty()
+            && transaction.instructions.iter().all(|ix| {
+                transaction.accounts.get(ix.program_id_index as usize) == Some(&crate::id())
+            }),
+        RaffleError::InvalidTukTukTask
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let trigger_time = sol_raffle
+        .current_round_end_time
+        .filter(|end_time| *end_time > now);
+    let trigger = match trigger_time {
+        Some(end_time) => TriggerV0::Timestamp(end_time),
+        None => TriggerV0::Now,
+    };
+
+    let queue_task_args = QueueTaskArgsV0 {
+        id: args.task_id,
+        trigger,
+        transaction: TransactionSourceV0::CompiledV0(args.transaction),
+        crank_reward: Some(args.crank_reward),
+        free_tasks: args.free_tasks,
+        description: format!("bclot round end {}", sol_raffle.cadence_id),
+    };
+
+    let mut data = hash(b"global:queue_task_v0").to_bytes()[..8].to_vec();
+    queue_task_args.serialize(&mut data)?;
+
+    let ix = Instruction {
+        program_id: TUKTUK_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(accounts.queue_authority.key(), true), // payer
+            AccountMeta::new_readonly(accounts.queue_authority.key(), true),
+            AccountMeta::new_readonly(accounts.task_queue_authority.key(), false),
+            AccountMeta::new(accounts.task_queue.key(), false),
+            AccountMeta::new(accounts.task.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke_signed(
+        &ix,
+        &[
+            accounts.queue_authority,
+            accounts.task_queue_authority,
+            accounts.task_queue,
+            accounts.task.clone(),
+            accounts.system_program,
+            accounts.tuktuk_program,
+        ],
+        &[&[TUKTUK_QUEUE_AUTHORITY_SEED, &[queue_authority_bump]]],
+    )?;
+
+    tuktuk_task.task = accounts.task.key();
+    tuktuk_task.task_id = args.task_id;
+    tuktuk_task.crank_reward = args.crank_reward;
+    tuktuk_task.trigger_time = trigger_time.unwrap_or(now);
+
+    msg!("✅ TukTuk task {} queued for {}", args.task_id, tuktuk_task.trigger_time);
+
+    emit!(TukTukTaskQueued {
+        token_raffle: tuktuk_task.token_raffle,
+        task: tuktuk_task.task,
+        task_id: args.task_id,
+        trigger_time: tuktuk_task.trigger_time,
+        crank_reward: args.crank_reward,
+    });
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct FundTukTukVault<'info> {
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TUKTUK_QUEUE_AUTHORITY_SEED],
+        bump
+    )]
+    pub queue_authority: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawTukTukVault<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [TUKTUK_QUEUE_AUTHORITY_SEED],
+        bump
+    )]
+    pub queue_authority: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RegisterTukTukTask<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TukTukTask::INIT_SPACE,
+        seeds = [b"tuktuk_task", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub tuktuk_task: Account<'info, TukTukTask>,
+
+    #[account(
+        mut,
+        seeds = [TUKTUK_QUEUE_AUTHORITY_SEED],
+        bump
+    )]
+    pub queue_authority: SystemAccount<'info>,
+
+    /// CHECK: PDA of the TukTuk program
+    #[account(
+        seeds = [b"task_queue_authority", task_queue.key().as_ref(), queue_authority.key().as_ref()],
+        seeds::program = TUKTUK_PROGRAM_ID,
+        bump
+    )]
+    pub task_queue_authority: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the TukTuk program
+    #[account(mut)]
+    pub task_queue: UncheckedAccount<'info>,
+
+    /// CHECK: created by the TukTuk program
+    #[account(mut)]
+    pub task: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: address checked
+    #[account(address = TUKTUK_PROGRAM_ID)]
+    pub tuktuk_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshTukTukTask<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [b"tuktuk_task", sol_raffle.key().as_ref()],
+        bump = tuktuk_task.bump,
+        has_one = task_queue @ RaffleError::InvalidTukTukTask
+    )]
+    pub tuktuk_task: Account<'info, TukTukTask>,
+
+    #[account(
+        mut,
+        seeds = [TUKTUK_QUEUE_AUTHORITY_SEED],
+        bump
+    )]
+    pub queue_authority: SystemAccount<'info>,
+
+    /// CHECK: PDA of the TukTuk program
+    #[account(
+        seeds = [b"task_queue_authority", task_queue.key().as_ref(), queue_authority.key().as_ref()],
+        seeds::program = TUKTUK_PROGRAM_ID,
+        bump
+    )]
+    pub task_queue_authority: UncheckedAccount<'info>,
+
+    /// CHECK: checked against tuktuk_task.task_queue
+    #[account(mut)]
+    pub task_queue: UncheckedAccount<'info>,
+
+    /// CHECK: created by the TukTuk program
+    #[account(mut)]
+    pub task: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    /// CHECK: address checked
+    #[account(address = TUKTUK_PROGRAM_ID)]
+    pub tuktuk_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct TukTukTask {
+    pub token_raffle: Pubkey, //connection with TokenRaffle struct
+    pub task_queue: Pubkey,
+    pub task: Pubkey, // last queued task
+    pub task_id: u16,
+    pub crank_reward: u64, // lamports per run, paid from the queue authority PDA
+    pub trigger_time: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TukTukTaskArgs {
+    pub task_id: u16, // free slot in the task queue
+    pub crank_reward: u64,
+    pub free_tasks: u8,
+    pub transaction: CompiledTransactionV0, // request_randomness built off-chain
+}
+
+// TukTuk queue_task_v0 argument layout
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QueueTaskArgsV0 {
+    pub id: u16,
+    pub trigger: TriggerV0,
+    pub transaction: TransactionSourceV0,
+    pub crank_reward: Option<u64>,
+    pub free_tasks: u8,
+    pub description: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TriggerV0 {
+    Now,
+    Timestamp(i64),
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum TransactionSourceV0 {
+    CompiledV0(CompiledTransactionV0),
+    RemoteV0 { url: String, signer: Pubkey },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompiledTransactionV0 {
+    pub num_rw_signers: u8,
+    pub num_ro_signers: u8,
+    pub num_rw: u8,
+    pub accounts: Vec<Pubkey>,
+    pub instructions: Vec<CompiledInstructionV0>,
+    pub signer_seeds: Vec<Vec<Vec<u8>>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompiledInstructionV0 {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+// Events
+#[event]
+pub struct TukTukTaskQueued {
+    pub token_raffle: Pubkey,
+    pub task: Pubkey,
+    pub task_id: u16,
+    pub trigger_time: i64,
+    pub crank_reward: u64,
+}
@@ -0,0 +1,425 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RoundStatus,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateRaffleState,
+};
+use crate::audit::{record_audit, AuditAction};
+use crate::blocklist::{require_not_blocked, Blocklist};
+use crate::commission::settle_sol_commission;
+use crate::daily_stats::{utc_day, DailyStats};
+use crate::raffle_stats::{record_payout, PayoutStats, RaffleStats};
+use crate::solvency::{release_in_ledger_account, require_solvent};
+
+// Only authority. A zero threshold disables vesting.
+pub fn set_vesting_config(
+    ctx: Context<UpdateRaffleState>,
+    threshold: u64,
+    cliff_seconds: i64,
+    duration_seconds: i64,
+) -> Result<()> {
+    require!(cliff_seconds >= 0, RaffleError::InvalidVestingConfig);
+    require!(duration_seconds >= cliff_seconds, RaffleError::InvalidVestingConfig);
+    require!(threshold == 0 || duration_seconds > 0, RaffleError::InvalidVestingConfig);
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    raffle_state.vesting_threshold = threshold;
+    raffle_state.vesting_cliff_seconds = cliff_seconds;
+    raffle_state.vesting_duration_seconds = duration_seconds;
+
+    emit!(VestingConfigUpdated {
+        threshold,
+        cliff_seconds,
+        duration_seconds,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::VestingConfigChanged,
+        threshold,
+    )?;
+
+    Ok(())
+}
+
+pub fn start_vesting(ctx: Context<StartVesting>, round_id: u32) -> Result<()> {
+    let raffle_state = &ctx.accounts.raffle_state;
+    require_not_blocked(raffle_state, ctx.accounts.blocklist.as_ref(), &ctx.accounts.winner.key())?;
+    let round = &mut ctx.accounts.round;
+
+    require!(
+        round.status == RoundStatus::Completed,
+        RaffleError::RoundNotCompleted
+    );
+    require!(
+        round.winner_address == Some(ctx.accounts.winner.key()),
+        RaffleError::NotTheWinner
+    );
+    require!(!round.prize_claimed, RaffleError::PrizeAlreadyClaimed);
+    require!(!round.frozen, RaffleError::RoundFrozen);
+    require!(round.staked_lamports == 0, RaffleError::PrizePoolStaked);
+    require!(
+        raffle_state.is_vested_prize(round.prize_amount),
+        RaffleError::PrizeNotSubjectToVesting
+    );
+
+    let prize_amount = round.prize_amount;
+    let commission_amount = round.commission_balance;
+    let total_required = prize_amount
+        .checked_add(commission_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    require!(
+        ctx.accounts.vault.lamports() >= total_required,
+        RaffleError::InsufficientVaultBalance
+    );
+    require_solvent(&ctx.accounts.vault_ledger, &ctx.accounts.vault, round_id)?;
+
+    // Commission is settled immediately, the prize stays in the vault until vested
+    settle_sol_commission(
+        raffle_state,
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_ledger,
+        &ctx.accounts.beneficiary,
+        &ctx.accounts.insurance_vault,
+        round.round_id,
+        commission_amount,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+    vesting.round = round.key();
+    vesting.winner = ctx.accounts.winner.key();
+    vesting.total_amount = prize_amount;
+    vesting.claimed_amount = 0;
+    vesting.start_time = now;
+    vesting.cliff_time = now
+        .checked_add(raffle_state.vesting_cliff_seconds)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    vesting.end_time = now
+        .checked_add(raffle_state.vesting_duration_seconds)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    vesting.bump = ctx.bumps.vesting;
+
+    round.prize_claimed = true;
+    round.commission_settled = true;
+
+    msg!("✅ Vesting started for round {}: {} lamports", round_id, prize_amount);
+
+    emit!(VestingStarted {
+        round_id,
+        winner: vesting.winner,
+        total_amount: vesting.total_amount,
+        cliff_time: vesting.cliff_time,
+        end_time: vesting.end_time,
+    });
+
+    Ok(())
+}
+
+pub fn claim_vested(ctx: Context<ClaimVested>, round_id: u32) -> Result<()> {
+    require!(!ctx.accounts.round.frozen, RaffleError::RoundFrozen);
+    require_not_blocked(
+        &ctx.accounts.raffle_state,
+        ctx.accounts.blocklist.as_ref(),
+        &ctx.accounts.winner.key(),
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let vesting = &mut ctx.accounts.vesting;
+
+    let claimable = vesting
+        .vested_amount(now)?
+        .checked_sub(vesting.claimed_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    require!(claimable > 0, RaffleError::NothingToClaim);
+
+    require!(
+        ctx.accounts.vault.lamports() >= claimable,
+        RaffleError::InsufficientVaultBalance
+    );
+    require_solvent(&ctx.accounts.vault_ledger, &ctx.accounts.vault, round_id)?;
+
+    **ctx.accounts.vault.try_borrow_mut_lamports()? -= claimable;
+    **ctx.accounts.winner.try_borrow_mut_lamports()? += claimable;
+    release_in_ledger_account(&ctx.accounts.vault_ledger, claimable)?;
+    record_payout(
+        PayoutStats {
+            raffle_stats: &mut ctx.accounts.raffle_stats,
+            raffle_stats_bump: ctx.bumps.raffle_stats,
+            daily_stats: &mut ctx.accounts.daily_stats,
+            daily_stats_bump: ctx.bumps.daily_stats,
+            winner_history: &ctx.accounts.winner_history,
+        },
+        &ctx.accounts.round,
+        claimable,
+        vesting.claimed_amount == 0,
+    )?;
+
+    vesting.claimed_amount = vesting
+        .claimed_amount
+        .checked_add(claimable)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("✓ Released {} vested lamports for round {}", claimable, round_id);
+
+    emit!(VestedPrizeClaimed {
+        round_id,
+        winner: vesting.winner,
+        amount: claimable,
+        claimed_amount: vesting.claimed_amount,
+        total_amount: vesting.total_amount,
+    });
+
+    if vesting.claimed_amount == vesting.total_amount {
+        ctx.accounts
+            .vesting
+            .close(ctx.accounts.winner.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct StartVesting<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + VestingAccount::INIT_SPACE,
+        seeds = [
+            b"vesting",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = beneficiary.key() == raffle_state.beneficiary @ RaffleError::InvalidBeneficiary
+    )]
+    /// CHECK: key validated via constraint
+    pub beneficiary: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance_vault"],
+        bump
+    )]
+    pub insurance_vault: SystemAccount<'info>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct ClaimVested<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"vesting",
+            round.key().as_ref()
+        ],
+        bump = vesting.bump,
+        has_one = winner @ RaffleError::NotTheWinner
+    )]
+    pub vesting: Account<'info, VestingAccount>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"sol_vault"],
+        bump
+    )]
+    /// CHECK: vault for storing SOL
+    pub vault: AccountInfo<'info>,
+
+    /// CHECK: VaultLedger PDA, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"vault_ledger"],
+        bump
+    )]
+    pub vault_ledger: UncheckedAccount<'info>,
+
+    // Lifetime prize totals, see RaffleStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + RaffleStats::INIT_SPACE,
+        seeds = [b"raffle_stats", sol_raffle.key().as_ref()],
+        bump
+    )]
+    pub raffle_stats: Account<'info, RaffleStats>,
+
+    /// CHECK: WinnerHistory PDA for the recent winners widget, may not be initialized yet
+    #[account(
+        mut,
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: UncheckedAccount<'info>,
+
+    // Today's totals of this raffle, see DailyStats
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + DailyStats::INIT_SPACE,
+        seeds = [
+            b"daily_stats",
+            sol_raffle.key().as_ref(),
+            &utc_day(Clock::get()?.unix_timestamp).to_le_bytes()
+        ],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+
+    // Required while the blocklist is enabled
+    #[account(
+        seeds = [b"blocklist"],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Option<Account<'info, Blocklist>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account data structs
+#[account]
+#[derive(InitSpace, Debug)]
+pub struct VestingAccount {
+    pub round: Pubkey, //connection with Round struct
+    pub winner: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_time: i64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+    pub bump: u8,
+}
+
+impl VestingAccount {
+    // Nothing before the cliff, then linear from start_time to end_time
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        if now < self.cliff_time {
+            return Ok(0);
+        }
+        if now >= self.end_time {
+            return Ok(self.total_amount);
+        }
+
+        let elapsed = (now - self.start_time) as u128;
+        let duration = (self.end_time - self.start_time) as u128;
+        let vested = (self.total_amount as u128)
+            .checked_mul(elapsed)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / duration;
+
+        Ok(vested as u64)
+    }
+}
+
+// Events
+#[event]
+pub struct VestingConfigUpdated {
+    pub threshold: u64,
+    pub cliff_seconds: i64,
+    pub duration_seconds: i64,
+}
+
+#[event]
+pub struct VestingStarted {
+    pub round_id: u32,
+    pub winner: Pubkey,
+    pub total_amount: u64,
+    pub cliff_time: i64,
+    pub end_time: i64,
+}
+
+#[event]
+pub struct VestedPrizeClaimed {
+    pub round_id: u32,
+    pub winner: Pubkey,
+    pub amount: u64,
+    pub claimed_amount: u64,
+    pub total_amount: u64,
+}
@@ -1,15 +1,12 @@
 use anchor_lang::prelude::*;
 use orao_solana_vrf_cb::{
-    cpi,
-    program::OraoVrfCb,
     state::{
-        client::{Callback, Client, RemainingAccount},
+        client::Client,
         network_state::NetworkState,
         request::RequestAccount,
     },
-    RequestParams, CB_CLIENT_ACCOUNT_SEED, CB_CONFIG_ACCOUNT_SEED, CB_REQUEST_ACCOUNT_SEED,
+    CB_CLIENT_ACCOUNT_SEED, CB_CONFIG_ACCOUNT_SEED, CB_REQUEST_ACCOUNT_SEED,
 };
-use bytemuck::Pod;
 use crate::{
     Round,
     TokenRaffle,
@@ -17,9 +14,20 @@ use crate::{
     ClientState,
     RoundTickets,
     RaffleError,
+    RaffleState,
+    UpdateRaffleState,
     CLIENT_STATE_SEED,
-    change_round_status
 };
+use crate::audit::{record_audit, AuditAction, AuditLog};
+use crate::commission::accrue_round_commission;
+use crate::lifecycle::transition_round;
+use crate::house_ticket::{is_house_ticket, roll_house_win};
+use crate::jackpot::try_award_jackpot;
+use crate::migration::Versioned;
+use crate::prize_limits::pass_on_carryover;
+use crate::queue::load_pending_queue_mut;
+use crate::redraw::{drawable_tickets, skip_excluded_range};
+use crate::solvency::{record_in_ledger_account, release_in_ledger_account};
 
 pub fn initialize_client_state(ctx: Context<InitializeClientState>) -> Result<()> {
     let client_state = &mut ctx.accounts.client_state;
@@ -35,6 +43,89 @@ pub fn initialize_client_state(ctx: Context<InitializeClientState>) -> Result<()
     Ok(())
 }
 
+// Only authority. cooldown 0 - no cooldown, max_requests 0 - unlimited retries
+pub fn set_randomness_rate_limit(
+    ctx: Context<UpdateRaffleState>,
+    cooldown_seconds: i64,
+    max_requests: u8,
+) -> Result<()> {
+    require!(cooldown_seconds >= 0, RaffleError::InvalidAmount);
+
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    raffle_state.randomness_cooldown_seconds = cooldown_seconds;
+    raffle_state.max_randomness_requests = max_requests;
+
+    emit!(RandomnessRateLimitUpdated {
+        cooldown_seconds,
+        max_requests,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::RandomnessRateLimitChanged,
+        cooldown_seconds as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority. Allows a round, or a pending redraw, that ran out of retries to be requested again
+pub fn reset_randomness_requests(ctx: Context<ResetRandomnessRequests>, round_id: u32) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    require!(
+        !round.status.is_final() || round.redraw_pending,
+        RaffleError::CannotChangeCompletedRound
+    );
+
+    round.request_count = 0;
+    round.last_request_time = 0;
+
+    msg!("✅ Randomness requests reset for round {}", round_id);
+
+    emit!(RandomnessRequestsReset { round_id });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::RandomnessRequestsReset,
+        round_id as u64,
+    )?;
+
+    Ok(())
+}
+
+// Enforces cooldown and retry limit, then records the request on the round
+pub fn record_randomness_request(raffle_state: &RaffleState, round: &mut Round, now: i64) -> Result<()> {
+    if round.request_count > 0 {
+        require!(
+            now >= round.last_request_time.saturating_add(raffle_state.randomness_cooldown_seconds),
+            RaffleError::RandomnessCooldown
+        );
+    }
+    require!(
+        raffle_state.max_randomness_requests == 0
+            || round.request_count < raffle_state.max_randomness_requests,
+        RaffleError::RandomnessRetriesExhausted
+    );
+
+    round.last_request_time = now;
+    round.request_count = round.request_count.saturating_add(1);
+
+    Ok(())
+}
+
+// Non-failing variant for tick. A zero cooldown means tick never retries, explicit request_randomness only
+pub fn is_randomness_request_due(raffle_state: &RaffleState, round: &Round, now: i64) -> bool {
+    let cooled_down = round.request_count == 0
+        || (raffle_state.randomness_cooldown_seconds > 0
+            && now >= round.last_request_time.saturating_add(raffle_state.randomness_cooldown_seconds));
+
+    cooled_down
+        && (raffle_state.max_randomness_requests == 0
+            || round.request_count < raffle_state.max_randomness_requests)
+}
+
 pub fn consume_randomness(
     ctx: Context<ConsumeRandomness>,
     round_id: u32
@@ -47,14 +138,17 @@ pub fn consume_randomness(
         .randomness;
     msg!("randomness: {:?}", randomness);
 
-    let random_number = u64::from_le_bytes(randomness[0..8].try_into().unwrap());
-    msg!("random_number: {}", random_number);
-
     let sol_raffle = &mut ctx.accounts.sol_raffle;
     msg!("Sol_raffle: {:?}", sol_raffle);
     let round = &mut ctx.accounts.round;
     msg!("Round: {:?}", round);
 
+    // Frozen rounds stay pending and are drawn again after unfreeze
+    if round.frozen {
+        msg!("🧊 Round {} is frozen, skipping draw", round_id);
+        return Ok(());
+    }
+
     let round_pubkey = round.key();
     let round_tickets_seeds = &[
         b"round_tickets",
@@ -69,9 +163,59 @@ pub fn consume_randomness(
         .find(|acc| acc.key == &round_tickets_pubkey)
         .ok_or(RaffleError::NotEnoughRemainingAccounts)?;
 
-    pick_winner(sol_raffle, round, round_tickets_info, random_number)?;
+    let redraw = round.redraw_pending;
+    let drawn = pick_winner(sol_raffle, round, round_tickets_info, randomness, ctx.accounts.request.key())?;
+
+    // Secondary draw on an independent part of the randomness
+    let (mega_jackpot_pubkey, _) = Pubkey::find_program_address(&[b"mega_jackpot"], &crate::id());
+    let (sol_vault_pubkey, _) = Pubkey::find_program_address(&[b"sol_vault"], &crate::id());
+    let (vault_ledger_pubkey, _) = Pubkey::find_program_address(&[b"vault_ledger"], &crate::id());
+
+    let mega_jackpot_info = ctx.remaining_accounts.iter().find(|acc| acc.key == &mega_jackpot_pubkey);
+    let sol_vault_info = ctx.remaining_accounts.iter().find(|acc| acc.key == &sol_vault_pubkey);
+    let vault_ledger_info = ctx.remaining_accounts.iter().find(|acc| acc.key == &vault_ledger_pubkey);
 
-    sol_raffle.pending_rounds.retain(|&id| id != round_id);
+    // Only the first settlement draws the jackpot: a replayed callback finds the round completed,
+    // a redraw keeps the jackpot settled with the first winner
+    if let (Some(mega_jackpot_info), Some(sol_vault_info)) = (mega_jackpot_info.filter(|_| drawn && !redraw), sol_vault_info) {
+        let jackpot_number = u64::from_le_bytes(randomness[8..16].try_into().unwrap());
+        let awarded = try_award_jackpot(mega_jackpot_info, sol_vault_info, round, jackpot_number)?;
+
+        // Requests submitted before the ledger existed don't carry it
+        if let Some(vault_ledger_info) = vault_ledger_info.filter(|_| awarded > 0) {
+            record_in_ledger_account(vault_ledger_info, awarded)?;
+        }
+    }
+
+    if drawn && round.winner_ticket_index.is_some_and(|index| is_house_ticket(round, index)) {
+        let moved = roll_house_win(sol_raffle, round, mega_jackpot_info, sol_vault_info, vault_ledger_info)?;
+
+        if let Some(vault_ledger_info) = vault_ledger_info.filter(|_| moved > 0) {
+            release_in_ledger_account(vault_ledger_info, moved)?;
+        }
+    }
+
+    let (commission_vault_pubkey, _) = Pubkey::find_program_address(&[b"commission_vault"], &crate::id());
+    let commission_vault_info = ctx.remaining_accounts.iter().find(|acc| acc.key == &commission_vault_pubkey);
+
+    if let (Some(commission_vault_info), Some(sol_vault_info)) = (commission_vault_info, sol_vault_info) {
+        let moved = accrue_round_commission(commission_vault_info, sol_vault_info, round)?;
+
+        if let Some(vault_ledger_info) = vault_ledger_info.filter(|_| moved > 0) {
+            release_in_ledger_account(vault_ledger_info, moved)?;
+        }
+    }
+
+    let (pending_queue_pubkey, _) = Pubkey::find_program_address(
+        &[b"pending_queue", sol_raffle.key().as_ref()],
+        &crate::id(),
+    );
+    let pending_queue_info = ctx.remaining_accounts
+        .iter()
+        .find(|acc| acc.key == &pending_queue_pubkey)
+        .ok_or(RaffleError::NotEnoughRemainingAccounts)?;
+
+    load_pending_queue_mut(pending_queue_info)?.remove(round_id);
     
     Ok(())
 }
@@ -80,11 +224,12 @@ fn pick_winner(
     sol_raffle: &mut Account<TokenRaffle>,
     round: &mut Account<Round>,
     round_tickets_info: &AccountInfo,
-    random_number: u64,
-) -> Result<()> {
-    if round.status == RoundStatus::Completed {
+    randomness: [u8; 64],
+    vrf_request: Pubkey,
+) -> Result<bool> {
+    if round.status == RoundStatus::Completed && !round.redraw_pending {
         msg!("⚠️ Round {} already completed, skipping pick_winner", round.round_id);
-        return Ok(());
+        return Ok(false);
     }
 
     // Ensure the account has enough allocated space to safely read or modify its content
@@ -95,39 +240,123 @@ fn pick_winner(
     );
     
     let round_tickets: &RoundTickets = bytemuck::from_bytes(&data[8..]); // Skip 8-byte discriminator
-    
-    let tickets = round_tickets.get_tickets();
-    msg!("tickets_length: {}", tickets.len());
-    
-    let winner_ticket_index = (random_number % round.total_tickets as u64) as u32;
+    round_tickets.check_version()?;
+    msg!("tickets_length: {}", round_tickets.len);
+
+    // Nothing may change the pool between lock and draw. Rounds drawing since before locking
+    // existed have no locked totals
+    require!(
+        round.locked_at == 0 || round.total_tickets == round.locked_total_tickets,
+        RaffleError::RoundChangedAfterLock
+    );
+
+    let random_number = u64::from_le_bytes(randomness[0..8].try_into().unwrap());
+    msg!("random_number: {}", random_number);
+
+    // A redraw leaves the first winner's tickets out of the pool
+    let drawn_tickets = drawable_tickets(round_tickets, round)?;
+    require!(drawn_tickets > 0, RaffleError::NoTicketsToDraw);
+    let winner_ticket_index = skip_excluded_range(
+        round_tickets,
+        round,
+        (random_number % drawn_tickets as u64) as u32,
+    );
     msg!("Winner ticket index: {}", winner_ticket_index);
 
-    let purchase_index = tickets.partition_point(|&c| c <= winner_ticket_index);
+    let purchases = round_tickets.len as usize;
+    // Rounds past one page resolve the purchase in finalize_draw
+    let purchase_index = if is_house_ticket(round, winner_ticket_index) {
+        Some(purchases)
+    } else if purchases <= RoundTickets::PAGE_SIZE {
+        let purchase_index = round_tickets.partition_point(|c| c <= winner_ticket_index);
+        if purchase_index >= purchases {
+            msg!("ERROR: winner_ticket_number {} not found", winner_ticket_index);
+            return Err(RaffleError::TicketNotFound.into());
+        }
+        Some(purchase_index)
+    } else {
+        None
+    };
+
+    round.winner_purchase_index = purchase_index.map(|index| index as u32);
+    round.winner_ticket_index = Some(winner_ticket_index);
+    round.randomness = randomness;
+    round.vrf_request = vrf_request;
+
+    let clock = Clock::get()?;
 
-    if purchase_index >= tickets.len() {
-        msg!("ERROR: winner_ticket_number {} not found", winner_ticket_index);
-        return Err(RaffleError::TicketNotFound.into());
+    if round.redraw_pending {
+        round.redraw_pending = false;
+    } else {
+        transition_round(sol_raffle, round, RoundStatus::Completed)?;
+        pass_on_carryover(sol_raffle, round.carryover_out)?;
     }
+    round.completed_at = clock.unix_timestamp;
 
+    if round.winner_purchase_index.is_some() {
+        emit_winner_picked(sol_raffle, round, clock.unix_timestamp);
+    } else {
+        msg!("📄 Round {} has {} purchases, winner left to finalize_draw", round.round_id, purchases);
+
+        emit!(DrawDeferred {
+            round_id: round.round_id,
+            winner_ticket_index,
+            pages: purchases.div_ceil(RoundTickets::PAGE_SIZE) as u32,
+        });
+    }
+
+    Ok(true)
+}
+
+// Permissionless crank for rounds drawn without a winning purchase. page_hint is the
+// RoundTickets page holding winner_ticket_index, a wrong page fails without changes so
+// the crank can move on to the next one
+pub fn finalize_draw(ctx: Context<FinalizeDraw>, round_id: u32, page_hint: u32) -> Result<()> {
+    let round = &mut ctx.accounts.round;
+    round.check_version()?;
+
+    require!(
+        round.status == RoundStatus::Completed && !round.redraw_pending,
+        RaffleError::RoundNotCompleted
+    );
+    require!(round.winner_purchase_index.is_none(), RaffleError::WinnerAlreadySet);
+    let winner_ticket_index = round.winner_ticket_index.ok_or(RaffleError::RoundNotCompleted)?;
+
+    let round_tickets = ctx.accounts.round_tickets.load()?;
+    round_tickets.check_version()?;
+
+    let (start, end) = round_tickets
+        .page_range(page_hint as usize)
+        .ok_or(RaffleError::InvalidPageHint)?;
+    let first_ticket = if start == 0 { 0 } else { round_tickets.get_ticket_at(start - 1) };
+    let end_ticket = round_tickets.get_ticket_at(end - 1);
+
+    if winner_ticket_index < first_ticket || winner_ticket_index >= end_ticket {
+        msg!("Winner ticket {} is not on page {}", winner_ticket_index, page_hint);
+        return err!(RaffleError::InvalidPageHint);
+    }
+
+    let purchase_index = round_tickets.partition_point_in(start, end, |c| c <= winner_ticket_index);
     round.winner_purchase_index = Some(purchase_index as u32);
-    round.winner_ticket_index = Some(winner_ticket_index);
 
-    let res = change_round_status(sol_raffle, round, RoundStatus::Completed)?;
-    msg!("change_round_status res: {:?}", res);
+    msg!("✅ Round {} winner resolved on page {}", round_id, page_hint);
 
-    let clock = Clock::get()?;
+    emit_winner_picked(&ctx.accounts.sol_raffle, round, Clock::get()?.unix_timestamp);
+
+    Ok(())
+}
 
+fn emit_winner_picked(sol_raffle: &TokenRaffle, round: &Account<Round>, timestamp: i64) {
     emit!(WinnerPicked {
         token: sol_raffle.token_mint,
         round: round.key(),
         round_id: round.round_id,
-        winner_purchase_index: purchase_index as u32,
-        winner_ticket_index: winner_ticket_index,
+        winner_purchase_index: round.winner_purchase_index.unwrap_or_default(),
+        winner_ticket_index: round.winner_ticket_index.unwrap_or_default(),
         prize_amount: round.prize_amount,
-        timestamp: clock.unix_timestamp,
+        carryover: round.carryover_out,
+        timestamp,
     });
-
-    Ok(())
 }
 
 // Account validation structs
@@ -182,7 +411,7 @@ pub struct ConsumeRandomness<'info> {
 
     #[account(
         mut,
-        seeds = [b"sol_raffle"],
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
         bump = sol_raffle.bump
     )]
     pub sol_raffle: Account<'info, TokenRaffle>,
@@ -199,7 +428,92 @@ pub struct ConsumeRandomness<'info> {
     pub round: Account<'info, Round>,
 }
 
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct FinalizeDraw<'info> {
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    #[account(
+        seeds = [
+            b"round_tickets",
+            round.key().as_ref()
+        ],
+        bump
+    )]
+    pub round_tickets: AccountLoader<'info, RoundTickets>,
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct ResetRandomnessRequests<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
 // Events
+#[event]
+pub struct RandomnessRateLimitUpdated {
+    pub cooldown_seconds: i64,
+    pub max_requests: u8,
+}
+
+#[event]
+pub struct RandomnessRequestsReset {
+    pub round_id: u32,
+}
+
+#[event]
+pub struct DrawDeferred {
+    pub round_id: u32,
+    pub winner_ticket_index: u32,
+    pub pages: u32, // RoundTickets pages finalize_draw may need to look at
+}
+
 #[event]
 pub struct WinnerPicked {
     pub token: Pubkey,
@@ -208,5 +522,6 @@ pub struct WinnerPicked {
     pub winner_purchase_index: u32,
     pub winner_ticket_index: u32,
     pub prize_amount: u64,
+    pub carryover: u64, // prize above the cap, goes to the next round
     pub timestamp: i64,
 }
\ No newline at end of file
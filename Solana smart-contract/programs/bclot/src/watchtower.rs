@@ -0,0 +1,144 @@
+use anchor_lang::prelude::*;
+use crate::{
+    Round,
+    RaffleError,
+    RaffleState,
+    TokenRaffle,
+    UpdateRaffleState,
+};
+use crate::audit::{record_audit, AuditAction, AuditLog};
+
+// Only authority. Pubkey::default() removes the watchtower
+pub fn set_watchtower(ctx: Context<UpdateRaffleState>, watchtower: Pubkey) -> Result<()> {
+    let raffle_state = &mut ctx.accounts.raffle_state;
+    let old_watchtower = raffle_state.watchtower;
+    raffle_state.watchtower = watchtower;
+
+    emit!(WatchtowerChanged {
+        old_watchtower,
+        new_watchtower: watchtower,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.authority.key(),
+        AuditAction::WatchtowerChanged,
+        0,
+    )?;
+
+    Ok(())
+}
+
+// Watchtower or authority. A frozen round can't be drawn or claimed
+pub fn freeze_round(ctx: Context<FreezeRound>, round_id: u32) -> Result<()> {
+    require!(
+        ctx.accounts.raffle_state.is_watchtower(&ctx.accounts.signer.key()),
+        RaffleError::Unauthorized
+    );
+
+    let round = &mut ctx.accounts.round;
+    require!(!round.frozen, RaffleError::RoundFrozen);
+    round.frozen = true;
+
+    msg!("🧊 Round {} frozen", round_id);
+
+    emit!(RoundFrozen {
+        round_id,
+        by: ctx.accounts.signer.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.signer.key(),
+        AuditAction::RoundFrozen,
+        round_id as u64,
+    )?;
+
+    Ok(())
+}
+
+// Only authority
+pub fn unfreeze_round(ctx: Context<FreezeRound>, round_id: u32) -> Result<()> {
+    require!(
+        ctx.accounts.signer.key() == ctx.accounts.raffle_state.authority,
+        RaffleError::Unauthorized
+    );
+
+    let round = &mut ctx.accounts.round;
+    require!(round.frozen, RaffleError::RoundNotFrozen);
+    round.frozen = false;
+
+    msg!("✅ Round {} unfrozen", round_id);
+
+    emit!(RoundUnfrozen {
+        round_id,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    record_audit(
+        &ctx.accounts.audit_log,
+        ctx.accounts.signer.key(),
+        AuditAction::RoundUnfrozen,
+        round_id as u64,
+    )?;
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+#[instruction(round_id: u32)]
+pub struct FreezeRound<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        seeds = [b"sol_raffle", &sol_raffle.cadence_id.to_le_bytes()],
+        bump = sol_raffle.bump
+    )]
+    pub sol_raffle: Account<'info, TokenRaffle>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"round",
+            sol_raffle.key().as_ref(),
+            &round_id.to_le_bytes()
+        ],
+        bump = round.bump
+    )]
+    pub round: Account<'info, Round>,
+
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"audit_log"],
+        bump = audit_log.load()?.bump
+    )]
+    pub audit_log: AccountLoader<'info, AuditLog>,
+}
+
+// Events
+#[event]
+pub struct WatchtowerChanged {
+    pub old_watchtower: Pubkey,
+    pub new_watchtower: Pubkey,
+}
+
+#[event]
+pub struct RoundFrozen {
+    pub round_id: u32,
+    pub by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RoundUnfrozen {
+    pub round_id: u32,
+    pub timestamp: i64,
+}
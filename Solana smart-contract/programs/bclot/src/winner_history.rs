@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::{
+    RaffleError,
+    RaffleState,
+    Round,
+};
+
+pub const WINNER_HISTORY_CAPACITY: usize = 100;
+// Return data is capped at 1024 bytes
+pub const MAX_WINNER_VIEW_ENTRIES: u8 = 10;
+
+// Only authority
+pub fn initialize_winner_history(ctx: Context<InitializeWinnerHistory>) -> Result<()> {
+    let mut winner_history = ctx.accounts.winner_history.load_init()?;
+    winner_history.bump = ctx.bumps.winner_history;
+
+    msg!("✅ Winner history initialized");
+
+    Ok(())
+}
+
+// Winners with seq >= from_seq, oldest first. Winners older than the last
+// WINNER_HISTORY_CAPACITY are overwritten
+pub fn get_winner_history(ctx: Context<GetWinnerHistory>, from_seq: u64, limit: u8) -> Result<Vec<WinnerEntryView>> {
+    let winner_history = ctx.accounts.winner_history.load()?;
+
+    let start = from_seq.max(winner_history.oldest_seq());
+    let end = winner_history
+        .total
+        .min(start.saturating_add(limit.min(MAX_WINNER_VIEW_ENTRIES) as u64));
+
+    Ok((start..end)
+        .map(|seq| {
+            let entry = &winner_history.entries[(seq % WINNER_HISTORY_CAPACITY as u64) as usize];
+            WinnerEntryView {
+                seq,
+                token_raffle: entry.token_raffle,
+                round_id: entry.round_id,
+                winner: entry.winner,
+                prize_amount: entry.prize_amount,
+                timestamp: entry.timestamp,
+            }
+        })
+        .collect())
+}
+
+impl WinnerHistory {
+    pub fn oldest_seq(&self) -> u64 {
+        self.total.saturating_sub(WINNER_HISTORY_CAPACITY as u64)
+    }
+
+    // Overwrites the oldest entry once full
+    pub fn append(&mut self, round: &Round, winner: Pubkey, timestamp: i64) {
+        self.entries[(self.total % WINNER_HISTORY_CAPACITY as u64) as usize] = WinnerEntry {
+            token_raffle: round.token_raffle,
+            winner,
+            prize_amount: round.prize_amount,
+            timestamp,
+            round_id: round.round_id,
+            padding: [0; 4],
+        };
+        self.total += 1;
+    }
+}
+
+// Called by record_payout on a prize's first payment, skipped before the history is
+// initialized. House wins pay no prize and are left out
+pub fn record_winner(winner_history_info: &AccountInfo, round: &Round) -> Result<()> {
+    let Some(winner) = round.winner_address else {
+        return Ok(());
+    };
+    if round.prize_amount == 0
+        || winner_history_info.data_is_empty()
+        || winner_history_info.owner != &crate::id()
+    {
+        return Ok(());
+    }
+
+    let mut data = winner_history_info.try_borrow_mut_data()?;
+    require!(
+        data.len() >= 8 + std::mem::size_of::<WinnerHistory>(),
+        RaffleError::InvalidAccountSize
+    );
+    require!(
+        data[..8] == *WinnerHistory::DISCRIMINATOR,
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    let winner_history: &mut WinnerHistory =
+        bytemuck::from_bytes_mut(&mut data[8..8 + std::mem::size_of::<WinnerHistory>()]);
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    winner_history.append(round, winner, timestamp);
+
+    Ok(())
+}
+
+// Account validation structs
+#[derive(Accounts)]
+pub struct InitializeWinnerHistory<'info> {
+    #[account(
+        seeds = [b"raffle_state"],
+        bump = raffle_state.bump,
+        has_one = authority @ RaffleError::Unauthorized
+    )]
+    pub raffle_state: Account<'info, RaffleState>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<WinnerHistory>(),
+        seeds = [b"winner_history"],
+        bump
+    )]
+    pub winner_history: AccountLoader<'info, WinnerHistory>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct GetWinnerHistory<'info> {
+    #[account(
+        seeds = [b"winner_history"],
+        bump = winner_history.load()?.bump
+    )]
+    pub winner_history: AccountLoader<'info, WinnerHistory>,
+}
+
+// Account data structs
+#[zero_copy]
+#[derive(Debug)]
+pub struct WinnerEntry {
+    pub token_raffle: Pubkey,
+    pub winner: Pubkey,
+    pub prize_amount: u64,
+    pub timestamp: i64, // first payment time
+    pub round_id: u32,
+    pub padding: [u8; 4],
+}
+
+// Latest paid winners across every raffle, for the recent winners widget
+#[account(zero_copy)]
+#[derive(Debug)]
+pub struct WinnerHistory {
+    pub entries: [WinnerEntry; WINNER_HISTORY_CAPACITY], // ring buffer, winner seq lives at seq % capacity
+    pub total: u64, // winners ever recorded, the next seq
+    pub bump: u8,
+    pub padding: [u8; 7],
+}
+
+// View data structures
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WinnerEntryView {
+    pub seq: u64,
+    pub token_raffle: Pubkey,
+    pub round_id: u32,
+    pub winner: Pubkey,
+    pub prize_amount: u64,
+    pub timestamp: i64,
+}
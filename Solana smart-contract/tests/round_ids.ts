@@ -0,0 +1,153 @@
+// tests/round_ids.ts - Round id sequencing and rollover at the round boundary
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Raffle } from "../target/types/raffle";
+import { assert } from "chai";
+
+describe("Round id sequencing", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.Raffle as Program<Raffle>;
+  const authority = provider.wallet.publicKey;
+
+  // Dedicated cadence with short rounds so the boundary is reached quickly
+  const cadenceId = 42;
+  const roundDuration = 2;
+
+  const pda = (seeds: Buffer[]) =>
+    anchor.web3.PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const raffleState = pda([Buffer.from("raffle_state")]);
+  const solVault = pda([Buffer.from("sol_vault")]);
+  const rentVault = pda([Buffer.from("rent_vault")]);
+  const solRaffle = pda([Buffer.from("sol_raffle"), Buffer.from([cadenceId])]);
+  const pendingQueue = pda([Buffer.from("pending_queue"), solRaffle.toBuffer()]);
+
+  const roundPda = (roundId: number) => {
+    const id = Buffer.alloc(4);
+    id.writeUInt32LE(roundId);
+    return pda([Buffer.from("round"), solRaffle.toBuffer(), id]);
+  };
+
+  const rollover = (prev: number, next: number, keeper: anchor.web3.Keypair) => {
+    const nextRound = roundPda(next);
+    return program.methods
+      .rollover(prev, next)
+      .accountsPartial({
+        raffleState,
+        solRaffle,
+        pendingQueue,
+        nextRound,
+        nextRoundTickets: pda([Buffer.from("round_tickets"), nextRound.toBuffer()]),
+        rentVault,
+        keeper: keeper.publicKey,
+        keeperRegistry: null,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([keeper])
+      .rpc();
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      assert.fail(`Should have thrown ${code}`);
+    } catch (error) {
+      assert.include(String(error), code);
+    }
+  };
+
+  const waitForRoundEnd = async () => {
+    const raffle = await program.account.tokenRaffle.fetch(solRaffle);
+    const endTime = raffle.currentRoundEndTime!.toNumber();
+
+    for (;;) {
+      const slot = await provider.connection.getSlot();
+      const blockTime = await provider.connection.getBlockTime(slot);
+      if (blockTime !== null && blockTime >= endTime) {
+        return;
+      }
+      await new Promise((resolve) => setTimeout(resolve, 500));
+    }
+  };
+
+  before(async () => {
+    const existing = await provider.connection.getAccountInfo(raffleState);
+    if (existing === null) {
+      await program.methods
+        .initializeRaffle(5, authority)
+        .accountsPartial({
+          raffleState,
+          authority,
+          vault: solVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+    }
+
+    await program.methods
+      .initializeSolRaffle(cadenceId, new anchor.BN(roundDuration))
+      .accountsPartial({
+        authority,
+        solRaffle,
+        pendingQueue,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    await program.methods
+      .fundRentVault(new anchor.BN(anchor.web3.LAMPORTS_PER_SOL))
+      .accountsPartial({
+        rentVault,
+        funder: authority,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("Should reject a first round other than 0", async () => {
+    await expectError(rollover(0, 1, anchor.web3.Keypair.generate()), "RoundIdNotSequential");
+  });
+
+  it("Should open round 0", async () => {
+    await rollover(0, 0, anchor.web3.Keypair.generate());
+
+    const raffle = await program.account.tokenRaffle.fetch(solRaffle);
+    assert.equal(raffle.currentRoundId, 0);
+    assert.equal(raffle.totalRounds, 1);
+  });
+
+  it("Should reject rollover before the round ends", async () => {
+    await expectError(rollover(0, 1, anchor.web3.Keypair.generate()), "RoundNotEndedYet");
+  });
+
+  it("Should open exactly one next round when keepers race at the boundary", async () => {
+    await waitForRoundEnd();
+
+    const results = await Promise.allSettled([
+      rollover(0, 1, anchor.web3.Keypair.generate()),
+      rollover(0, 1, anchor.web3.Keypair.generate()),
+    ]);
+
+    assert.equal(results.filter((r) => r.status === "fulfilled").length, 1);
+
+    const raffle = await program.account.tokenRaffle.fetch(solRaffle);
+    assert.equal(raffle.currentRoundId, 1);
+    assert.equal(raffle.totalRounds, 2);
+
+    const queue = await program.account.pendingQueue.fetch(pendingQueue);
+    assert.equal(queue.len, 1);
+    assert.equal(queue.rounds[queue.head], 0);
+  });
+
+  it("Should reject skipping a round id", async () => {
+    await waitForRoundEnd();
+
+    await expectError(rollover(1, 3, anchor.web3.Keypair.generate()), "RoundIdNotSequential");
+  });
+
+  it("Should reject a stale previous round", async () => {
+    await expectError(rollover(0, 2, anchor.web3.Keypair.generate()), "RoundNotAvailable");
+  });
+});